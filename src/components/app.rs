@@ -4,8 +4,108 @@ use web_sys::HtmlInputElement;
 use yew::events::{MouseEvent, SubmitEvent};
 use yew::prelude::*;
 
-use crate::services::{JsonGenerator, JsonParser, OpenAiService, ValidationService};
-use crate::types::{DataType, DocumentType, Index, Property, ValidationError};
+use crate::services::{
+    AiRequestHandle, AiResponseError, ChangeKind, ChangeTarget, ComparisonRow, ContractChange,
+    ContractComparison, ContractDiffService, ContractFingerprint, ContractImport, ContractMerge,
+    DescriptionService, DescriptionSuggestion, DiagramFormat, DiagramService, DocsGenerator,
+    EditScope, GalleryService, JsonGenerator, JsonParser, MergeSide, OfflineGenerator,
+    OpenAiService, PromptLibrary, RustGenerator, ScopedEditService, SourceLocations,
+    TypeScriptGenerator, ValidationService,
+};
+use crate::types::{
+    ChatMessage, ChatRole, Conversation, DataType, Diagnostic, DocumentType, Index, KeyOrder,
+    OutputFormat, PathStep, Property, PromptPart, PromptTemplate, PropertyOrder, ValidationError,
+    MAX_INDENT,
+};
+use crate::utils::{
+    diff_lines, download_text, with_context, DiffLine, DEFAULT_AI_REPAIR_ROUNDS,
+    MAX_AI_REPAIR_ROUNDS,
+};
+
+/// Tabs of the output section
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputTab {
+    Json,
+    Rust,
+    TypeScript,
+    Docs,
+    Diagram,
+    Compare,
+    Merge,
+}
+
+impl OutputTab {
+    /// Returns all tabs in display order
+    pub fn all() -> Vec<OutputTab> {
+        vec![
+            OutputTab::Json,
+            OutputTab::Rust,
+            OutputTab::TypeScript,
+            OutputTab::Docs,
+            OutputTab::Diagram,
+            OutputTab::Compare,
+            OutputTab::Merge,
+        ]
+    }
+
+    /// Returns the tab label
+    pub fn label(&self) -> &'static str {
+        match self {
+            OutputTab::Json => "JSON",
+            OutputTab::Rust => "Rust",
+            OutputTab::TypeScript => "TypeScript",
+            OutputTab::Docs => "Docs",
+            OutputTab::Diagram => "Diagram",
+            OutputTab::Compare => "Compare",
+            OutputTab::Merge => "Merge",
+        }
+    }
+
+    /// Returns the heading shown above the tab content
+    pub fn title(&self) -> &'static str {
+        match self {
+            OutputTab::Json => "Contract",
+            OutputTab::Rust => "Rust types",
+            OutputTab::TypeScript => "TypeScript types",
+            OutputTab::Docs => "Documentation",
+            OutputTab::Diagram => "Diagram",
+            OutputTab::Compare => "Compare contracts",
+            OutputTab::Merge => "Merge drafts",
+        }
+    }
+}
+
+/// An AI request in flight, kept so repair rounds can continue the same exchange
+pub struct AiRequest {
+    /// Prompt as typed by the user
+    pub prompt: String,
+    /// User message content sent for the prompt
+    pub request: String,
+    /// All messages sent so far, including repair rounds
+    pub messages: Vec<ChatMessage>,
+    /// Contract JSON from the previous repair round
+    pub last_schema: Option<String>,
+    /// Document type or property the request is limited to
+    pub scope: Option<EditScope>,
+    /// Prompt template selected when the request was made, also used for repair rounds
+    pub template: PromptTemplate,
+}
+
+/// One attempt by the AI to fix validation errors in its previous output
+pub struct RepairRound {
+    /// Errors the model was asked to fix
+    pub errors: Vec<ValidationError>,
+    /// Changes the model made, or `None` while waiting for its reply
+    pub diff: Option<Vec<DiffLine>>,
+}
+
+/// AI-proposed contract waiting for the user to accept or reject each change
+pub struct PendingReview {
+    pub proposed: Vec<DocumentType>,
+    pub changes: Vec<ContractChange>,
+    /// Whether each change in `changes` will be applied
+    pub accepted: Vec<bool>,
+}
 
 /// Main application state
 pub struct App {
@@ -27,26 +127,119 @@ pub struct App {
     /// AI error messages
     ai_errors: Vec<String>,
 
-    /// Prompt history
-    prompt_history: Vec<String>,
+    /// Raw model output behind the latest AI error, if any
+    ai_raw_response: Option<String>,
+
+    /// AI conversation turns, which can branch from any earlier turn
+    conversation: Conversation,
+
+    /// Turn whose resulting contract is being previewed
+    viewed_turn: Option<usize>,
+
+    /// AI request in progress
+    ai_request: Option<AiRequest>,
+
+    /// Handle for cancelling the AI request in progress
+    ai_handle: Option<AiRequestHandle>,
+
+    /// Partial model output streamed so far, shown while loading
+    ai_stream_preview: String,
+
+    /// Maximum rounds the AI may take to fix validation errors
+    ai_repair_rounds_max: u32,
+
+    /// Repair rounds of the latest AI request
+    repair_rounds: Vec<RepairRound>,
+
+    /// Validation errors left in the latest AI proposal
+    ai_remaining_errors: Vec<ValidationError>,
+
+    /// AI changes waiting for review
+    pending_review: Option<PendingReview>,
+
+    /// Document type or property targeted by the open "Ask AI" popup
+    ai_edit_scope: Option<EditScope>,
+
+    /// Instruction typed into the "Ask AI" popup
+    ai_edit_instruction: String,
+
+    /// AI-suggested descriptions for undocumented fields, editable before acceptance
+    description_suggestions: Vec<DescriptionSuggestion>,
+
+    /// Prompt templates, saved in local storage
+    prompt_library: PromptLibrary,
+
+    /// Template being edited, not yet saved to the library
+    prompt_draft: Option<PromptTemplate>,
+
+    /// Messages of the most recent AI request, shown for debugging
+    ai_sent_messages: Vec<ChatMessage>,
+
+    /// Recorded response of the most recent AI request, replayable as a test fixture
+    ai_recording: String,
 
     /// Import JSON text
     import_json: String,
 
-    /// Whether to show formatted JSON
-    show_formatted: bool,
+    /// Problems found in the import text, underlined while the import fails
+    import_diagnostics: Vec<Diagnostic>,
+
+    /// How the latest import was read, when it was not a Dash contract in strict JSON
+    import_summary: Option<String>,
+
+    /// JSON converted from the import text, which the diagnostics refer to if set
+    import_normalized: Option<String>,
+
+    /// Layer behind the import textarea that draws the underlines
+    import_highlights: NodeRef,
+
+    /// Layout of the contract JSON, saved in local storage
+    output_format: OutputFormat,
 
     /// Whether validation has been explicitly requested
     validation_requested: bool,
 
     /// Whether the compact JSON popup is visible
     show_compact_popup: bool,
+
+    /// Contract JSON on the left of the comparison
+    compare_left: String,
+
+    /// Contract JSON on the right of the comparison
+    compare_right: String,
+
+    /// Contract JSON of the common base of a three-way merge
+    merge_base: String,
+
+    /// Contract JSON of our draft
+    merge_ours: String,
+
+    /// Contract JSON of their draft
+    merge_theirs: String,
+
+    /// Merge of the drafts, with its conflicts and their resolutions
+    merge: Option<ContractMerge>,
+
+    /// Why the drafts could not be merged
+    merge_error: Option<String>,
+
+    /// Validation errors of the merged contract, which must be fixed before accepting it
+    merge_validation: Vec<ValidationError>,
+
+    /// DPP fingerprint of the contract, computed on validation or when the compact popup opens
+    fingerprint: Option<Result<String, String>>,
+
+    /// Fingerprint typed into the compact popup to compare with the contract's
+    fingerprint_compare: String,
+
+    /// Selected output tab
+    output_tab: OutputTab,
     
     /// Track which property optional sections are expanded
     pub expanded_property_options: std::collections::HashSet<(usize, usize)>,
     
     /// Track which nested property optional sections are expanded
-    pub expanded_nested_property_options: std::collections::HashSet<(usize, usize, Vec<usize>)>,
+    pub expanded_nested_property_options: std::collections::HashSet<(usize, usize, Vec<PathStep>)>,
     
     /// Track which info tooltip is shown (document type index)
     pub shown_info_tooltip: Option<usize>,
@@ -73,6 +266,8 @@ pub enum AppMsg {
     UpdatePropertyType(usize, usize, DataType),
     UpdatePropertyRequired(usize, usize, bool),
     UpdatePropertyDescription(usize, usize, String),
+    UpdatePropertyComment(usize, usize, String),
+    UpdatePropertyTransient(usize, usize, bool),
 
     // Property validation parameters
     UpdatePropertyMinLength(usize, usize, String),
@@ -84,20 +279,25 @@ pub enum AppMsg {
     UpdatePropertyMinItems(usize, usize, String),
     UpdatePropertyMaxItems(usize, usize, String),
     UpdatePropertyContentMediaType(usize, usize, String),
+    UpdatePropertyEnum(usize, usize, String),
+    UpdatePropertyReference(usize, usize, String),
     
     // Nested property operations (doc_index, prop_index, nested_indices...)
-    AddNestedProperty(usize, usize, Vec<usize>),
-    RemoveNestedProperty(usize, usize, Vec<usize>),
-    UpdateNestedPropertyName(usize, usize, Vec<usize>, String),
-    UpdateNestedPropertyType(usize, usize, Vec<usize>, DataType),
-    UpdateNestedPropertyRequired(usize, usize, Vec<usize>, bool),
-    UpdateNestedPropertyDescription(usize, usize, Vec<usize>, String),
-    UpdateNestedPropertyMinLength(usize, usize, Vec<usize>, String),
-    UpdateNestedPropertyMaxLength(usize, usize, Vec<usize>, String),
-    UpdateNestedPropertyPattern(usize, usize, Vec<usize>, String),
-    UpdateNestedPropertyFormat(usize, usize, Vec<usize>, String),
-    UpdateNestedPropertyMinimum(usize, usize, Vec<usize>, String),
-    UpdateNestedPropertyMaximum(usize, usize, Vec<usize>, String),
+    AddNestedProperty(usize, usize, Vec<PathStep>),
+    RemoveNestedProperty(usize, usize, Vec<PathStep>),
+    UpdateNestedPropertyName(usize, usize, Vec<PathStep>, String),
+    UpdateNestedPropertyType(usize, usize, Vec<PathStep>, DataType),
+    UpdateNestedPropertyRequired(usize, usize, Vec<PathStep>, bool),
+    UpdateNestedPropertyDescription(usize, usize, Vec<PathStep>, String),
+    UpdateNestedPropertyMinLength(usize, usize, Vec<PathStep>, String),
+    UpdateNestedPropertyMaxLength(usize, usize, Vec<PathStep>, String),
+    UpdateNestedPropertyPattern(usize, usize, Vec<PathStep>, String),
+    UpdateNestedPropertyFormat(usize, usize, Vec<PathStep>, String),
+    UpdateNestedPropertyMinimum(usize, usize, Vec<PathStep>, String),
+    UpdateNestedPropertyMaximum(usize, usize, Vec<PathStep>, String),
+    UpdateNestedPropertyEnum(usize, usize, Vec<PathStep>, String),
+    /// Makes an array hold bytes (`None`) or items of a type
+    UpdateArrayItems(usize, usize, Vec<PathStep>, Option<DataType>),
 
     // Index operations
     AddIndex(usize),
@@ -112,15 +312,63 @@ pub enum AppMsg {
     // AI operations
     UpdateAiPrompt(String),
     GenerateWithAi,
+    GenerateOffline,
     AiGenerationComplete(String),
-    AiGenerationError(String),
+    AiGenerationError(String, Option<String>),
+    AiStreamDelta(String),
+    CancelAiGeneration,
+    UpdateAiRepairRounds(String),
+    ToggleReviewChange(usize),
+    SetAllReviewChanges(bool),
+    ApplyReview,
+    DiscardReview,
+    ViewConversationTurn(usize),
+    BranchFromTurn(usize),
+    StartNewConversation,
+    OpenAiEdit(EditScope),
+    UpdateAiEditInstruction(String),
+    SendAiEdit,
+    CloseAiEdit,
+    SuggestDescriptions,
+    DescriptionSuggestionsReady(Vec<DescriptionSuggestion>),
+    UpdateDescriptionSuggestion(usize, String),
+    AcceptDescriptionSuggestion(usize),
+    DismissDescriptionSuggestion(usize),
+    AcceptAllDescriptionSuggestions,
+    SelectPromptTemplate(String),
+    EditPromptTemplate,
+    UpdatePromptDraftName(String),
+    UpdatePromptDraftPart(PromptPart, String),
+    SavePromptDraft,
+    CancelPromptDraft,
+    DeletePromptTemplate(String),
+    RestorePromptVersion(String, u32),
 
     // Import/Export operations
     UpdateImportJson(String),
     ImportJson,
+    LoadStarterContract(String),
     ExportJson,
-    ToggleJsonFormat,
+    UpdateOutputFormat(OutputFormat),
     Clear,
+    SelectOutputTab(OutputTab),
+    DownloadOutput,
+    DownloadDocsHtml,
+    DownloadDiagram(DiagramFormat),
+    DownloadAiRecording,
+    UpdateCompareLeft(String),
+    UpdateCompareRight(String),
+    /// Puts the contract being edited on the left of the comparison
+    CompareWithCurrent,
+    UpdateMergeBase(String),
+    UpdateMergeOurs(String),
+    UpdateMergeTheirs(String),
+    /// Puts the contract being edited in place of our draft
+    MergeUseCurrent,
+    StartMerge,
+    ResolveMergeConflict(usize, MergeSide),
+    /// Replaces the contract being edited with the merged one
+    AcceptMerge,
 
     // Validation
     ValidateContract,
@@ -130,6 +378,7 @@ pub enum AppMsg {
     ShowCompactPopup,
     HideCompactPopup,
     PopupContentClick, // No-op message for preventing popup close
+    UpdateFingerprintCompare(String),
     
     // Toggle optional fields visibility
     TogglePropertyOptions(usize, usize),
@@ -151,11 +400,42 @@ impl Component for App {
             ai_prompt: String::new(),
             ai_loading: false,
             ai_errors: Vec::new(),
-            prompt_history: Vec::new(),
+            ai_raw_response: None,
+            conversation: Conversation::default(),
+            viewed_turn: None,
+            ai_request: None,
+            ai_handle: None,
+            ai_stream_preview: String::new(),
+            ai_repair_rounds_max: DEFAULT_AI_REPAIR_ROUNDS,
+            repair_rounds: Vec::new(),
+            ai_remaining_errors: Vec::new(),
+            pending_review: None,
+            ai_edit_scope: None,
+            ai_edit_instruction: String::new(),
+            description_suggestions: Vec::new(),
+            prompt_library: PromptLibrary::load(),
+            prompt_draft: None,
+            ai_sent_messages: Vec::new(),
+            ai_recording: String::new(),
             import_json: String::new(),
-            show_formatted: true,
+            import_diagnostics: Vec::new(),
+            import_summary: None,
+            import_normalized: None,
+            import_highlights: NodeRef::default(),
+            output_format: OutputFormat::load(),
             validation_requested: false,
             show_compact_popup: false,
+            fingerprint: None,
+            fingerprint_compare: String::new(),
+            compare_left: String::new(),
+            compare_right: String::new(),
+            merge_base: String::new(),
+            merge_ours: String::new(),
+            merge_theirs: String::new(),
+            merge: None,
+            merge_error: None,
+            merge_validation: Vec::new(),
+            output_tab: OutputTab::Json,
             expanded_property_options: std::collections::HashSet::new(),
             expanded_nested_property_options: std::collections::HashSet::new(),
             shown_info_tooltip: None,
@@ -322,6 +602,26 @@ impl Component for App {
                 true
             }
 
+            AppMsg::UpdatePropertyComment(doc_index, prop_index, comment) => {
+                if let Some(property) = self.get_property_mut(doc_index, prop_index) {
+                    property.comment = if comment.is_empty() {
+                        None
+                    } else {
+                        Some(comment)
+                    };
+                    self.update_json_output();
+                }
+                true
+            }
+
+            AppMsg::UpdatePropertyTransient(doc_index, prop_index, transient) => {
+                if let Some(property) = self.get_property_mut(doc_index, prop_index) {
+                    property.transient = transient;
+                    self.update_json_output();
+                }
+                true
+            }
+
             // Property validation parameter updates
             AppMsg::UpdatePropertyMinLength(doc_index, prop_index, value) => {
                 if let Some(property) = self.get_property_mut(doc_index, prop_index) {
@@ -407,6 +707,23 @@ impl Component for App {
                 true
             }
             
+            AppMsg::UpdatePropertyEnum(doc_index, prop_index, values) => {
+                if let Some(property) = self.get_property_mut(doc_index, prop_index) {
+                    if property.set_enum_values_from_str(&values).is_ok() {
+                        self.update_json_output();
+                    }
+                }
+                true
+            }
+
+            AppMsg::UpdatePropertyReference(doc_index, prop_index, doc_type_name) => {
+                if let Some(property) = self.get_property_mut(doc_index, prop_index) {
+                    // An empty selection falls back to inferring the reference from the name
+                    property.ref_document_type = Some(doc_type_name).filter(|n| !n.is_empty());
+                }
+                true
+            }
+
             // Nested property operations
             AppMsg::AddNestedProperty(doc_index, prop_index, nested_indices) => {
                 if let Some(parent_property) = self.get_nested_property_mut(doc_index, prop_index, &nested_indices) {
@@ -428,7 +745,7 @@ impl Component for App {
             }
             
             AppMsg::RemoveNestedProperty(doc_index, prop_index, mut nested_indices) => {
-                if let Some(prop_to_remove_index) = nested_indices.pop() {
+                if let Some(PathStep::Property(prop_to_remove_index)) = nested_indices.pop() {
                     if let Some(parent_property) = self.get_nested_property_mut(doc_index, prop_index, &nested_indices) {
                         if let Some(properties) = &mut parent_property.properties {
                             if prop_to_remove_index < properties.len() {
@@ -537,6 +854,27 @@ impl Component for App {
                 true
             }
 
+            AppMsg::UpdateNestedPropertyEnum(doc_index, prop_index, nested_indices, values) => {
+                if let Some(property) =
+                    self.get_nested_property_mut(doc_index, prop_index, &nested_indices)
+                {
+                    if property.set_enum_values_from_str(&values).is_ok() {
+                        self.update_json_output();
+                    }
+                }
+                true
+            }
+
+            AppMsg::UpdateArrayItems(doc_index, prop_index, nested_indices, data_type) => {
+                if let Some(property) =
+                    self.get_nested_property_mut(doc_index, prop_index, &nested_indices)
+                {
+                    property.set_items(data_type);
+                    self.update_json_output();
+                }
+                true
+            }
+
             // Index operations
             AppMsg::AddIndex(doc_index) => {
                 if let Some(doc_type) = self.document_types.get_mut(doc_index) {
@@ -644,348 +982,1905 @@ impl Component for App {
 
             AppMsg::GenerateWithAi => {
                 if !self.ai_prompt.trim().is_empty() && !self.ai_loading {
-                    self.ai_loading = true;
                     self.ai_errors.clear();
+                    self.ai_raw_response = None;
+                    self.repair_rounds.clear();
 
                     let prompt = self.ai_prompt.clone();
-                    let existing_schema = if self.json_output.trim().is_empty() {
+                    // Only resend the contract if it differs from what the last turn produced
+                    let existing_schema = if self.json_output.trim().is_empty()
+                        || self.conversation.head_schema() == Some(self.json_output.as_str())
+                    {
                         None
                     } else {
-                        Some(self.json_output.clone())
+                        Some(self.json_output.as_str())
                     };
+                    let template = self.prompt_library.selected().clone();
+                    let history = self.conversation.active_path();
+                    let request = OpenAiService::user_request(
+                        &template,
+                        &prompt,
+                        existing_schema,
+                        !history.is_empty(),
+                    );
+                    let messages =
+                        OpenAiService::conversation_messages(&template, &history, &request);
+
+                    self.pending_review = None;
+                    self.send_ai_request(
+                        ctx,
+                        AiRequest {
+                            prompt,
+                            request,
+                            messages,
+                            last_schema: None,
+                            scope: None,
+                            template,
+                        },
+                    );
+                }
+                true
+            }
 
-                    let link = ctx.link().clone();
-                    spawn_local(async move {
-                        match OpenAiService::generate_contract(&prompt, existing_schema.as_deref())
-                            .await
-                        {
-                            Ok(schema) => {
-                                link.send_message(AppMsg::AiGenerationComplete(schema));
-                            }
-                            Err(e) => {
-                                link.send_message(AppMsg::AiGenerationError(e.to_string()));
-                            }
-                        }
-                    });
+            AppMsg::GenerateOffline => {
+                if self.ai_loading {
+                    return false;
+                }
+                self.ai_errors.clear();
+                self.ai_raw_response = None;
+                self.repair_rounds.clear();
+                self.pending_review = None;
+
+                // Goes through the same review and validation steps as an AI reply, keeping
+                // the references the generator linked, which contract JSON cannot hold
+                match OfflineGenerator::document_types(&self.ai_prompt) {
+                    Ok(document_types) => self.propose_document_types(ctx, document_types),
+                    Err(e) => self.ai_errors.push(e),
                 }
                 true
             }
 
             AppMsg::AiGenerationComplete(schema) => {
-                self.ai_loading = false;
-                self.prompt_history.push(self.ai_prompt.clone());
-                self.ai_prompt.clear();
+                self.finish_ai_request();
+                let Some(mut pending) = self.ai_request.take() else {
+                    return true;
+                };
+                if let Some(scope) = pending.scope {
+                    self.complete_scoped_edit(ctx, scope, schema);
+                    return true;
+                }
 
-                // Parse the generated schema
+                // Parse the generated schema; the form is only updated once the proposal is final
                 match JsonParser::parse_contract(&schema) {
                     Ok(document_types) => {
-                        self.document_types = document_types;
-                        self.validation_requested = true; // AI generation should trigger validation
-                        self.update_json_output();
+                        let proposed_json = self.contract_json(&document_types);
+
+                        if let (Some(round), Some(previous_json)) =
+                            (self.repair_rounds.last_mut(), pending.last_schema.as_ref())
+                        {
+                            if round.diff.is_none() {
+                                round.diff = Some(diff_lines(previous_json, &proposed_json));
+                            }
+                        }
+
+                        let errors = match ValidationService::validate_schema(&proposed_json) {
+                            Ok(errors) => errors,
+                            Err(e) => vec![ValidationError::schema_error("".to_string(), e)],
+                        };
+
+                        if !errors.is_empty()
+                            && (self.repair_rounds.len() as u32) < self.ai_repair_rounds_max
+                        {
+                            // Feed the errors back and let the model try again
+                            pending
+                                .messages
+                                .push(ChatMessage::assistant(proposed_json.clone()));
+                            pending
+                                .messages
+                                .push(ChatMessage::user(OpenAiService::repair_request(
+                                    &pending.template,
+                                    &errors,
+                                )));
+                            pending.last_schema = Some(proposed_json);
+                            self.repair_rounds.push(RepairRound { errors, diff: None });
+                            self.send_ai_request(ctx, pending);
+                        } else {
+                            self.ai_remaining_errors = errors;
+                            self.conversation
+                                .push_turn(pending.prompt, pending.request, proposed_json);
+                            self.viewed_turn = None;
+                            self.ai_prompt.clear();
+                            self.propose_document_types(ctx, document_types);
+                        }
                     }
                     Err(e) => {
                         self.ai_errors
                             .push(format!("Failed to parse generated schema: {}", e));
+                        self.ai_raw_response = Some(schema);
                     }
                 }
                 true
             }
 
-            AppMsg::AiGenerationError(error) => {
-                self.ai_loading = false;
+            AppMsg::AiGenerationError(error, raw_response) => {
+                self.finish_ai_request();
+                self.ai_request = None;
                 self.ai_errors.push(error);
+                self.ai_raw_response = raw_response;
                 true
             }
 
-            // Import/Export operations
-            AppMsg::UpdateImportJson(json) => {
-                self.import_json = json;
+            AppMsg::AiStreamDelta(delta) => {
+                // Only the preview sees partial output; the form waits for the full reply
+                if self.ai_loading {
+                    self.ai_stream_preview.push_str(&delta);
+                }
                 true
             }
 
-            AppMsg::ImportJson => {
-                if !self.import_json.trim().is_empty() {
-                    match JsonParser::parse_contract(&self.import_json) {
-                        Ok(document_types) => {
-                            self.document_types = document_types;
-                            self.validation_requested = true; // Import should trigger validation
-                            self.update_json_output();
-                            self.import_json.clear();
-                        }
-                        Err(e) => {
-                            self.ai_errors.push(format!("Import failed: {}", e));
-                        }
+            AppMsg::CancelAiGeneration => {
+                if let Some(handle) = &self.ai_handle {
+                    handle.cancel();
+                }
+                true
+            }
+
+            AppMsg::ToggleReviewChange(index) => {
+                if let Some(review) = self.pending_review.as_mut() {
+                    if let Some(accepted) = review.accepted.get_mut(index) {
+                        *accepted = !*accepted;
                     }
                 }
                 true
             }
 
-            AppMsg::ExportJson => {
-                // Copy to clipboard would be implemented here
-                log::info!("Export functionality would copy JSON to clipboard");
+            AppMsg::SetAllReviewChanges(accepted) => {
+                if let Some(review) = self.pending_review.as_mut() {
+                    review.accepted.iter_mut().for_each(|a| *a = accepted);
+                }
                 true
             }
 
-            AppMsg::ToggleJsonFormat => {
-                self.show_formatted = !self.show_formatted;
-                self.update_json_output();
+            AppMsg::ApplyReview => {
+                if let Some(review) = self.pending_review.take() {
+                    self.document_types = ContractDiffService::apply(
+                        &self.document_types,
+                        &review.proposed,
+                        &review.changes,
+                        &review.accepted,
+                    );
+                    if self.document_types.is_empty() {
+                        self.document_types.push(DocumentType::default());
+                    }
+                    ctx.link().send_message(AppMsg::ValidateContract);
+                }
+                self.ai_edit_scope = None;
                 true
             }
 
-            AppMsg::Clear => {
-                self.document_types = vec![DocumentType::default()];
-                self.json_output.clear();
-                self.validation_errors.clear();
-                self.import_json.clear();
-                self.validation_requested = false; // Reset validation state
-                self.update_json_output();
+            AppMsg::DiscardReview => {
+                self.pending_review = None;
+                self.ai_edit_scope = None;
                 true
             }
 
-            // Validation
-            AppMsg::ValidateContract => {
-                // First update the JSON output
-                self.update_json_output();
+            AppMsg::OpenAiEdit(scope) => {
+                self.ai_edit_scope = Some(scope);
+                self.ai_edit_instruction.clear();
+                self.ai_errors.clear();
+                self.ai_raw_response = None;
+                true
+            }
 
-                // Mark that validation has been explicitly requested AFTER update
-                self.validation_requested = true;
+            AppMsg::UpdateAiEditInstruction(instruction) => {
+                self.ai_edit_instruction = instruction;
+                true
+            }
 
-                if !self.json_output.trim().is_empty() {
-                    let json = self.json_output.clone();
-                    let link = ctx.link().clone();
+            AppMsg::SendAiEdit => {
+                let Some(scope) = self.ai_edit_scope.clone() else {
+                    return false;
+                };
+                if self.ai_edit_instruction.trim().is_empty() || self.ai_loading {
+                    return false;
+                }
+                let (Some(label), Some(subtree)) = (
+                    ScopedEditService::label(&self.document_types, &scope),
+                    ScopedEditService::subtree(&self.document_types, &scope),
+                ) else {
+                    self.ai_edit_scope = None;
+                    return true;
+                };
+
+                self.ai_errors.clear();
+                self.ai_raw_response = None;
+                self.repair_rounds.clear();
+                self.pending_review = None;
+
+                // Only the subtree is sent, without the conversation history
+                let prompt = self.ai_edit_instruction.clone();
+                let template = self.prompt_library.selected().clone();
+                let request = OpenAiService::scoped_request(
+                    &template,
+                    &prompt,
+                    &label,
+                    &self.contract_json(&subtree),
+                );
+                let messages = OpenAiService::conversation_messages(&template, &[], &request);
+                self.send_ai_request(
+                    ctx,
+                    AiRequest {
+                        prompt,
+                        request,
+                        messages,
+                        last_schema: None,
+                        scope: Some(scope),
+                        template,
+                    },
+                );
+                true
+            }
 
-                    spawn_local(async move {
-                        let errors = match ValidationService::validate_schema(&json) {
-                            Ok(errors) => errors,
-                            Err(e) => vec![ValidationError::schema_error("".to_string(), e)],
-                        };
+            AppMsg::CloseAiEdit => {
+                // A request still in flight finishes in the AI section instead
+                self.ai_edit_scope = None;
+                true
+            }
 
-                        link.send_message(AppMsg::ValidationComplete(errors));
+            AppMsg::SuggestDescriptions => {
+                let fields = DescriptionService::undocumented(&self.document_types);
+                if fields.is_empty() || self.ai_loading {
+                    return false;
+                }
+                let Some(handle) = self.begin_ai_request() else {
+                    return true;
+                };
+                self.ai_errors.clear();
+                self.ai_raw_response = None;
+                self.description_suggestions.clear();
+
+                let messages = OpenAiService::description_messages(
+                    self.prompt_library.selected(),
+                    &DescriptionService::request_fields(&self.document_types, &fields),
+                );
+                self.ai_sent_messages = messages.clone();
+                let link = ctx.link().clone();
+                spawn_local(async move {
+                    let delta_link = link.clone();
+                    let result = OpenAiService::suggest_descriptions_streaming(
+                        &messages,
+                        &handle,
+                        move |delta| {
+                            delta_link.send_message(AppMsg::AiStreamDelta(delta.to_string()))
+                        },
+                    )
+                    .await;
+
+                    link.send_message(match result {
+                        Ok(response) => AppMsg::DescriptionSuggestionsReady(
+                            DescriptionService::suggestions(&fields, &response),
+                        ),
+                        Err(e) => ai_error_msg(e),
                     });
+                });
+                true
+            }
+
+            AppMsg::DescriptionSuggestionsReady(suggestions) => {
+                self.finish_ai_request();
+                if suggestions.is_empty() {
+                    self.ai_errors.push(
+                        "The AI did not suggest descriptions for any of the fields".to_string(),
+                    );
                 }
+                self.description_suggestions = suggestions;
                 true
             }
 
-            AppMsg::ValidationComplete(errors) => {
-                self.validation_errors = errors;
+            AppMsg::UpdateDescriptionSuggestion(index, text) => {
+                if let Some(suggestion) = self.description_suggestions.get_mut(index) {
+                    suggestion.text = text;
+                }
                 true
             }
 
-            AppMsg::ShowCompactPopup => {
-                self.show_compact_popup = true;
+            AppMsg::AcceptDescriptionSuggestion(index) => {
+                if index < self.description_suggestions.len() {
+                    let suggestion = self.description_suggestions.remove(index);
+                    self.accept_description(&suggestion);
+                    self.update_json_output();
+                }
                 true
             }
 
-            AppMsg::HideCompactPopup => {
-                self.show_compact_popup = false;
+            AppMsg::DismissDescriptionSuggestion(index) => {
+                if index < self.description_suggestions.len() {
+                    self.description_suggestions.remove(index);
+                }
                 true
             }
 
-            AppMsg::PopupContentClick => {
-                // No-op: prevents popup from closing when clicking inside content
-                false
+            AppMsg::SelectPromptTemplate(id) => {
+                self.prompt_library.select(&id);
+                self.prompt_draft = None;
+                self.store_prompt_library();
+                true
             }
-            
-            AppMsg::TogglePropertyOptions(doc_index, prop_index) => {
-                let key = (doc_index, prop_index);
-                if self.expanded_property_options.contains(&key) {
-                    self.expanded_property_options.remove(&key);
-                } else {
-                    self.expanded_property_options.insert(key);
+
+            AppMsg::EditPromptTemplate => {
+                self.prompt_draft = Some(self.prompt_library.selected().clone());
+                true
+            }
+
+            AppMsg::UpdatePromptDraftName(name) => {
+                if let Some(draft) = self.prompt_draft.as_mut() {
+                    draft.name = name;
                 }
                 true
             }
-            
-            AppMsg::ToggleInfoTooltip(doc_index) => {
-                if self.shown_info_tooltip == Some(doc_index) {
-                    self.shown_info_tooltip = None;
-                } else {
-                    self.shown_info_tooltip = Some(doc_index);
+
+            AppMsg::UpdatePromptDraftPart(part, text) => {
+                if let Some(draft) = self.prompt_draft.as_mut() {
+                    draft.set_part(part, text);
                 }
                 true
             }
-            
-            AppMsg::HideInfoTooltip => {
-                self.shown_info_tooltip = None;
+
+            AppMsg::SavePromptDraft => {
+                if let Some(draft) = self.prompt_draft.take() {
+                    self.prompt_library.save(draft);
+                    self.store_prompt_library();
+                }
                 true
             }
-        }
-    }
 
-    fn view(&self, ctx: &Context<Self>) -> Html {
-        html! {
-            <main class="home" onclick={ctx.link().callback(|_| AppMsg::HideInfoTooltip)}>
-                <body>
-                    { self.view_header() }
-                    { self.view_ai_section(ctx) }
-                    <div class="columns">
-                        <div class="column-left">
-                            <div class="column-text">
-                                <img src="https://media.dash.org/wp-content/uploads/icon-left.svg" />
-                                <p>{"Use the left column to build, edit, and submit a data contract."}</p>
-                            </div>
-                            { self.view_form_section(ctx) }
-                            <div class="button-container">
-                                <button class="button2" onclick={ctx.link().callback(|_| AppMsg::AddDocumentType)}>
-                                    <span>{"+"}</span>{"Add document type"}
-                                </button>
-                            </div>
-                            <div class="footnotes"></div>
-                        </div>
-                        <div class="column-right">
-                            <div class="column-text">
-                                <img src="https://media.dash.org/wp-content/uploads/icon-left.svg" class="rotate-180" />
-                                <p>{"Use the right column to copy the generated data contract to your clipboard or import a contract."}</p>
-                            </div>
-                            { self.view_output_section(ctx) }
-                        </div>
-                    </div>
-                    { self.view_footer() }
-                    { self.view_compact_popup(ctx) }
-                </body>
-            </main>
-        }
-    }
-}
+            AppMsg::CancelPromptDraft => {
+                self.prompt_draft = None;
+                true
+            }
 
-// Implementation continues in next part due to length...
-impl App {
-    /// Helper to get mutable reference to a property
-    fn get_property_mut(&mut self, doc_index: usize, prop_index: usize) -> Option<&mut Property> {
-        self.document_types
-            .get_mut(doc_index)?
-            .properties
-            .get_mut(prop_index)
-    }
-    
+            AppMsg::DeletePromptTemplate(id) => {
+                self.prompt_library.delete(&id);
+                self.prompt_draft = None;
+                self.store_prompt_library();
+                true
+            }
+
+            AppMsg::RestorePromptVersion(id, version) => {
+                self.prompt_library.restore(&id, version);
+                self.prompt_draft = None;
+                self.store_prompt_library();
+                true
+            }
+
+            AppMsg::AcceptAllDescriptionSuggestions => {
+                for suggestion in std::mem::take(&mut self.description_suggestions) {
+                    self.accept_description(&suggestion);
+                }
+                self.update_json_output();
+                true
+            }
+
+            AppMsg::UpdateAiRepairRounds(value) => {
+                if let Ok(rounds) = value.trim().parse::<u32>() {
+                    self.ai_repair_rounds_max = rounds.min(MAX_AI_REPAIR_ROUNDS);
+                }
+                true
+            }
+
+            AppMsg::ViewConversationTurn(id) => {
+                self.viewed_turn = if self.viewed_turn == Some(id) {
+                    None
+                } else {
+                    Some(id)
+                };
+                true
+            }
+
+            AppMsg::BranchFromTurn(id) => {
+                if let Some(turn) = self.conversation.turn(id) {
+                    match JsonParser::parse_contract(&turn.schema) {
+                        Ok(document_types) => {
+                            self.document_types = document_types;
+                            self.conversation.branch_from(id);
+                            self.viewed_turn = None;
+                            self.validation_requested = true;
+                            self.update_json_output();
+                        }
+                        Err(e) => {
+                            self.ai_errors
+                                .push(format!("Failed to restore turn: {}", e));
+                        }
+                    }
+                }
+                true
+            }
+
+            AppMsg::StartNewConversation => {
+                // Keep earlier turns so they can still be viewed or branched from
+                self.conversation.head = None;
+                self.viewed_turn = None;
+                true
+            }
+
+            // Import/Export operations
+            AppMsg::UpdateImportJson(json) => {
+                self.import_json = json;
+                // Offsets no longer match the edited text
+                self.import_diagnostics.clear();
+                self.import_summary = None;
+                self.import_normalized = None;
+                true
+            }
+
+            AppMsg::ImportJson => {
+                if !self.import_json.trim().is_empty() {
+                    let imported = ContractImport::parse(&self.import_json);
+                    self.import_summary = imported.summary();
+                    self.import_normalized = None;
+                    let parsed = imported.parsed;
+                    if !parsed.has_errors() {
+                        self.document_types = parsed.document_types;
+                        self.validation_requested = true; // Import should trigger validation
+                        self.update_json_output();
+                        self.import_json.clear();
+                    } else {
+                        // Diagnostics refer to the converted JSON, shown next to the pasted text
+                        self.import_normalized = imported.normalized;
+                    }
+                    // Warnings are listed after a successful import too
+                    self.import_diagnostics = parsed.diagnostics;
+                }
+                true
+            }
+
+            AppMsg::LoadStarterContract(id) => {
+                if let Some(contract) = GalleryService::find(&id) {
+                    match contract.document_types() {
+                        Ok(document_types) => {
+                            self.document_types = document_types;
+                            self.import_json.clear();
+                            self.import_diagnostics.clear();
+                            self.import_summary = None;
+                            self.import_normalized = None;
+                            ctx.link().send_message(AppMsg::ValidateContract);
+                        }
+                        Err(e) => {
+                            self.ai_errors
+                                .push(format!("Failed to load starter contract: {}", e));
+                        }
+                    }
+                }
+                true
+            }
+
+            AppMsg::ExportJson => {
+                // Copy to clipboard would be implemented here
+                log::info!("Export functionality would copy JSON to clipboard");
+                true
+            }
+
+            AppMsg::UpdateOutputFormat(format) => {
+                self.output_format = OutputFormat {
+                    indent: format.indent.min(MAX_INDENT),
+                    ..format
+                };
+                if let Err(e) = self.output_format.store() {
+                    log::warn!("{}", e);
+                }
+                self.update_json_output();
+                true
+            }
+
+            AppMsg::Clear => {
+                self.document_types = vec![DocumentType::default()];
+                self.json_output.clear();
+                self.validation_errors.clear();
+                self.import_json.clear();
+                self.import_diagnostics.clear();
+                self.import_summary = None;
+                self.import_normalized = None;
+                self.conversation.head = None; // Next prompt starts a new conversation
+                self.validation_requested = false; // Reset validation state
+                self.update_json_output();
+                true
+            }
+
+            AppMsg::SelectOutputTab(tab) => {
+                self.output_tab = tab;
+                true
+            }
+
+            AppMsg::DownloadOutput => {
+                let (filename, mime_type, contents) = match self.output_tab {
+                    OutputTab::Json => (
+                        "contract.json",
+                        "application/json",
+                        self.json_output.clone(),
+                    ),
+                    OutputTab::Rust => (
+                        "contract.rs",
+                        "text/x-rust",
+                        RustGenerator::generate(&self.document_types),
+                    ),
+                    OutputTab::TypeScript => (
+                        "contract.ts",
+                        "application/typescript",
+                        TypeScriptGenerator::generate(&self.document_types),
+                    ),
+                    OutputTab::Docs => (
+                        "contract.md",
+                        "text/markdown",
+                        DocsGenerator::to_markdown(&self.document_types),
+                    ),
+                    OutputTab::Diagram => (
+                        "contract.svg",
+                        "image/svg+xml",
+                        DiagramService::to_svg(&self.document_types),
+                    ),
+                    OutputTab::Merge => (
+                        "contract-merged.json",
+                        "application/json",
+                        self.merge
+                            .as_ref()
+                            .and_then(ContractMerge::result)
+                            .map(|merged| self.contract_json(&merged))
+                            .unwrap_or_default(),
+                    ),
+                    OutputTab::Compare => (
+                        "contract-diff.txt",
+                        "text/plain",
+                        ContractComparison::compare_json(&self.compare_left, &self.compare_right)
+                            .map(|rows| ContractComparison::report(&rows))
+                            .unwrap_or_else(|e| e),
+                    ),
+                };
+                if let Err(e) = download_text(filename, mime_type, &contents) {
+                    self.ai_errors.push(format!("Download failed: {}", e));
+                }
+                true
+            }
+
+            AppMsg::UpdateCompareLeft(value) => {
+                self.compare_left = value;
+                true
+            }
+
+            AppMsg::UpdateCompareRight(value) => {
+                self.compare_right = value;
+                true
+            }
+
+            AppMsg::CompareWithCurrent => {
+                self.compare_left = self.json_output.clone();
+                true
+            }
+
+            AppMsg::UpdateMergeBase(value) => {
+                self.merge_base = value;
+                self.merge = None;
+                true
+            }
+
+            AppMsg::UpdateMergeOurs(value) => {
+                self.merge_ours = value;
+                self.merge = None;
+                true
+            }
+
+            AppMsg::UpdateMergeTheirs(value) => {
+                self.merge_theirs = value;
+                self.merge = None;
+                true
+            }
+
+            AppMsg::MergeUseCurrent => {
+                self.merge_ours = self.json_output.clone();
+                self.merge = None;
+                true
+            }
+
+            AppMsg::StartMerge => {
+                let parse = |label: &str, json: &str| {
+                    JsonParser::parse_contract(json).map_err(|e| format!("{}: {}", label, e))
+                };
+                let drafts = parse("Base", &self.merge_base).and_then(|base| {
+                    Ok((
+                        base,
+                        parse("Ours", &self.merge_ours)?,
+                        parse("Theirs", &self.merge_theirs)?,
+                    ))
+                });
+                match drafts {
+                    Ok((base, ours, theirs)) => {
+                        self.merge = Some(ContractMerge::new(base, ours, theirs));
+                        self.merge_error = None;
+                        self.validate_merge();
+                    }
+                    Err(e) => {
+                        self.merge = None;
+                        self.merge_error = Some(e);
+                    }
+                }
+                true
+            }
+
+            AppMsg::ResolveMergeConflict(conflict, side) => {
+                if let Some(merge) = self.merge.as_mut() {
+                    merge.resolve(conflict, side);
+                    self.validate_merge();
+                }
+                true
+            }
+
+            AppMsg::AcceptMerge => {
+                if !self.merge_validation.is_empty() {
+                    return false;
+                }
+                let Some(merged) = self.merge.as_ref().and_then(ContractMerge::result) else {
+                    return false;
+                };
+                self.merge = None;
+                self.document_types = merged;
+                self.update_json_output();
+                self.output_tab = OutputTab::Json;
+                true
+            }
+
+            AppMsg::DownloadDocsHtml => {
+                let html = DocsGenerator::to_html(&self.document_types);
+                if let Err(e) = download_text("contract.html", "text/html", &html) {
+                    self.ai_errors.push(format!("Download failed: {}", e));
+                }
+                true
+            }
+
+            AppMsg::DownloadAiRecording => {
+                let recording = &self.ai_recording;
+                if let Err(e) = download_text("ai-response.http", "text/plain", recording) {
+                    self.ai_errors.push(format!("Download failed: {}", e));
+                }
+                true
+            }
+
+            AppMsg::DownloadDiagram(format) => {
+                let (filename, mime_type) = format.file_info();
+                let contents = DiagramService::render(&self.document_types, format);
+                if let Err(e) = download_text(filename, mime_type, &contents) {
+                    self.ai_errors.push(format!("Download failed: {}", e));
+                }
+                true
+            }
+
+            // Validation
+            AppMsg::ValidateContract => {
+                // First update the JSON output
+                self.update_json_output();
+
+                // Mark that validation has been explicitly requested AFTER update
+                self.validation_requested = true;
+                self.update_fingerprint();
+
+                if !self.json_output.trim().is_empty() {
+                    let json = self.json_output.clone();
+                    let link = ctx.link().clone();
+
+                    spawn_local(async move {
+                        let errors = match ValidationService::validate_schema(&json) {
+                            Ok(errors) => errors,
+                            Err(e) => vec![ValidationError::schema_error("".to_string(), e)],
+                        };
+
+                        link.send_message(AppMsg::ValidationComplete(errors));
+                    });
+                }
+                true
+            }
+
+            AppMsg::ValidationComplete(errors) => {
+                self.validation_errors = errors;
+                true
+            }
+
+            AppMsg::ShowCompactPopup => {
+                self.show_compact_popup = true;
+                self.update_fingerprint();
+                true
+            }
+
+            AppMsg::HideCompactPopup => {
+                self.show_compact_popup = false;
+                true
+            }
+
+            AppMsg::PopupContentClick => {
+                // No-op: prevents popup from closing when clicking inside content
+                false
+            }
+
+            AppMsg::UpdateFingerprintCompare(value) => {
+                self.fingerprint_compare = value;
+                true
+            }
+            
+            AppMsg::TogglePropertyOptions(doc_index, prop_index) => {
+                let key = (doc_index, prop_index);
+                if self.expanded_property_options.contains(&key) {
+                    self.expanded_property_options.remove(&key);
+                } else {
+                    self.expanded_property_options.insert(key);
+                }
+                true
+            }
+            
+            AppMsg::ToggleInfoTooltip(doc_index) => {
+                if self.shown_info_tooltip == Some(doc_index) {
+                    self.shown_info_tooltip = None;
+                } else {
+                    self.shown_info_tooltip = Some(doc_index);
+                }
+                true
+            }
+            
+            AppMsg::HideInfoTooltip => {
+                self.shown_info_tooltip = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <main class="home" onclick={ctx.link().callback(|_| AppMsg::HideInfoTooltip)}>
+                <body>
+                    { self.view_header() }
+                    { self.view_ai_section(ctx) }
+                    { self.view_gallery_section(ctx) }
+                    <div class="columns">
+                        <div class="column-left">
+                            <div class="column-text">
+                                <img src="https://media.dash.org/wp-content/uploads/icon-left.svg" />
+                                <p>{"Use the left column to build, edit, and submit a data contract."}</p>
+                            </div>
+                            { self.view_form_section(ctx) }
+                            <div class="button-container">
+                                <button class="button2" onclick={ctx.link().callback(|_| AppMsg::AddDocumentType)}>
+                                    <span>{"+"}</span>{"Add document type"}
+                                </button>
+                            </div>
+                            <div class="footnotes"></div>
+                        </div>
+                        <div class="column-right">
+                            <div class="column-text">
+                                <img src="https://media.dash.org/wp-content/uploads/icon-left.svg" class="rotate-180" />
+                                <p>{"Use the right column to copy the generated data contract to your clipboard or import a contract."}</p>
+                            </div>
+                            { self.view_output_section(ctx) }
+                        </div>
+                    </div>
+                    { self.view_footer() }
+                    { self.view_compact_popup(ctx) }
+                    { self.view_ai_edit_popup(ctx) }
+                </body>
+            </main>
+        }
+    }
+}
+
+/// Turns a failed AI request into a message, keeping the raw response if there is one
+fn ai_error_msg(e: anyhow::Error) -> AppMsg {
+    let raw_response = e
+        .downcast_ref::<AiResponseError>()
+        .map(|e| e.raw_response.clone());
+    AppMsg::AiGenerationError(e.to_string(), raw_response)
+}
+
+// Implementation continues in next part due to length...
+impl App {
+    /// Sends an AI request, streaming its reply into the preview until `AiGenerationComplete`
+    fn send_ai_request(&mut self, ctx: &Context<Self>, request: AiRequest) {
+        let Some(handle) = self.begin_ai_request() else {
+            return;
+        };
+        let messages = request.messages.clone();
+        self.ai_sent_messages = messages.clone();
+        self.ai_request = Some(request);
+
+        let link = ctx.link().clone();
+        spawn_local(async move {
+            let delta_link = link.clone();
+            let result =
+                OpenAiService::generate_contract_streaming(&messages, &handle, move |delta| {
+                    delta_link.send_message(AppMsg::AiStreamDelta(delta.to_string()))
+                })
+                .await;
+
+            link.send_message(match result {
+                Ok(schema) => AppMsg::AiGenerationComplete(schema),
+                Err(e) => ai_error_msg(e),
+            });
+        });
+    }
+
+    /// Creates a handle for a new AI request and shows the request as in progress
+    fn begin_ai_request(&mut self) -> Option<AiRequestHandle> {
+        match AiRequestHandle::new() {
+            Ok(handle) => {
+                self.ai_loading = true;
+                self.ai_stream_preview.clear();
+                self.ai_handle = Some(handle.clone());
+                Some(handle)
+            }
+            Err(e) => {
+                self.ai_errors.push(e.to_string());
+                None
+            }
+        }
+    }
+
+    /// Shows the AI request as finished, keeping the recording of its response
+    fn finish_ai_request(&mut self) {
+        self.ai_loading = false;
+        if let Some(handle) = self.ai_handle.take() {
+            self.ai_recording = handle.recording();
+        }
+    }
+
+    /// Saves the prompt library, reporting failures in the AI section
+    fn store_prompt_library(&mut self) {
+        if let Err(e) = self.prompt_library.store() {
+            self.ai_errors.push(e);
+        }
+    }
+
+    /// Writes an accepted description into the form
+    fn accept_description(&mut self, suggestion: &DescriptionSuggestion) {
+        if !DescriptionService::apply(&mut self.document_types, &suggestion.key, &suggestion.text) {
+            self.ai_errors.push(format!(
+                "{} no longer exists, so its description was not applied",
+                suggestion.key
+            ));
+        }
+    }
+
+    /// Helper to get mutable reference to a property
+    fn get_property_mut(&mut self, doc_index: usize, prop_index: usize) -> Option<&mut Property> {
+        self.document_types
+            .get_mut(doc_index)?
+            .properties
+            .get_mut(prop_index)
+    }
+    
     /// Helper to get mutable reference to a nested property
-    fn get_nested_property_mut(&mut self, doc_index: usize, prop_index: usize, nested_indices: &[usize]) -> Option<&mut Property> {
-        let mut current_property = self.get_property_mut(doc_index, prop_index)?;
-        
-        for &index in nested_indices {
-            if let Some(properties) = &mut current_property.properties {
-                current_property = properties.get_mut(index)?;
-            } else {
-                return None;
+    fn get_nested_property_mut(&mut self, doc_index: usize, prop_index: usize, nested_indices: &[PathStep]) -> Option<&mut Property> {
+        self.get_property_mut(doc_index, prop_index)?.nested_mut(nested_indices)
+    }
+
+    /// Helper to get a nested property, following the same paths as `get_nested_property_mut`
+    pub fn get_nested_property(&self, doc_index: usize, prop_index: usize, nested_indices: &[PathStep]) -> Option<&Property> {
+        self.document_types.get(doc_index)?.properties.get(prop_index)?.nested(nested_indices)
+    }
+
+    /// Helper to get mutable reference to an index
+    fn get_index_mut(&mut self, doc_index: usize, index_index: usize) -> Option<&mut Index> {
+        self.document_types
+            .get_mut(doc_index)?
+            .indices
+            .get_mut(index_index)
+    }
+
+    /// Applies AI-proposed document types, asking for a review if the form has content
+    fn propose_document_types(&mut self, ctx: &Context<Self>, proposed: Vec<DocumentType>) {
+        let changes = ContractDiffService::diff(&self.document_types, &proposed);
+        let form_is_empty = self.document_types.iter().all(|d| d.name.is_empty());
+
+        if form_is_empty || changes.is_empty() {
+            self.document_types = proposed;
+            ctx.link().send_message(AppMsg::ValidateContract);
+        } else {
+            let accepted = vec![true; changes.len()];
+            self.pending_review = Some(PendingReview {
+                proposed,
+                changes,
+                accepted,
+            });
+        }
+    }
+
+    /// Patches the subtree returned by a targeted edit into the form, via review
+    fn complete_scoped_edit(&mut self, ctx: &Context<Self>, scope: EditScope, schema: String) {
+        let patched = JsonParser::parse_contract(&schema)
+            .and_then(|edited| ScopedEditService::patch(&self.document_types, &scope, &edited));
+
+        match patched {
+            Ok(proposed) => {
+                self.propose_document_types(ctx, proposed);
+                if self.pending_review.is_none() {
+                    self.ai_edit_scope = None;
+                }
+            }
+            Err(e) => {
+                self.ai_errors.push(format!("Failed to apply AI edit: {}", e));
+                self.ai_raw_response = Some(schema);
+            }
+        }
+    }
+
+    /// Serializes document types the way the output section shows them
+    fn contract_json(&self, document_types: &[DocumentType]) -> String {
+        JsonGenerator::generate_text(document_types, &self.output_format)
+    }
+
+    /// Updates the JSON output and resets validation state
+    fn update_json_output(&mut self) {
+        let new_json_output = self.contract_json(&self.document_types);
+
+        // Only reset validation if the JSON actually changed
+        if new_json_output != self.json_output {
+            self.reset_validation();
+            self.fingerprint = None;
+        }
+
+        self.json_output = new_json_output;
+    }
+
+    /// Computes the fingerprint of the contract if it changed since it was last computed
+    fn update_fingerprint(&mut self) {
+        if self.fingerprint.is_none() && self.json_output.len() > 2 {
+            self.fingerprint = Some(ContractFingerprint::of_document_types(&self.document_types));
+        }
+    }
+
+    /// Validates the merged contract, which can only be accepted once it is valid
+    fn validate_merge(&mut self) {
+        self.merge_validation.clear();
+        let Some(merged) = self.merge.as_ref().and_then(ContractMerge::result) else {
+            return;
+        };
+        let json = self.contract_json(&merged);
+        self.merge_validation = match ValidationService::validate_schema(&json) {
+            Ok(errors) => errors,
+            Err(e) => vec![ValidationError::schema_error("".to_string(), e)],
+        };
+    }
+
+    /// Resets validation state when contract is edited
+    fn reset_validation(&mut self) {
+        self.validation_requested = false;
+        self.validation_errors.clear();
+    }
+
+    fn view_header(&self) -> Html {
+        html! {
+            <div class="top-section_ai">
+                <img class="logo_ai" src="https://media.dash.org/wp-content/uploads/dash-logo.svg" alt="Dash logo" width="100" height="50" />
+                <h1 class="header_ai">{"Data Contract Creator"}</h1>
+            </div>
+        }
+    }
+
+    fn view_ai_section(&self, ctx: &Context<Self>) -> Html {
+        let on_prompt_change = ctx.link().callback(|e: InputEvent| {
+            let target = e.target().expect("Event should have target");
+            let input = target
+                .dyn_into::<HtmlInputElement>()
+                .expect("Target should be input element");
+            AppMsg::UpdateAiPrompt(input.value())
+        });
+
+        let onsubmit = ctx.link().callback(|event: SubmitEvent| {
+            event.prevent_default();
+            AppMsg::GenerateWithAi
+        });
+
+        let on_repair_rounds_change = ctx.link().callback(|e: Event| {
+            let input = e.target_unchecked_into::<HtmlInputElement>();
+            AppMsg::UpdateAiRepairRounds(input.value())
+        });
+
+        let undocumented = DescriptionService::undocumented(&self.document_types).len();
+
+        html! {
+            <div class="container_ai">
+                <div class="content-container_ai">
+                    <div class="input-container_ai">
+                        <p>{"Generate a data contract using AI here or by filling out the form below."}</p>
+                        <form onsubmit={onsubmit}>
+                            { if self.json_output.is_empty() {
+                                html! { <label class="padded-label">{"  Project description"}</label> }
+                            } else {
+                                html! { <label class="padded-label">{"  Adjustments to existing contract"}</label> }
+                            }}
+                            <div class="input-button-container_ai">
+                                <input
+                                    value={self.ai_prompt.clone()}
+                                    oninput={on_prompt_change}
+                                />
+                                <button type="submit">{"Generate"}</button>
+                                <button
+                                    type="button"
+                                    class="generate-offline_ai"
+                                    title="Build a starter contract without AI from a description like: post: title!, body:text, author:ownerRef; comment: post:ref, body"
+                                    onclick={ctx.link().callback(|_| AppMsg::GenerateOffline)}
+                                >
+                                    {"Generate offline"}
+                                </button>
+                            </div>
+                            <p class="offline-hint_ai">
+                                {"Offline generation takes entities and typed fields, for example "}
+                                <code>{"post: title!, body:text, author:ownerRef; comment: post:ref, body"}</code>
+                            </p>
+                            <label class="repair-rounds_ai">
+                                {"Automatic repair rounds "}
+                                <input
+                                    type="number"
+                                    min="0"
+                                    max={MAX_AI_REPAIR_ROUNDS.to_string()}
+                                    value={self.ai_repair_rounds_max.to_string()}
+                                    onchange={on_repair_rounds_change}
+                                />
+                            </label>
+                        </form>
+                        { self.view_prompt_library(ctx) }
+                        <button
+                            class="button-compact describe_ai"
+                            disabled={self.ai_loading || undocumented == 0}
+                            onclick={ctx.link().callback(|_| AppMsg::SuggestDescriptions)}
+                        >
+                            { format!("Suggest descriptions for {} undocumented fields", undocumented) }
+                        </button>
+                    </div>
+                </div>
+                {
+                    // Targeted edits show their progress and review in the popup
+                    if self.ai_edit_scope.is_none() {
+                        html! {
+                            <>
+                                { self.view_ai_status(ctx) }
+                                { self.view_sent_prompt(ctx) }
+                                { self.view_repair_rounds() }
+                                { self.view_review(ctx) }
+                            </>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                { self.view_description_suggestions(ctx) }
+            </div>
+        }
+    }
+
+    fn view_prompt_library(&self, ctx: &Context<Self>) -> Html {
+        let library = &self.prompt_library;
+        let selected = library.selected();
+        let versions = library.versions(&selected.id);
+
+        let on_select = ctx.link().callback(|e: Event| {
+            let select = e.target_unchecked_into::<web_sys::HtmlSelectElement>();
+            AppMsg::SelectPromptTemplate(select.value())
+        });
+        let on_restore = {
+            let id = selected.id.clone();
+            ctx.link().batch_callback(move |e: Event| {
+                let select = e.target_unchecked_into::<web_sys::HtmlSelectElement>();
+                let version = select.value().parse::<u32>().ok()?;
+                Some(AppMsg::RestorePromptVersion(id.clone(), version))
+            })
+        };
+
+        html! {
+            <details class="prompt-library_ai" open={self.prompt_draft.is_some()}>
+                <summary>{ format!("Prompt template: {} (v{})", selected.name, selected.version) }</summary>
+                <div class="prompt-library-controls">
+                    <select onchange={on_select}>
+                        { for library.templates.iter().map(|template| html! {
+                            <option value={template.id.clone()} selected={template.id == selected.id}>
+                                { if template.built_in {
+                                    format!("{} (built-in)", template.name)
+                                } else {
+                                    format!("{} v{}", template.name, template.version)
+                                }}
+                            </option>
+                        }) }
+                    </select>
+                    <button type="button" class="button-compact" onclick={ctx.link().callback(|_| AppMsg::EditPromptTemplate)}>
+                        { if selected.built_in { "Customize" } else { "Edit" } }
+                    </button>
+                    { if selected.built_in {
+                        html! {}
+                    } else {
+                        let id = selected.id.clone();
+                        html! {
+                            <button type="button" class="button-compact" onclick={ctx.link().callback(move |_| AppMsg::DeletePromptTemplate(id.clone()))}>
+                                { "Delete" }
+                            </button>
+                        }
+                    }}
+                    { if versions.is_empty() {
+                        html! {}
+                    } else {
+                        html! {
+                            <select onchange={on_restore}>
+                                <option value="" selected=true>{ "Restore earlier version…" }</option>
+                                { for versions.iter().map(|version| html! {
+                                    <option value={version.version.to_string()}>{ format!("v{}", version.version) }</option>
+                                }) }
+                            </select>
+                        }
+                    }}
+                </div>
+                { self.view_prompt_draft(ctx) }
+            </details>
+        }
+    }
+
+    fn view_prompt_draft(&self, ctx: &Context<Self>) -> Html {
+        let Some(draft) = &self.prompt_draft else {
+            return html! {};
+        };
+
+        html! {
+            <div class="prompt-draft">
+                { if draft.built_in {
+                    html! { <p>{ "Built-in templates stay as defaults; saving creates a new template." }</p> }
+                } else {
+                    html! {}
+                }}
+                <label>{ "Name" }</label>
+                <input
+                    type="text"
+                    value={draft.name.clone()}
+                    oninput={ctx.link().callback(|e: InputEvent| {
+                        let input = e.target_unchecked_into::<HtmlInputElement>();
+                        AppMsg::UpdatePromptDraftName(input.value())
+                    })}
+                />
+                { for PromptPart::all().into_iter().map(|part| {
+                    let variables = part
+                        .variables()
+                        .iter()
+                        .map(|variable| format!("{{{{{}}}}}", variable))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let unknown = draft.unknown_variables(part);
+                    html! {
+                        <div class="prompt-draft-part">
+                            <label>
+                                { part.label() }
+                                { if variables.is_empty() {
+                                    html! {}
+                                } else {
+                                    html! { <span class="prompt-variables">{ format!(" — variables: {}", variables) }</span> }
+                                }}
+                            </label>
+                            <textarea
+                                value={draft.part(part).to_string()}
+                                oninput={ctx.link().callback(move |e: InputEvent| {
+                                    let textarea = e.target_unchecked_into::<web_sys::HtmlTextAreaElement>();
+                                    AppMsg::UpdatePromptDraftPart(part, textarea.value())
+                                })}
+                            ></textarea>
+                            { if unknown.is_empty() {
+                                html! {}
+                            } else {
+                                html! {
+                                    <p class="error-text_ai">
+                                        { format!("Unknown variables are sent as written: {}", unknown.join(", ")) }
+                                    </p>
+                                }
+                            }}
+                        </div>
+                    }
+                }) }
+                <div class="button-block">
+                    <button type="button" class="button button-primary" onclick={ctx.link().callback(|_| AppMsg::SavePromptDraft)}>
+                        { "Save template" }
+                    </button>
+                    <button type="button" class="button" onclick={ctx.link().callback(|_| AppMsg::CancelPromptDraft)}>
+                        { "Cancel" }
+                    </button>
+                </div>
+            </div>
+        }
+    }
+
+    /// Renders text with each import problem underlined
+    fn view_import_highlights(&self, text: &str) -> Html {
+        let mut diagnostics: Vec<&Diagnostic> = self.import_diagnostics.iter().collect();
+        diagnostics.sort_by_key(|diagnostic| diagnostic.start);
+
+        let mut segments = Vec::new();
+        let mut cursor = 0;
+        for diagnostic in diagnostics {
+            let start = SourceLocations::floor_boundary(text, diagnostic.start);
+            if start < cursor {
+                continue; // Overlaps an earlier underline
             }
+            // Only the first line of large values, such as whole objects, is underlined
+            let line_end = text[start..].find('\n').map_or(text.len(), |i| start + i);
+            let end = SourceLocations::floor_boundary(text, diagnostic.end.min(line_end));
+            let marked = if end > start { &text[start..end] } else { " " };
+
+            segments.push(html! { { &text[cursor..start] } });
+            let class = if diagnostic.is_error() { "error" } else { "warning" };
+            segments.push(html! {
+                <mark class={class} title={diagnostic.message.clone()}>{ marked }</mark>
+            });
+            cursor = end;
+        }
+        segments.push(html! { { &text[cursor..] } });
+        // A trailing newline needs a character after it to take up space
+        segments.push(html! { { " " } });
+
+        html! { for segments }
+    }
+
+    /// Lists the problems found by the latest import
+    fn view_import_diagnostics(&self) -> Html {
+        if self.import_diagnostics.is_empty() && self.import_summary.is_none() {
+            return html! {};
+        }
+
+        let summary = if self.import_diagnostics.iter().any(Diagnostic::is_error) {
+            "Import failed. Fix the underlined problems and import again."
+        } else if self.import_diagnostics.is_empty() {
+            "Imported."
+        } else {
+            "Imported with warnings:"
+        };
+
+        html! {
+            <div class="import-diagnostics">
+                if let Some(source) = &self.import_summary {
+                    <p class="warning-text">{ source }</p>
+                }
+                <p>{ summary }</p>
+                <ul>
+                    { for self.import_diagnostics.iter().map(|diagnostic| {
+                        let class = if diagnostic.is_error() { "error-text" } else { "warning-text" };
+                        html! { <li class={class}>{ diagnostic.display_message() }</li> }
+                    }) }
+                </ul>
+            </div>
+        }
+    }
+
+    /// Shows the messages of the most recent AI request, as sent
+    fn view_sent_prompt(&self, ctx: &Context<Self>) -> Html {
+        if self.ai_sent_messages.is_empty() {
+            return html! {};
+        }
+
+        html! {
+            <details class="sent-prompt_ai">
+                <summary>{ "Prompt sent" }</summary>
+                { for self.ai_sent_messages.iter().map(|message| {
+                    let role = match message.role {
+                        ChatRole::System => "System",
+                        ChatRole::User => "User",
+                        ChatRole::Assistant => "Assistant",
+                    };
+                    html! {
+                        <div class="sent-prompt-message">
+                            <strong>{ role }</strong>
+                            <pre>{ &message.content }</pre>
+                        </div>
+                    }
+                }) }
+                if !self.ai_recording.is_empty() {
+                    <button class="button-compact" onclick={ctx.link().callback(|_| AppMsg::DownloadAiRecording)}>
+                        { "Download response recording" }
+                    </button>
+                }
+            </details>
+        }
+    }
+
+    fn view_description_suggestions(&self, ctx: &Context<Self>) -> Html {
+        if self.description_suggestions.is_empty() {
+            return html! {};
+        }
+
+        html! {
+            <div class="description-suggestions">
+                <h3>{ "Suggested descriptions" }</h3>
+                <p>{ "Edit any suggestion, then accept it to fill in the description." }</p>
+                { for self.description_suggestions.iter().enumerate().map(|(i, suggestion)| {
+                    html! {
+                        <div class="description-suggestion">
+                            <label>{ &suggestion.key }</label>
+                            <textarea
+                                value={suggestion.text.clone()}
+                                oninput={ctx.link().callback(move |e: InputEvent| {
+                                    let textarea = e.target_unchecked_into::<web_sys::HtmlTextAreaElement>();
+                                    AppMsg::UpdateDescriptionSuggestion(i, textarea.value())
+                                })}
+                            ></textarea>
+                            <div class="button-block">
+                                <button class="button-compact" onclick={ctx.link().callback(move |_| AppMsg::AcceptDescriptionSuggestion(i))}>
+                                    { "Accept" }
+                                </button>
+                                <button class="button" onclick={ctx.link().callback(move |_| AppMsg::DismissDescriptionSuggestion(i))}>
+                                    { "Dismiss" }
+                                </button>
+                            </div>
+                        </div>
+                    }
+                }) }
+                <div class="button-block">
+                    <button class="button button-primary" onclick={ctx.link().callback(|_| AppMsg::AcceptAllDescriptionSuggestions)}>
+                        { format!("Accept all {}", self.description_suggestions.len()) }
+                    </button>
+                </div>
+            </div>
+        }
+    }
+
+    /// Shows progress, errors and the raw response of the current AI request
+    fn view_ai_status(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <>
+                {
+                    if self.ai_loading {
+                        html! {
+                            <>
+                                <div class="loader_ai"></div>
+                                <button class="button-compact cancel_ai" onclick={ctx.link().callback(|_| AppMsg::CancelAiGeneration)}>
+                                    { "Cancel" }
+                                </button>
+                                { if self.ai_stream_preview.is_empty() {
+                                    html! {}
+                                } else {
+                                    html! {
+                                        <pre class="stream-preview_ai">{ &self.ai_stream_preview }</pre>
+                                    }
+                                }}
+                            </>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                {
+                    if !self.ai_errors.is_empty() {
+                        html! {<div class="error-text_ai">{for self.ai_errors.iter().map(|e| html!{<>{e}</>})}</div>}
+                    } else {
+                        html! {}
+                    }
+                }
+                {
+                    if let Some(raw_response) = &self.ai_raw_response {
+                        html! {
+                            <details class="raw-response_ai">
+                                <summary>{ "Raw response" }</summary>
+                                <pre>{ raw_response }</pre>
+                            </details>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+            </>
+        }
+    }
+
+    fn view_review(&self, ctx: &Context<Self>) -> Html {
+        let Some(review) = &self.pending_review else {
+            return html! {};
+        };
+        let accepted_count = review.accepted.iter().filter(|a| **a).count();
+
+        html! {
+            <div class="review">
+                <h3>{ "Review AI changes" }</h3>
+                <p>{ "Choose which changes to merge into the form. Rejected changes keep your current version." }</p>
+                <div class="review-changes">
+                    { for review.changes.iter().enumerate().map(|(i, change)| {
+                        let kind_class = match change.kind {
+                            ChangeKind::Added => "review-change added",
+                            ChangeKind::Removed => "review-change removed",
+                            ChangeKind::Changed => "review-change changed",
+                        };
+                        html! {
+                            <label class={kind_class}>
+                                <input
+                                    type="checkbox"
+                                    checked={review.accepted.get(i).copied().unwrap_or(false)}
+                                    onchange={ctx.link().callback(move |_| AppMsg::ToggleReviewChange(i))}
+                                />
+                                <span class="review-change-summary">{ change.summary() }</span>
+                                { if change.details.is_empty() {
+                                    html! {}
+                                } else {
+                                    html! {
+                                        <ul>
+                                            { for change.details.iter().map(|detail| html! { <li>{ detail }</li> }) }
+                                        </ul>
+                                    }
+                                }}
+                            </label>
+                        }
+                    }) }
+                </div>
+                <div class="button-block">
+                    <button class="button-compact" onclick={ctx.link().callback(|_| AppMsg::SetAllReviewChanges(true))}>
+                        { "Select all" }
+                    </button>
+                    <button class="button-compact" onclick={ctx.link().callback(|_| AppMsg::SetAllReviewChanges(false))}>
+                        { "Select none" }
+                    </button>
+                    <button class="button button-primary" onclick={ctx.link().callback(|_| AppMsg::ApplyReview)}>
+                        { format!("Apply {} of {} changes", accepted_count, review.changes.len()) }
+                    </button>
+                    <button class="button" onclick={ctx.link().callback(|_| AppMsg::DiscardReview)}>
+                        { "Discard" }
+                    </button>
+                </div>
+            </div>
         }
-        
-        Some(current_property)
     }
 
-    /// Helper to get mutable reference to an index
-    fn get_index_mut(&mut self, doc_index: usize, index_index: usize) -> Option<&mut Index> {
-        self.document_types
-            .get_mut(doc_index)?
-            .indices
-            .get_mut(index_index)
+    fn view_repair_rounds(&self) -> Html {
+        if self.repair_rounds.is_empty() {
+            return html! {};
+        }
+
+        html! {
+            <div class="repair-rounds">
+                { for self.repair_rounds.iter().enumerate().map(|(i, round)| html! {
+                    <details class="repair-round" open={i + 1 == self.repair_rounds.len()}>
+                        <summary>
+                            { format!("Repair round {}: {} validation error(s)", i + 1, round.errors.len()) }
+                        </summary>
+                        <ul>
+                            { for round.errors.iter().map(|error| html! {
+                                <li>{ format!("{} {}", error.path, error.message).trim() }</li>
+                            }) }
+                        </ul>
+                        { match &round.diff {
+                            Some(diff) => html! {
+                                <pre class="diff">
+                                    { for with_context(diff, 2).into_iter().map(|line| match line {
+                                        Some(line @ DiffLine::Added(_)) => html! {
+                                            <div class="diff-added">{ line.to_prefixed() }</div>
+                                        },
+                                        Some(line @ DiffLine::Removed(_)) => html! {
+                                            <div class="diff-removed">{ line.to_prefixed() }</div>
+                                        },
+                                        Some(line) => html! { <div>{ line.to_prefixed() }</div> },
+                                        None => html! { <div class="diff-skipped">{ "  ..." }</div> },
+                                    }) }
+                                </pre>
+                            },
+                            None if self.ai_loading => html! {
+                                <p>{ "Waiting for the model to fix these errors..." }</p>
+                            },
+                            None => html! { <p>{ "The model's reply could not be used." }</p> },
+                        }}
+                    </details>
+                }) }
+                { if !self.ai_loading {
+                    let passed = self.ai_remaining_errors.is_empty();
+                    html! {
+                        <p class={if passed { "passed-text" } else { "error-text" }}>
+                            { if passed {
+                                "The repaired contract passes validation.".to_string()
+                            } else {
+                                format!(
+                                    "Repair budget used up with {} validation error(s) remaining.",
+                                    self.ai_remaining_errors.len()
+                                )
+                            }}
+                        </p>
+                    }
+                } else {
+                    html! {}
+                }}
+            </div>
+        }
     }
 
-    /// Updates the JSON output and resets validation state
-    fn update_json_output(&mut self) {
-        let json_value = JsonGenerator::generate_contract(&self.document_types);
+    fn view_gallery_section(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div class="gallery">
+                <p class="gallery-intro">{"Or start from one of these example contracts:"}</p>
+                <div class="gallery-cards">
+                    { for GalleryService::starter_contracts().iter().map(|contract| {
+                        let id = contract.id.to_string();
+                        html! {
+                            <div class="gallery-card">
+                                <h3>{ contract.name }</h3>
+                                <p>{ contract.description }</p>
+                                <button
+                                    class="button button-primary"
+                                    onclick={ctx.link().callback(move |_| AppMsg::LoadStarterContract(id.clone()))}
+                                >
+                                    { "Use this contract" }
+                                </button>
+                            </div>
+                        }
+                    }) }
+                </div>
+            </div>
+        }
+    }
 
-        let new_json_output = if self.show_formatted {
-            serde_json::to_string_pretty(&json_value).unwrap_or_default()
-        } else {
-            serde_json::to_string(&json_value).unwrap_or_default()
-        };
+    fn view_form_section(&self, ctx: &Context<Self>) -> Html {
+        self.view_full_form_section(ctx)
+    }
 
-        // Only reset validation if the JSON actually changed
-        if new_json_output != self.json_output {
-            self.reset_validation();
+    fn view_output_tabs(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div class="output-tabs">
+                { for OutputTab::all().into_iter().map(|tab| html! {
+                    <button
+                        class={if tab == self.output_tab { "output-tab active" } else { "output-tab" }}
+                        onclick={ctx.link().callback(move |_| AppMsg::SelectOutputTab(tab))}
+                    >
+                        { tab.label() }
+                    </button>
+                }) }
+            </div>
         }
+    }
 
-        self.json_output = new_json_output;
+    fn view_output_section(&self, ctx: &Context<Self>) -> Html {
+        match self.output_tab {
+            OutputTab::Json => self.view_json_output(ctx),
+            OutputTab::Rust => self.view_generated_code(
+                ctx,
+                RustGenerator::generate(&self.document_types),
+                html! {},
+            ),
+            OutputTab::TypeScript => self.view_generated_code(
+                ctx,
+                TypeScriptGenerator::generate(&self.document_types),
+                html! {},
+            ),
+            OutputTab::Docs => self.view_generated_code(
+                ctx,
+                DocsGenerator::to_markdown(&self.document_types),
+                html! {
+                    <button class="button button-primary" onclick={ctx.link().callback(|_| AppMsg::DownloadDocsHtml)}>
+                        { "Download HTML" }
+                    </button>
+                },
+            ),
+            OutputTab::Diagram => self.view_diagram_output(ctx),
+            OutputTab::Compare => self.view_compare_output(ctx),
+            OutputTab::Merge => self.view_merge_output(ctx),
+        }
     }
 
-    /// Resets validation state when contract is edited
-    fn reset_validation(&mut self) {
-        self.validation_requested = false;
-        self.validation_errors.clear();
+    fn view_merge_output(&self, ctx: &Context<Self>) -> Html {
+        let on_base = ctx.link().callback(|e: InputEvent| {
+            let textarea = e.target_unchecked_into::<web_sys::HtmlTextAreaElement>();
+            AppMsg::UpdateMergeBase(textarea.value())
+        });
+        let on_ours = ctx.link().callback(|e: InputEvent| {
+            let textarea = e.target_unchecked_into::<web_sys::HtmlTextAreaElement>();
+            AppMsg::UpdateMergeOurs(textarea.value())
+        });
+        let on_theirs = ctx.link().callback(|e: InputEvent| {
+            let textarea = e.target_unchecked_into::<web_sys::HtmlTextAreaElement>();
+            AppMsg::UpdateMergeTheirs(textarea.value())
+        });
+
+        html! {
+            <div class="input-container">
+                { self.view_output_tabs(ctx) }
+                <h2>{ self.output_tab.title() }</h2>
+                <p>{ "Paste the contract both drafts started from, then each draft." }</p>
+                <div class="compare-inputs">
+                    <textarea class="textarea-whitespace" placeholder="Base" value={self.merge_base.clone()} oninput={on_base}></textarea>
+                    <textarea class="textarea-whitespace" placeholder="Ours" value={self.merge_ours.clone()} oninput={on_ours}></textarea>
+                    <textarea class="textarea-whitespace" placeholder="Theirs" value={self.merge_theirs.clone()} oninput={on_theirs}></textarea>
+                </div>
+                <div class="button-block">
+                    <button
+                        class="button-compact"
+                        disabled={self.json_output.is_empty()}
+                        onclick={ctx.link().callback(|_| AppMsg::MergeUseCurrent)}
+                    >
+                        { "Use current contract as ours" }
+                    </button>
+                    <button class="button button-primary" onclick={ctx.link().callback(|_| AppMsg::StartMerge)}>
+                        { "Merge" }
+                    </button>
+                </div>
+                { if let Some(e) = &self.merge_error {
+                    html! { <p class="error-text">{ e }</p> }
+                } else {
+                    html! {}
+                }}
+                { self.view_merge_result(ctx) }
+            </div>
+        }
     }
 
-    fn view_header(&self) -> Html {
+    fn view_merge_result(&self, ctx: &Context<Self>) -> Html {
+        let Some(merge) = self.merge.as_ref() else {
+            return html! {};
+        };
+        let merged = merge.result().map(|merged| self.contract_json(&merged));
+        let can_accept = merged.is_some() && self.merge_validation.is_empty();
+        let version = |text: &Option<String>| text.clone().unwrap_or_else(|| "(removed)".to_string());
+
         html! {
-            <div class="top-section_ai">
-                <img class="logo_ai" src="https://media.dash.org/wp-content/uploads/dash-logo.svg" alt="Dash logo" width="100" height="50" />
-                <h1 class="header_ai">{"Data Contract Creator"}</h1>
+            <div class="merge-result">
+                { if merge.conflicts.is_empty() {
+                    html! { <p class="passed-text">{ "All changes merged without conflicts ✓" }</p> }
+                } else {
+                    html! {
+                        <>
+                            <h3>{ format!("{} conflicts, {} unresolved", merge.conflicts.len(), merge.unresolved()) }</h3>
+                            { for merge.conflicts.iter().enumerate().map(|(index, conflict)| html! {
+                                <div class={classes!("merge-conflict", conflict.resolution.is_none().then_some("unresolved"))}>
+                                    <strong>{ conflict.label() }</strong>
+                                    <div class="merge-base">{ format!("Base: {}", version(&conflict.base)) }</div>
+                                    { for [(MergeSide::Ours, "Ours", &conflict.ours), (MergeSide::Theirs, "Theirs", &conflict.theirs)]
+                                        .into_iter()
+                                        .map(|(side, label, text)| html! {
+                                            <label class={classes!("merge-side", (conflict.resolution == Some(side)).then_some("chosen"))}>
+                                                <input
+                                                    type="radio"
+                                                    name={format!("merge-conflict-{}", index)}
+                                                    checked={conflict.resolution == Some(side)}
+                                                    onchange={ctx.link().callback(move |_| AppMsg::ResolveMergeConflict(index, side))}
+                                                />
+                                                { format!(" {}: {}", label, version(text)) }
+                                            </label>
+                                        }) }
+                                </div>
+                            }) }
+                        </>
+                    }
+                }}
+                { if let Some(merged) = merged {
+                    html! {
+                        <>
+                            <h3>{ "Merged contract" }</h3>
+                            <pre class="conversation-turn-schema">{ merged }</pre>
+                            { if self.merge_validation.is_empty() {
+                                html! { <p class="passed-text">{ "DPP validation passing ✓" }</p> }
+                            } else {
+                                html! {
+                                    <div>
+                                        { for self.merge_validation.iter().map(|error| html! {
+                                            <p class="error-text">{ error.display_message() }</p>
+                                        }) }
+                                    </div>
+                                }
+                            }}
+                        </>
+                    }
+                } else {
+                    html! { <p class="error-text">{ "Choose a version for every conflict to see and validate the merged contract." }</p> }
+                }}
+                <div class="button-block">
+                    <button
+                        class="button button-primary"
+                        disabled={!can_accept}
+                        onclick={ctx.link().callback(|_| AppMsg::AcceptMerge)}
+                    >
+                        { "Accept merge" }
+                    </button>
+                    <button
+                        class="button-compact"
+                        disabled={merge.unresolved() > 0}
+                        onclick={ctx.link().callback(|_| AppMsg::DownloadOutput)}
+                    >
+                        { "Download merged contract" }
+                    </button>
+                </div>
             </div>
         }
     }
 
-    fn view_ai_section(&self, ctx: &Context<Self>) -> Html {
-        let on_prompt_change = ctx.link().callback(|e: InputEvent| {
-            let target = e.target().expect("Event should have target");
-            let input = target
-                .dyn_into::<HtmlInputElement>()
-                .expect("Target should be input element");
-            AppMsg::UpdateAiPrompt(input.value())
+    fn view_compare_output(&self, ctx: &Context<Self>) -> Html {
+        let on_left = ctx.link().callback(|e: InputEvent| {
+            let textarea = e.target_unchecked_into::<web_sys::HtmlTextAreaElement>();
+            AppMsg::UpdateCompareLeft(textarea.value())
         });
-
-        let onsubmit = ctx.link().callback(|event: SubmitEvent| {
-            event.prevent_default();
-            AppMsg::GenerateWithAi
+        let on_right = ctx.link().callback(|e: InputEvent| {
+            let textarea = e.target_unchecked_into::<web_sys::HtmlTextAreaElement>();
+            AppMsg::UpdateCompareRight(textarea.value())
         });
 
+        let both = !self.compare_left.trim().is_empty() && !self.compare_right.trim().is_empty();
+        let comparison = if both {
+            match ContractComparison::compare_json(&self.compare_left, &self.compare_right) {
+                Ok(rows) => self.view_comparison_rows(&rows),
+                Err(e) => html! { <p class="error-text">{ e }</p> },
+            }
+        } else {
+            html! { <p>{ "Paste a contract on each side to compare them." }</p> }
+        };
+
         html! {
-            <div class="container_ai">
-                <div class="content-container_ai">
-                    <div class="input-container_ai">
-                        <p>{"Generate a data contract using AI here or by filling out the form below."}</p>
-                        <form onsubmit={onsubmit}>
-                            { if self.json_output.is_empty() {
-                                html! { <label class="padded-label">{"  Project description"}</label> }
-                            } else {
-                                html! { <label class="padded-label">{"  Adjustments to existing contract"}</label> }
-                            }}
-                            <div class="input-button-container_ai">
-                                <input
-                                    value={self.ai_prompt.clone()}
-                                    oninput={on_prompt_change}
-                                />
-                                <button type="submit">{"Generate"}</button>
-                            </div>
-                        </form>
-                    </div>
+            <div class="input-container">
+                { self.view_output_tabs(ctx) }
+                <h2>{ self.output_tab.title() }</h2>
+                <div class="compare-inputs">
+                    <textarea
+                        class="textarea-whitespace"
+                        placeholder="Left contract"
+                        value={self.compare_left.clone()}
+                        oninput={on_left}
+                    ></textarea>
+                    <textarea
+                        class="textarea-whitespace"
+                        placeholder="Right contract"
+                        value={self.compare_right.clone()}
+                        oninput={on_right}
+                    ></textarea>
                 </div>
-                {
-                    if self.ai_loading {
+                <button
+                    class="button-compact"
+                    disabled={self.json_output.is_empty()}
+                    onclick={ctx.link().callback(|_| AppMsg::CompareWithCurrent)}
+                >
+                    { "Use current contract on the left" }
+                </button>
+                { comparison }
+                <div class="button-block">
+                    <button class="button button-primary" disabled={!both} onclick={ctx.link().callback(|_| AppMsg::DownloadOutput)}>
+                        { "Download report" }
+                    </button>
+                </div>
+            </div>
+        }
+    }
+
+    fn view_comparison_rows(&self, rows: &[ComparisonRow]) -> Html {
+        let changed = rows.iter().filter(|row| row.kind().is_some()).count();
+
+        html! {
+            <>
+                <p>{ format!("{} changed, {} unchanged", changed, rows.len() - changed) }</p>
+                <table class="compare-table">
+                    <thead>
+                        <tr><th></th><th>{ "Left" }</th><th>{ "Right" }</th></tr>
+                    </thead>
+                    <tbody>
+                    { for rows.iter().map(|row| {
+                        let kind = row.kind();
+                        let class = classes!(
+                            "compare-row",
+                            kind.map(|kind| kind.as_str()),
+                            (row.target == ChangeTarget::DocumentType).then_some("compare-document-type"),
+                        );
                         html! {
-                            <div class="loader_ai"></div>
+                            <tr class={class}>
+                                <td>{ row.label() }</td>
+                                <td>{ row.left.clone().unwrap_or_default() }</td>
+                                <td>
+                                    { row.right.clone().unwrap_or_default() }
+                                    { for row.details.iter().map(|detail| html! {
+                                        <div class="compare-detail">{ detail }</div>
+                                    }) }
+                                </td>
+                            </tr>
                         }
-                    } else {
-                        html! {}
-                    }
-                }
-                {
-                    if !self.ai_errors.is_empty() {
-                        html! {<div class="error-text_ai">{for self.ai_errors.iter().map(|e| html!{<>{e}</>})}</div>}
-                    } else {
-                        html! {}
-                    }
-                }
+                    }) }
+                    </tbody>
+                </table>
+            </>
+        }
+    }
+
+    fn view_diagram_output(&self, ctx: &Context<Self>) -> Html {
+        let svg = DiagramService::to_svg(&self.document_types);
+        let edges = DiagramService::edges(&self.document_types);
+        let doc_names: Vec<String> = self
+            .document_types
+            .iter()
+            .filter(|d| !d.name.is_empty())
+            .map(|d| d.name.clone())
+            .collect();
+
+        html! {
+            <div class="input-container">
+                { self.view_output_tabs(ctx) }
+                <h2>{ self.output_tab.title() }</h2>
+                <div class="diagram">
+                    { Html::from_html_unchecked(AttrValue::from(svg)) }
+                </div>
+                <h3>{ "References" }</h3>
+                <p class="diagram-hint">
+                    { "Identifier properties point to the document type named at the end of \
+                       the property name, such as " }
+                    <code>{ "postId" }</code>{ ". Pick a document type to declare the reference explicitly." }
+                </p>
+                <div class="diagram-references">
+                { for self.document_types.iter().enumerate().flat_map(|(doc_index, doc_type)| {
+                    doc_type.properties.iter().enumerate()
+                        .filter(|(_, p)| !p.name.is_empty() && p.is_identifier())
+                        .map(|(prop_index, prop)| {
+                            let target = edges
+                                .iter()
+                                .find(|e| e.from == doc_type.name && e.property == prop.name);
+                            let declared = prop.ref_document_type.clone().unwrap_or_default();
+                            let inferred_label = match target {
+                                Some(edge) if edge.inferred => format!("Inferred: {}", edge.to),
+                                _ => "Inferred: none".to_string(),
+                            };
+                            html! {
+                                <div class="diagram-reference">
+                                    <code>{ format!("{}.{}", doc_type.name, prop.name) }</code>
+                                    <select onchange={ctx.link().callback(move |e: Event| {
+                                        let select = e.target_unchecked_into::<web_sys::HtmlSelectElement>();
+                                        AppMsg::UpdatePropertyReference(doc_index, prop_index, select.value())
+                                    })}>
+                                        <option value="" selected={declared.is_empty()}>{ inferred_label }</option>
+                                        { for doc_names.iter().map(|name| html! {
+                                            <option value={name.clone()} selected={*name == declared}>{ name }</option>
+                                        }) }
+                                    </select>
+                                </div>
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                }) }
+                </div>
+                <div class="button-block">
+                    <button class="button button-primary" onclick={ctx.link().callback(|_| AppMsg::DownloadDiagram(DiagramFormat::Svg))}>
+                        { "Download SVG" }
+                    </button>
+                    <button class="button button-primary" onclick={ctx.link().callback(|_| AppMsg::DownloadDiagram(DiagramFormat::Mermaid))}>
+                        { "Download Mermaid" }
+                    </button>
+                    <button class="button button-primary" onclick={ctx.link().callback(|_| AppMsg::DownloadDiagram(DiagramFormat::Graphviz))}>
+                        { "Download Graphviz" }
+                    </button>
+                </div>
             </div>
         }
     }
 
-    fn view_form_section(&self, ctx: &Context<Self>) -> Html {
-        self.view_full_form_section(ctx)
+    fn view_generated_code(&self, ctx: &Context<Self>, code: String, extra_buttons: Html) -> Html {
+        html! {
+            <div class="input-container">
+                { self.view_output_tabs(ctx) }
+                <h2>{ self.output_tab.title() }</h2>
+                <pre>
+                    <textarea
+                        class="textarea-whitespace"
+                        readonly=true
+                        value={code}
+                    ></textarea>
+                </pre>
+                <div class="button-block">
+                    <button class="button button-primary" onclick={ctx.link().callback(|_| AppMsg::DownloadOutput)}>
+                        { "Download" }
+                    </button>
+                    { extra_buttons }
+                </div>
+            </div>
+        }
     }
 
-    fn view_output_section(&self, ctx: &Context<Self>) -> Html {
-        let json_pretty = if self.show_formatted {
-            serde_json::to_string_pretty(
-                &serde_json::from_str::<serde_json::Value>(&self.json_output).unwrap_or_default(),
-            )
-            .unwrap_or_default()
-        } else {
-            self.json_output.clone()
-        };
+    fn view_json_output(&self, ctx: &Context<Self>) -> Html {
+        let import_failed = self.import_diagnostics.iter().any(Diagnostic::is_error);
+        // Problems in converted JSON5 or YAML are underlined in the conversion, not the pasted text
+        let underline_import = import_failed && self.import_normalized.is_none();
+        // Keeps the underlines lined up with the text as it scrolls
+        let highlights = self.import_highlights.clone();
+        let on_import_scroll = Callback::from(move |e: Event| {
+            if let (Some(textarea), Some(layer)) = (
+                e.target_dyn_into::<web_sys::Element>(),
+                highlights.cast::<web_sys::Element>(),
+            ) {
+                layer.set_scroll_top(textarea.scroll_top());
+                layer.set_scroll_left(textarea.scroll_left());
+            }
+        });
 
         html! {
             <div class="input-container">
-                <h2>{ "Contract" }</h2>
+                { self.view_output_tabs(ctx) }
+                <h2>{ self.output_tab.title() }</h2>
 
                 <div>{
                     if self.import_json.is_empty() && !self.validation_errors.is_empty() {
@@ -1003,20 +2898,33 @@ impl App {
                     }
                 }</div>
 
-                <pre>
+                <pre class={classes!("import-editor", underline_import.then_some("has-diagnostics"))}>
+                    if underline_import {
+                        <div class="import-highlights textarea-whitespace" ref={self.import_highlights.clone()}>
+                            { self.view_import_highlights(&self.import_json) }
+                        </div>
+                    }
                     <textarea
                         class="textarea-whitespace"
                         id="json_output"
                         placeholder="Paste here to import"
-                        value={if self.json_output.is_empty() { self.import_json.clone() } else { json_pretty }}
+                        value={if self.json_output.is_empty() || import_failed { self.import_json.clone() } else { self.json_output.clone() }}
                         oninput={ctx.link().callback(|e: InputEvent| {
                             let target = e.target().expect("Event should have target");
                             let textarea = target.dyn_into::<web_sys::HtmlTextAreaElement>().expect("Target should be textarea");
                             AppMsg::UpdateImportJson(textarea.value())
                         })}
+                        onscroll={on_import_scroll}
                     ></textarea>
                 </pre>
-
+                if let Some(normalized) = self.import_normalized.as_ref().filter(|_| import_failed) {
+                    <details class="import-normalized" open={true}>
+                        <summary>{ "Converted JSON, with the problems underlined" }</summary>
+                        <pre class="textarea-whitespace">{ self.view_import_highlights(normalized) }</pre>
+                    </details>
+                }
+                { self.view_import_diagnostics() }
+                { self.view_output_format(ctx) }
 
                 <p>{
                     if self.json_output.len() > 2 {
@@ -1025,6 +2933,16 @@ impl App {
                         "Size: 0 bytes".to_string()
                     }
                 }</p>
+                { if let Some(Ok(fingerprint)) = &self.fingerprint {
+                    html! {
+                        <p class="contract-fingerprint" title="SHA-256 of the contract as DPP serializes it">
+                            { "Fingerprint: " }
+                            <code>{ fingerprint }</code>
+                        </p>
+                    }
+                } else {
+                    html! {}
+                }}
 
                 <div class="button-block">
                     <button class="button-clear" onclick={ctx.link().callback(|_| AppMsg::Clear)}>
@@ -1048,16 +2966,128 @@ impl App {
                     </button>
                 </div>
 
-                <div class="prompt-history">
-                    { if !self.prompt_history.is_empty() {
-                        html! { <h3>{ "Prompt history:" }</h3> }
-                    } else {
-                        html! {}
-                    }}
-                    { for self.prompt_history.iter().map(|prompt| html! {
-                        <div>{ prompt }</div>
-                    }) }
-                </div>
+                { self.view_conversation(ctx) }
+            </div>
+        }
+    }
+
+    fn view_output_format(&self, ctx: &Context<Self>) -> Html {
+        let format = self.output_format;
+
+        let on_key_order = ctx.link().batch_callback(move |e: Event| {
+            let select = e.target_unchecked_into::<web_sys::HtmlSelectElement>();
+            let key_order = KeyOrder::from_id(&select.value())?;
+            Some(AppMsg::UpdateOutputFormat(OutputFormat { key_order, ..format }))
+        });
+        let on_property_order = ctx.link().batch_callback(move |e: Event| {
+            let select = e.target_unchecked_into::<web_sys::HtmlSelectElement>();
+            let property_order = PropertyOrder::from_id(&select.value())?;
+            Some(AppMsg::UpdateOutputFormat(OutputFormat { property_order, ..format }))
+        });
+        let on_indent = ctx.link().batch_callback(move |e: Event| {
+            let input = e.target_unchecked_into::<HtmlInputElement>();
+            let indent = input.value().trim().parse::<usize>().ok()?;
+            Some(AppMsg::UpdateOutputFormat(OutputFormat { indent, ..format }))
+        });
+        let on_defaults = ctx.link().callback(move |e: Event| {
+            let input = e.target_unchecked_into::<HtmlInputElement>();
+            AppMsg::UpdateOutputFormat(OutputFormat {
+                explicit_defaults: input.checked(),
+                ..format
+            })
+        });
+
+        html! {
+            <div class="output-format">
+                <label>
+                    { "Keys " }
+                    <select onchange={on_key_order}>
+                        { for KeyOrder::all().into_iter().map(|order| html! {
+                            <option value={order.id()} selected={order == format.key_order}>{ order.label() }</option>
+                        }) }
+                    </select>
+                </label>
+                <label>
+                    { "Properties " }
+                    <select onchange={on_property_order}>
+                        { for PropertyOrder::all().into_iter().map(|order| html! {
+                            <option value={order.id()} selected={order == format.property_order}>{ order.label() }</option>
+                        }) }
+                    </select>
+                </label>
+                <label title="Spaces per level, 0 for a single line">
+                    { "Indent " }
+                    <input
+                        type="number"
+                        min="0"
+                        max={MAX_INDENT.to_string()}
+                        value={format.indent.to_string()}
+                        onchange={on_indent}
+                    />
+                </label>
+                <label>
+                    <input type="checkbox" checked={format.explicit_defaults} onchange={on_defaults} />
+                    { " Write default values" }
+                </label>
+            </div>
+        }
+    }
+
+    fn view_conversation(&self, ctx: &Context<Self>) -> Html {
+        if self.conversation.is_empty() {
+            return html! {};
+        }
+
+        let active: Vec<usize> = self
+            .conversation
+            .active_path()
+            .iter()
+            .map(|turn| turn.id)
+            .collect();
+
+        html! {
+            <div class="prompt-history">
+                <h3>{ "Prompt history:" }</h3>
+                { for self.conversation.tree_order().into_iter().map(|turn| {
+                    let id = turn.id;
+                    let depth = self.conversation.depth(id);
+                    let mut class = String::from("conversation-turn");
+                    if active.contains(&id) {
+                        class.push_str(" active");
+                    }
+                    if self.conversation.head == Some(id) {
+                        class.push_str(" head");
+                    }
+                    html! {
+                        <div class={class} style={format!("margin-left: {}px", depth * 20)}>
+                            <div class="conversation-turn-header">
+                                <span class="conversation-turn-prompt">{ &turn.prompt }</span>
+                                <button class="button-compact" onclick={ctx.link().callback(move |_| AppMsg::ViewConversationTurn(id))}>
+                                    { if self.viewed_turn == Some(id) { "Hide" } else { "View" } }
+                                </button>
+                                <button class="button-compact" onclick={ctx.link().callback(move |_| AppMsg::BranchFromTurn(id))}>
+                                    { "Branch from here" }
+                                </button>
+                            </div>
+                            { if self.viewed_turn == Some(id) {
+                                html! {
+                                    <pre class="conversation-turn-schema">{ &turn.schema }</pre>
+                                }
+                            } else {
+                                html! {}
+                            }}
+                        </div>
+                    }
+                }) }
+                { if self.conversation.head.is_some() {
+                    html! {
+                        <button class="button-compact" onclick={ctx.link().callback(|_| AppMsg::StartNewConversation)}>
+                            { "Start new conversation" }
+                        </button>
+                    }
+                } else {
+                    html! {}
+                }}
             </div>
         }
     }
@@ -1079,8 +3109,13 @@ impl App {
         }
 
         // Generate compact JSON without whitespace
-        let json_value = JsonGenerator::generate_contract(&self.document_types);
-        let compact_json = serde_json::to_string(&json_value).unwrap_or_default();
+        let compact_json =
+            JsonGenerator::generate_text(&self.document_types, &self.output_format.compact());
+        let compare = self.fingerprint_compare.trim();
+        let on_compare = ctx.link().callback(|e: InputEvent| {
+            let input = e.target_unchecked_into::<HtmlInputElement>();
+            AppMsg::UpdateFingerprintCompare(input.value())
+        });
 
         html! {
             <div class="popup-overlay" onclick={ctx.link().callback(|_| AppMsg::HideCompactPopup)}>
@@ -1103,6 +3138,101 @@ impl App {
                         <p class="popup-size">
                             { format!("Size: {} bytes", compact_json.len()) }
                         </p>
+                        { match &self.fingerprint {
+                            Some(Ok(fingerprint)) => html! {
+                                <>
+                                    <p class="contract-fingerprint" title="SHA-256 of the contract as DPP serializes it">
+                                        { "Fingerprint: " }
+                                        <code>{ fingerprint }</code>
+                                    </p>
+                                    <label class="contract-fingerprint-compare">
+                                        { "Compare with fingerprint " }
+                                        <input
+                                            value={self.fingerprint_compare.clone()}
+                                            placeholder="Paste a fingerprint"
+                                            oninput={on_compare}
+                                        />
+                                    </label>
+                                    { if compare.is_empty() {
+                                        html! {}
+                                    } else if ContractFingerprint::matches(fingerprint, compare) {
+                                        html! { <p class="passed-text">{ "Fingerprints match ✓" }</p> }
+                                    } else {
+                                        html! { <p class="error-text">{ "Fingerprints differ: this is not the same contract" }</p> }
+                                    }}
+                                </>
+                            },
+                            Some(Err(e)) => html! {
+                                <p class="error-text">{ format!("No fingerprint until the contract is valid: {}", e) }</p>
+                            },
+                            None => html! {},
+                        }}
+                    </div>
+                </div>
+            </div>
+        }
+    }
+
+    fn view_ai_edit_popup(&self, ctx: &Context<Self>) -> Html {
+        let Some(scope) = &self.ai_edit_scope else {
+            return html! {};
+        };
+        let label = ScopedEditService::label(&self.document_types, scope).unwrap_or_default();
+
+        let on_instruction_change = ctx.link().callback(|e: InputEvent| {
+            let textarea = e.target_unchecked_into::<web_sys::HtmlTextAreaElement>();
+            AppMsg::UpdateAiEditInstruction(textarea.value())
+        });
+
+        let onsubmit = ctx.link().callback(|event: SubmitEvent| {
+            event.prevent_default();
+            AppMsg::SendAiEdit
+        });
+
+        html! {
+            <div class="popup-overlay" onclick={ctx.link().callback(|_| AppMsg::CloseAiEdit)}>
+                <div class="popup-content ai-edit" onclick={ctx.link().callback(|e: MouseEvent| {
+                    e.stop_propagation();
+                    AppMsg::PopupContentClick
+                })}>
+                    <div class="popup-header">
+                        <h3>{ format!("Ask AI about {}", label) }</h3>
+                        <button class="popup-close" onclick={ctx.link().callback(|_| AppMsg::CloseAiEdit)}>
+                            { "×" }
+                        </button>
+                    </div>
+                    <div class="popup-body">
+                        <p>{ "Only this part of the contract is sent to the AI, and only this part is changed." }</p>
+                        <div class="ai-edit-presets">
+                            { for scope.presets().iter().map(|preset| {
+                                let instruction = preset.to_string();
+                                html! {
+                                    <button
+                                        type="button"
+                                        class="button-compact"
+                                        onclick={ctx.link().callback(move |_| {
+                                            AppMsg::UpdateAiEditInstruction(instruction.clone())
+                                        })}
+                                    >
+                                        { preset.trim_end_matches([':', ' ']) }
+                                    </button>
+                                }
+                            }) }
+                        </div>
+                        <form {onsubmit}>
+                            <textarea
+                                class="ai-edit-instruction"
+                                placeholder="Describe the change"
+                                value={self.ai_edit_instruction.clone()}
+                                oninput={on_instruction_change}
+                            ></textarea>
+                            <button type="submit" class="button button-primary" disabled={self.ai_loading}>
+                                { "Ask AI" }
+                            </button>
+                        </form>
+                        { self.view_ai_status(ctx) }
+                        { self.view_sent_prompt(ctx) }
+                        { self.view_review(ctx) }
                     </div>
                 </div>
             </div>
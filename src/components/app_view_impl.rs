@@ -5,7 +5,8 @@ use yew::events::{Event, MouseEvent};
 use yew::prelude::*;
 
 use super::app::{App, AppMsg};
-use crate::types::{DataType, Property};
+use crate::services::EditScope;
+use crate::types::{DataType, PathStep, Property};
 
 impl App {
     pub fn view_full_form_section(&self, ctx: &Context<Self>) -> Html {
@@ -28,6 +29,7 @@ impl App {
 
     fn view_document_type_full(&self, ctx: &Context<Self>, index: usize) -> Html {
         let doc_type = &self.document_types[index];
+        let edit_scope = EditScope::DocumentType(doc_type.name.clone());
 
         html! {
             <>
@@ -44,6 +46,13 @@ impl App {
                                 AppMsg::UpdateDocumentTypeName(index, input.value())
                             })}
                         />
+                        <button
+                            class="button-compact ask-ai"
+                            title="Ask AI to edit this document type"
+                            onclick={ctx.link().callback(move |_| AppMsg::OpenAiEdit(edit_scope.clone()))}
+                        >
+                            { "Ask AI" }
+                        </button>
                         <button
                             class="button remove"
                             onclick={ctx.link().callback(move |_| AppMsg::RemoveDocumentType(index))}
@@ -235,6 +244,10 @@ impl App {
 
     fn view_property_full(&self, ctx: &Context<Self>, doc_index: usize, prop_index: usize) -> Html {
         let property = &self.document_types[doc_index].properties[prop_index];
+        let edit_scope = EditScope::Property(
+            self.document_types[doc_index].name.clone(),
+            property.name.clone(),
+        );
         let data_type_options = vec!["String", "Integer", "Array", "Object", "Number", "Boolean"];
         let selected_data_type = match property.data_type {
             DataType::String => "String",
@@ -262,6 +275,13 @@ impl App {
                             AppMsg::UpdatePropertyName(doc_index, prop_index, input.value())
                         })}
                     />
+                    <button
+                        class="button-compact ask-ai"
+                        title="Ask AI to edit this property"
+                        onclick={ctx.link().callback(move |_| AppMsg::OpenAiEdit(edit_scope.clone()))}
+                    >
+                        { "Ask AI" }
+                    </button>
                     <button
                         class="button remove"
                         onclick={ctx.link().callback(move |_| AppMsg::RemoveProperty(doc_index, prop_index))}
@@ -311,6 +331,22 @@ impl App {
                             <span class="checkmark"></span>
                         </label>
                     </div>
+
+                    <div class="form-headers checkbox-block">
+                        <label>{ "Transient" }</label>
+                        <label class="container-checkbox">
+                            <input
+                                type="checkbox"
+                                checked={property.transient}
+                                onchange={ctx.link().callback(move |e: Event| {
+                                    let target = e.target().expect("Event should have target");
+                                    let input = target.dyn_into::<HtmlInputElement>().expect("Target should be input element");
+                                    AppMsg::UpdatePropertyTransient(doc_index, prop_index, input.checked())
+                                })}
+                            />
+                            <span class="checkmark"></span>
+                        </label>
+                    </div>
                 </div>
 
                 { if property.data_type != DataType::Object {
@@ -350,8 +386,7 @@ impl App {
                                                 oninput={ctx.link().callback(move |e: InputEvent| {
                                                     let target = e.target().expect("Event should have target");
                                                     let input = target.dyn_into::<HtmlInputElement>().expect("Target should be input element");
-                                                    // Note: We don't have UpdatePropertyComment in our AppMsg, so this is a no-op for now
-                                                    AppMsg::UpdatePropertyDescription(doc_index, prop_index, input.value())
+                                                    AppMsg::UpdatePropertyComment(doc_index, prop_index, input.value())
                                                 })}
                                             />
                                         </div>
@@ -389,7 +424,7 @@ impl App {
                                         oninput={ctx.link().callback(move |e: InputEvent| {
                                             let target = e.target().expect("Event should have target");
                                             let input = target.dyn_into::<HtmlInputElement>().expect("Target should be input element");
-                                            AppMsg::UpdatePropertyDescription(doc_index, prop_index, input.value())
+                                            AppMsg::UpdatePropertyComment(doc_index, prop_index, input.value())
                                         })}
                                     />
                                 </div>
@@ -507,6 +542,24 @@ impl App {
                             })}
                         />
                     </div>
+                    <div class="forms-line">
+                        <label>{ "Allowed values " }</label>
+                        <input
+                            type="text3"
+                            placeholder="Comma-separated, leave empty for any value"
+                            value={property.enum_values_display()}
+                            onchange={ctx.link().callback(move |e: Event| {
+                                let target = e.target().expect("Event should have target");
+                                let input = target.dyn_into::<HtmlInputElement>().expect("Target should be input element");
+                                AppMsg::UpdatePropertyEnum(doc_index, prop_index, input.value())
+                            })}
+                        />
+                        { if let Some(error) = property.enum_input_error() {
+                            html! { <p class="error-text">{ error }</p> }
+                        } else {
+                            html! {}
+                        }}
+                    </div>
                 </>
             },
             DataType::Integer | DataType::Number => html! {
@@ -516,7 +569,7 @@ impl App {
                             <label>{ "Minimum " }</label>
                             <input
                                 type="number"
-                                value={property.minimum.map(|n| n.to_string()).unwrap_or_default()}
+                                value={property.minimum.as_ref().map(|n| n.to_string()).unwrap_or_default()}
                                 oninput={ctx.link().callback(move |e: InputEvent| {
                                     let target = e.target().expect("Event should have target");
                                     let input = target.dyn_into::<HtmlInputElement>().expect("Target should be input element");
@@ -528,7 +581,7 @@ impl App {
                             <label>{ "Maximum " }</label>
                             <input
                                 type="number"
-                                value={property.maximum.map(|n| n.to_string()).unwrap_or_default()}
+                                value={property.maximum.as_ref().map(|n| n.to_string()).unwrap_or_default()}
                                 oninput={ctx.link().callback(move |e: InputEvent| {
                                     let target = e.target().expect("Event should have target");
                                     let input = target.dyn_into::<HtmlInputElement>().expect("Target should be input element");
@@ -537,10 +590,29 @@ impl App {
                             />
                         </div>
                     </div>
+                    <div class="forms-line">
+                        <label>{ "Allowed values " }</label>
+                        <input
+                            type="text3"
+                            placeholder="Comma-separated, leave empty for any value"
+                            value={property.enum_values_display()}
+                            onchange={ctx.link().callback(move |e: Event| {
+                                let target = e.target().expect("Event should have target");
+                                let input = target.dyn_into::<HtmlInputElement>().expect("Target should be input element");
+                                AppMsg::UpdatePropertyEnum(doc_index, prop_index, input.value())
+                            })}
+                        />
+                        { if let Some(error) = property.enum_input_error() {
+                            html! { <p class="error-text">{ error }</p> }
+                        } else {
+                            html! {}
+                        }}
+                    </div>
                 </>
             },
             DataType::Array => html! {
                 <>
+                    { self.view_array_items(ctx, doc_index, prop_index, Vec::new(), property, 0) }
                     <div class="forms-line number-block">
                         <div class="forms-line min">
                             <label>{ "Min items " }</label>
@@ -567,6 +639,7 @@ impl App {
                             />
                         </div>
                     </div>
+                    if property.is_byte_array() {
                     <div class="forms-line">
                         <label class="label-with-info">
                             <span>{ "Content media type " }</span>
@@ -601,6 +674,7 @@ impl App {
                             })}
                         />
                     </div>
+                    }
                 </>
             },
             DataType::Object => html! {
@@ -617,7 +691,7 @@ impl App {
                             html! {
                                 <>
                                     { for properties.iter().enumerate().map(|(nested_index, _)| {
-                                        self.view_nested_property(ctx, doc_index, prop_index, vec![nested_index], 0)
+                                        self.view_nested_property(ctx, doc_index, prop_index, vec![PathStep::Property(nested_index)], 0)
                                     }) }
                                 </>
                             }
@@ -808,7 +882,7 @@ impl App {
         ctx: &Context<Self>,
         doc_index: usize,
         prop_index: usize,
-        nested_indices: Vec<usize>,
+        nested_indices: Vec<PathStep>,
         depth: usize,
     ) -> Html {
         // Limit nesting to 2 additional layers (depth 0 is the first nested level)
@@ -817,25 +891,7 @@ impl App {
         }
 
         // Get the nested property
-        let property = if let Some(doc_type) = self.document_types.get(doc_index) {
-            if let Some(parent_prop) = doc_type.properties.get(prop_index) {
-                let mut current_prop = parent_prop;
-                for &idx in &nested_indices {
-                    if let Some(props) = &current_prop.properties {
-                        if let Some(prop) = props.get(idx) {
-                            current_prop = prop;
-                        } else {
-                            return html! {};
-                        }
-                    } else {
-                        return html! {};
-                    }
-                }
-                current_prop
-            } else {
-                return html! {};
-            }
-        } else {
+        let Some(property) = self.get_nested_property(doc_index, prop_index, &nested_indices) else {
             return html! {};
         };
 
@@ -937,7 +993,7 @@ impl App {
                     html! {
                         <div class="optional-fields-section">
                             <div class="optional-fields-content">
-                                { self.render_nested_additional_properties(ctx, doc_index, prop_index, nested_indices_for_render.clone(), property) }
+                                { self.render_nested_additional_properties(ctx, doc_index, prop_index, nested_indices_for_render.clone(), property, depth) }
                             </div>
                         </div>
                     }
@@ -963,7 +1019,7 @@ impl App {
                                             <>
                                                 { for properties.iter().enumerate().map(|(inner_index, _)| {
                                                     let mut new_indices = nested_indices.clone();
-                                                    new_indices.push(inner_index);
+                                                    new_indices.push(PathStep::Property(inner_index));
                                                     self.view_nested_property(ctx, doc_index, prop_index, new_indices, depth + 1)
                                                 }) }
                                             </>
@@ -1002,8 +1058,9 @@ impl App {
         ctx: &Context<Self>,
         doc_index: usize,
         prop_index: usize,
-        nested_indices: Vec<usize>,
+        nested_indices: Vec<PathStep>,
         property: &Property,
+        depth: usize,
     ) -> Html {
         // Clone nested_indices for each closure
         let nested_indices_min_len = nested_indices.clone();
@@ -1012,6 +1069,8 @@ impl App {
         let nested_indices_format = nested_indices.clone();
         let nested_indices_minimum = nested_indices.clone();
         let nested_indices_maximum = nested_indices.clone();
+        let nested_indices_string_enum = nested_indices.clone();
+        let nested_indices_number_enum = nested_indices.clone();
 
         match property.data_type {
             DataType::String => html! {
@@ -1066,6 +1125,24 @@ impl App {
                             })}
                         />
                     </div>
+                    <div class="forms-line">
+                        <label>{ "Allowed values " }</label>
+                        <input
+                            type="text3"
+                            placeholder="Comma-separated, leave empty for any value"
+                            value={property.enum_values_display()}
+                            onchange={ctx.link().callback(move |e: Event| {
+                                let target = e.target().expect("Event should have target");
+                                let input = target.dyn_into::<HtmlInputElement>().expect("Target should be input element");
+                                AppMsg::UpdateNestedPropertyEnum(doc_index, prop_index, nested_indices_string_enum.clone(), input.value())
+                            })}
+                        />
+                        { if let Some(error) = property.enum_input_error() {
+                            html! { <p class="error-text">{ error }</p> }
+                        } else {
+                            html! {}
+                        }}
+                    </div>
                 </>
             },
             DataType::Integer | DataType::Number => html! {
@@ -1075,7 +1152,7 @@ impl App {
                             <label>{ "Minimum " }</label>
                             <input
                                 type="number"
-                                value={property.minimum.map(|n| n.to_string()).unwrap_or_default()}
+                                value={property.minimum.as_ref().map(|n| n.to_string()).unwrap_or_default()}
                                 oninput={ctx.link().callback(move |e: InputEvent| {
                                     let target = e.target().expect("Event should have target");
                                     let input = target.dyn_into::<HtmlInputElement>().expect("Target should be input element");
@@ -1087,7 +1164,7 @@ impl App {
                             <label>{ "Maximum " }</label>
                             <input
                                 type="number"
-                                value={property.maximum.map(|n| n.to_string()).unwrap_or_default()}
+                                value={property.maximum.as_ref().map(|n| n.to_string()).unwrap_or_default()}
                                 oninput={ctx.link().callback(move |e: InputEvent| {
                                     let target = e.target().expect("Event should have target");
                                     let input = target.dyn_into::<HtmlInputElement>().expect("Target should be input element");
@@ -1096,14 +1173,111 @@ impl App {
                             />
                         </div>
                     </div>
+                    <div class="forms-line">
+                        <label>{ "Allowed values " }</label>
+                        <input
+                            type="text3"
+                            placeholder="Comma-separated, leave empty for any value"
+                            value={property.enum_values_display()}
+                            onchange={ctx.link().callback(move |e: Event| {
+                                let target = e.target().expect("Event should have target");
+                                let input = target.dyn_into::<HtmlInputElement>().expect("Target should be input element");
+                                AppMsg::UpdateNestedPropertyEnum(doc_index, prop_index, nested_indices_number_enum.clone(), input.value())
+                            })}
+                        />
+                        { if let Some(error) = property.enum_input_error() {
+                            html! { <p class="error-text">{ error }</p> }
+                        } else {
+                            html! {}
+                        }}
+                    </div>
                 </>
             },
-            DataType::Array => html! {
-                <>
-                    <p class="info-text">{ "Note: Arrays in Dash Platform are byte arrays" }</p>
-                </>
-            },
+            DataType::Array => self.view_array_items(ctx, doc_index, prop_index, nested_indices, property, depth + 1),
             _ => html! {},
         }
     }
+
+    /// Lets an array hold bytes or typed items, with the fields of its items
+    fn view_array_items(
+        &self,
+        ctx: &Context<Self>,
+        doc_index: usize,
+        prop_index: usize,
+        nested_indices: Vec<PathStep>,
+        property: &Property,
+        depth: usize,
+    ) -> Html {
+        let item_options = ["Bytes", "String", "Integer", "Number", "Boolean", "Object", "Array"];
+        let selected_item = match property.items.as_deref().map(|items| &items.data_type) {
+            None => "Bytes",
+            Some(DataType::String) => "String",
+            Some(DataType::Integer) => "Integer",
+            Some(DataType::Number) => "Number",
+            Some(DataType::Boolean) => "Boolean",
+            Some(DataType::Object) => "Object",
+            Some(DataType::Array) => "Array",
+        };
+
+        let mut items_indices = nested_indices.clone();
+        items_indices.push(PathStep::Items);
+        let items_indices_for_add = items_indices.clone();
+
+        html! {
+            <>
+                <div class="forms-line">
+                    <label>{ "Items " }</label>
+                    <select
+                        value={selected_item}
+                        onchange={ctx.link().callback(move |e: Event| {
+                            let target = e.target().expect("Event should have target");
+                            let select = target.dyn_into::<HtmlSelectElement>().expect("Target should be select element");
+                            let data_type = match select.value().as_str() {
+                                "String" => Some(DataType::String),
+                                "Integer" => Some(DataType::Integer),
+                                "Number" => Some(DataType::Number),
+                                "Boolean" => Some(DataType::Boolean),
+                                "Object" => Some(DataType::Object),
+                                "Array" => Some(DataType::Array),
+                                _ => None,
+                            };
+                            AppMsg::UpdateArrayItems(doc_index, prop_index, nested_indices.clone(), data_type)
+                        })}
+                    >
+                        { for item_options.iter().map(|option| html! {
+                            <option value={*option} selected={*option == selected_item}>{ *option }</option>
+                        }) }
+                    </select>
+                </div>
+
+                { match property.items.as_deref() {
+                    Some(items) if items.data_type == DataType::Object => html! {
+                        <>
+                            <div class="nested-properties">
+                                { for items.properties.iter().flat_map(|properties| properties.iter()).enumerate().map(|(inner_index, _)| {
+                                    let mut inner_indices = items_indices.clone();
+                                    inner_indices.push(PathStep::Property(inner_index));
+                                    self.view_nested_property(ctx, doc_index, prop_index, inner_indices, depth)
+                                }) }
+                            </div>
+                            <div class="forms-line">
+                                <button
+                                    class="button"
+                                    onclick={ctx.link().callback(move |_| AppMsg::AddNestedProperty(doc_index, prop_index, items_indices_for_add.clone()))}
+                                >
+                                    { "Add item property" }
+                                </button>
+                            </div>
+                        </>
+                    },
+                    Some(items) => html! {
+                        <div class="nested-properties">
+                            { self.render_nested_additional_properties(ctx, doc_index, prop_index, items_indices.clone(), items, depth) }
+                        </div>
+                    },
+                    None => html! {},
+                }}
+            </>
+        }
+    }
 }
@@ -0,0 +1,303 @@
+use anyhow::{anyhow, Result};
+use std::cell::RefCell;
+use std::future::Future;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    AbortSignal, ReadableStreamDefaultReader, Request, RequestInit, RequestMode, Response,
+};
+
+/// Raw HTTP response from a chat completions endpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransportResponse {
+    pub status: u16,
+    /// Whether the body is a server-sent event stream
+    pub event_stream: bool,
+    /// Whole body, except for successful event streams, which are passed to `on_chunk`
+    pub body: String,
+}
+
+impl TransportResponse {
+    /// Returns true for 2xx statuses
+    pub fn ok(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Formats the response as a recording that `from_recording` reads back
+    pub fn to_recording(&self) -> String {
+        let content_type = if self.event_stream {
+            "text/event-stream"
+        } else {
+            "application/json"
+        };
+        format!(
+            "HTTP {}\ncontent-type: {}\n\n{}",
+            self.status, content_type, self.body
+        )
+    }
+
+    /// Reads a recording: a status line, headers, a blank line and the body
+    pub fn from_recording(recording: &str) -> Result<Self, String> {
+        let (head, body) = recording
+            .split_once("\n\n")
+            .ok_or("Recording has no blank line between headers and body")?;
+        let mut lines = head.lines();
+        let status = lines
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|status| status.parse::<u16>().ok())
+            .ok_or("Recording does not start with a status line such as `HTTP 200`")?;
+        let event_stream = lines.any(|line| {
+            line.split_once(':').is_some_and(|(name, value)| {
+                name.trim().eq_ignore_ascii_case("content-type")
+                    && value.trim().starts_with("text/event-stream")
+            })
+        });
+
+        Ok(Self {
+            status,
+            event_stream,
+            body: body.to_string(),
+        })
+    }
+}
+
+/// Sends chat completions requests, so the response handling can be tested without network
+pub trait AiTransport {
+    /// Posts a request body and returns the response
+    ///
+    /// Successful event-stream bodies are passed to `on_chunk` as they arrive;
+    /// it returns false to stop reading. Other bodies are returned whole.
+    fn send(
+        &self,
+        body: &str,
+        on_chunk: &mut dyn FnMut(&[u8]) -> bool,
+    ) -> impl Future<Output = Result<TransportResponse>>;
+}
+
+/// Transport using the browser's `fetch`
+pub struct FetchTransport {
+    signal: Option<AbortSignal>,
+}
+
+impl FetchTransport {
+    /// Creates a transport whose requests are aborted by `signal`
+    pub fn new(signal: Option<AbortSignal>) -> Self {
+        Self { signal }
+    }
+
+    /// Builds the chat completions request
+    fn build_request(&self, body: &str) -> Result<Request> {
+        let mut opts = RequestInit::new();
+        let headers =
+            web_sys::Headers::new().map_err(|e| anyhow!("Failed to create headers: {:?}", e))?;
+
+        headers
+            .append("Content-Type", "application/json")
+            .map_err(|e| anyhow!("Failed to set content type: {:?}", e))?;
+
+        // Note: For local testing, uncomment and add your API key:
+        // headers.append("Authorization", &format!("Bearer {}", "YOUR_API_KEY_HERE"))
+        //     .map_err(|e| anyhow!("Failed to set authorization: {:?}", e))?;
+
+        opts.method("POST");
+        opts.headers(&headers);
+        opts.body(Some(&JsValue::from_str(body)));
+        opts.mode(RequestMode::Cors);
+        opts.signal(self.signal.as_ref());
+
+        // Use the Lambda endpoint for production, OpenAI directly for local testing
+        let url = "https://22vazdmku2qz3prrn57elhdj2i0wyejr.lambda-url.us-west-2.on.aws/";
+        // For local testing, use: "https://api.openai.com/v1/chat/completions"
+
+        Request::new_with_str_and_init(url, &opts)
+            .map_err(|e| anyhow!("Failed to create request: {:?}", e))
+    }
+
+    /// Sends a request and waits for the response headers
+    async fn fetch(request: &Request) -> Result<Response> {
+        let window = web_sys::window().ok_or_else(|| anyhow!("Failed to obtain window object"))?;
+
+        let response = JsFuture::from(window.fetch_with_request(request))
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "Fetch request failed: {:?}",
+                    e.as_string().unwrap_or_default()
+                )
+            })?;
+
+        response.dyn_into().map_err(|e| {
+            anyhow!(
+                "Failed to convert response: {:?}",
+                e.as_string().unwrap_or_default()
+            )
+        })
+    }
+
+    /// Reads a whole response body as text
+    async fn response_text(response: &Response) -> Result<String> {
+        let text = JsFuture::from(
+            response
+                .text()
+                .map_err(|_| anyhow!("Failed to read response text"))?,
+        )
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "Failed to get response text: {:?}",
+                e.as_string().unwrap_or_default()
+            )
+        })?;
+
+        text.as_string()
+            .ok_or_else(|| anyhow!("Failed to convert response to string"))
+    }
+
+    /// Passes body chunks to `on_chunk` until the body ends or `on_chunk` returns false
+    async fn read_chunks(
+        response: &Response,
+        on_chunk: &mut dyn FnMut(&[u8]) -> bool,
+    ) -> Result<()> {
+        let body = response
+            .body()
+            .ok_or_else(|| anyhow!("Streamed response has no body"))?;
+        let reader: ReadableStreamDefaultReader = body.get_reader().unchecked_into();
+
+        loop {
+            let chunk = JsFuture::from(reader.read())
+                .await
+                .map_err(|e| anyhow!("Failed to read stream: {:?}", e))?;
+            let done = js_sys::Reflect::get(&chunk, &JsValue::from_str("done"))
+                .ok()
+                .and_then(|done| done.as_bool())
+                .unwrap_or(true);
+            if done {
+                return Ok(());
+            }
+
+            let value = js_sys::Reflect::get(&chunk, &JsValue::from_str("value"))
+                .map_err(|e| anyhow!("Failed to read stream chunk: {:?}", e))?;
+            if !on_chunk(&js_sys::Uint8Array::new(&value).to_vec()) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl AiTransport for FetchTransport {
+    async fn send(
+        &self,
+        body: &str,
+        on_chunk: &mut dyn FnMut(&[u8]) -> bool,
+    ) -> Result<TransportResponse> {
+        let request = self.build_request(body)?;
+        let response = Self::fetch(&request).await?;
+
+        let event_stream = response
+            .headers()
+            .get("content-type")
+            .ok()
+            .flatten()
+            .is_some_and(|content_type| content_type.starts_with("text/event-stream"));
+        let mut transport_response = TransportResponse {
+            status: response.status(),
+            event_stream,
+            body: String::new(),
+        };
+
+        if response.ok() && event_stream {
+            Self::read_chunks(&response, on_chunk).await?;
+        } else {
+            transport_response.body = Self::response_text(&response).await?;
+        }
+
+        Ok(transport_response)
+    }
+}
+
+/// Transport that keeps a recording of each response received through another transport
+pub struct RecordingTransport<T> {
+    inner: T,
+    recording: Rc<RefCell<String>>,
+}
+
+impl<T: AiTransport> RecordingTransport<T> {
+    /// Records responses from `inner` into `recording`
+    pub fn new(inner: T, recording: Rc<RefCell<String>>) -> Self {
+        Self { inner, recording }
+    }
+}
+
+impl<T: AiTransport> AiTransport for RecordingTransport<T> {
+    async fn send(
+        &self,
+        body: &str,
+        on_chunk: &mut dyn FnMut(&[u8]) -> bool,
+    ) -> Result<TransportResponse> {
+        let mut streamed: Vec<u8> = Vec::new();
+        let response = self
+            .inner
+            .send(body, &mut |chunk| {
+                streamed.extend_from_slice(chunk);
+                on_chunk(chunk)
+            })
+            .await?;
+
+        let mut recorded = response.clone();
+        recorded.body.push_str(&String::from_utf8_lossy(&streamed));
+        *self.recording.borrow_mut() = recorded.to_recording();
+
+        Ok(response)
+    }
+}
+
+/// Transport that replays a recorded response, for tests
+pub struct FixtureTransport {
+    response: TransportResponse,
+    chunk_size: usize,
+    /// Request bodies sent so far
+    pub requests: RefCell<Vec<String>>,
+}
+
+impl FixtureTransport {
+    /// Replays a recording in the format of `TransportResponse::to_recording`
+    pub fn from_recording(recording: &str) -> Result<Self, String> {
+        Ok(Self {
+            response: TransportResponse::from_recording(recording)?,
+            chunk_size: usize::MAX,
+            requests: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Splits event streams into chunks of `chunk_size` bytes, like a slow network
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+}
+
+impl AiTransport for FixtureTransport {
+    async fn send(
+        &self,
+        body: &str,
+        on_chunk: &mut dyn FnMut(&[u8]) -> bool,
+    ) -> Result<TransportResponse> {
+        self.requests.borrow_mut().push(body.to_string());
+
+        if !(self.response.ok() && self.response.event_stream) {
+            return Ok(self.response.clone());
+        }
+
+        for chunk in self.response.body.as_bytes().chunks(self.chunk_size) {
+            if !on_chunk(chunk) {
+                break;
+            }
+        }
+        Ok(TransportResponse {
+            body: String::new(),
+            ..self.response.clone()
+        })
+    }
+}
@@ -0,0 +1,303 @@
+use serde_json::{Map, Value};
+
+/// Service for bringing contract JSON into the canonical form written by `JsonGenerator`
+///
+/// For every contract `JsonParser` accepts, generating from the parsed document
+/// types gives exactly `CanonicalForm::of` the contract. The canonical form is:
+///
+/// - Document types keep their order. Each has `type`, `properties`, `indices`,
+///   `required`, `transient`, `additionalProperties`, `description`, `keywords`
+///   and `$comment`, in that order. `type` is always `"object"` and `additionalProperties`
+///   defaults to `false`.
+/// - Properties are ordered by `position`, missing positions counting as 0, with
+///   ties kept in their written order. Names starting with `$` are left out.
+///   Each property has `position`, `type`, `description`, `$comment` and `enum`,
+///   then the keywords of its type:
+///   - string: `minLength`, `maxLength`, `pattern`, `format`
+///   - integer and number: `minimum`, `maximum`
+///   - array: either `byteArray` (always `true`) or `items`, then `minItems`,
+///     `maxItems` and, for byte arrays, `contentMediaType`. An array has `items`
+///     when the contract gives an `items` object and no `byteArray: true`;
+///     `items` is written like a property without `position`.
+///   - object: `properties`, `required`, `minProperties`, `maxProperties`,
+///     `additionalProperties`
+///
+///   A missing or unknown `type` means `"string"`. Keywords of other types are dropped.
+/// - `required` lists the required properties in property order, followed by
+///   `$createdAt` and `$updatedAt`. Names that are not properties are dropped,
+///   as are duplicates.
+/// - `transient` lists the transient properties in property order, dropping
+///   names that are not properties and duplicates.
+/// - Indices without a name or properties are dropped, as are index properties
+///   with an empty name. `unique` is only written when `true`.
+/// - Keywords are trimmed. The form edits them as a comma-separated list, so a
+///   keyword containing a comma becomes separate keywords.
+/// - Empty strings, arrays and objects are left out, as are keys the form does
+///   not support, which `JsonParser` reports as warnings. Numbers are kept exactly
+///   as written, except counts too large for the form, which are dropped.
+pub struct CanonicalForm;
+
+impl CanonicalForm {
+    /// Returns the canonical form of a contract accepted by `JsonParser`
+    pub fn of(contract: &Value) -> Value {
+        let mut canonical = Map::new();
+
+        for (name, doc) in contract.as_object().into_iter().flatten() {
+            if let Some(doc) = doc.as_object().filter(|_| !name.is_empty()) {
+                canonical.insert(name.clone(), Self::document_type(doc));
+            }
+        }
+
+        Value::Object(canonical)
+    }
+
+    fn document_type(doc: &Map<String, Value>) -> Value {
+        let mut canonical = Map::new();
+        let properties = Self::sorted_properties(doc.get("properties"));
+
+        canonical.insert("type".to_string(), Value::from("object"));
+        if !properties.is_empty() {
+            canonical.insert("properties".to_string(), Self::properties(&properties));
+        }
+
+        let indices: Vec<Value> = doc
+            .get("indices")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Self::index)
+            .collect();
+        if !indices.is_empty() {
+            canonical.insert("indices".to_string(), Value::Array(indices));
+        }
+
+        let listed = Self::listed(doc.get("required"));
+        let mut required = Self::in_property_order(&properties, &listed);
+        for system in ["$createdAt", "$updatedAt"] {
+            if listed.contains(&system) {
+                required.push(Value::from(system));
+            }
+        }
+        if !required.is_empty() {
+            canonical.insert("required".to_string(), Value::Array(required));
+        }
+
+        let transient = Self::in_property_order(&properties, &Self::listed(doc.get("transient")));
+        if !transient.is_empty() {
+            canonical.insert("transient".to_string(), Value::Array(transient));
+        }
+
+        let additional_properties = doc.get("additionalProperties").and_then(Value::as_bool);
+        canonical.insert(
+            "additionalProperties".to_string(),
+            Value::Bool(additional_properties.unwrap_or(false)),
+        );
+
+        Self::copy_text(doc, &mut canonical, "description");
+
+        let keywords: Vec<Value> = doc
+            .get("keywords")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .flat_map(|keyword| keyword.split(','))
+            .map(str::trim)
+            .filter(|keyword| !keyword.is_empty())
+            .map(Value::from)
+            .collect();
+        if !keywords.is_empty() {
+            canonical.insert("keywords".to_string(), Value::Array(keywords));
+        }
+
+        Self::copy_text(doc, &mut canonical, "$comment");
+        Value::Object(canonical)
+    }
+
+    /// Returns the named properties ordered by position
+    fn sorted_properties(properties: Option<&Value>) -> Vec<(&String, &Map<String, Value>)> {
+        let mut sorted: Vec<(&String, &Map<String, Value>)> = properties
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter(|(name, _)| !name.is_empty() && !name.starts_with('$'))
+            .filter_map(|(name, prop)| Some((name, prop.as_object()?)))
+            .collect();
+        // Stable, so properties sharing a position keep their written order
+        sorted.sort_by_key(|(_, prop)| Self::position(prop));
+        sorted
+    }
+
+    fn properties(properties: &[(&String, &Map<String, Value>)]) -> Value {
+        Value::Object(
+            properties
+                .iter()
+                .map(|(name, prop)| ((*name).clone(), Self::property(prop)))
+                .collect(),
+        )
+    }
+
+    fn property(prop: &Map<String, Value>) -> Value {
+        let mut canonical = Map::new();
+        canonical.insert("position".to_string(), Value::from(Self::position(prop)));
+        canonical.extend(Self::schema(prop));
+        Value::Object(canonical)
+    }
+
+    /// Returns the canonical keywords of a property or of array items, without a position
+    fn schema(prop: &Map<String, Value>) -> Map<String, Value> {
+        let mut canonical = Map::new();
+        let data_type = prop
+            .get("type")
+            .and_then(Value::as_str)
+            .filter(|data_type| {
+                ["string", "integer", "number", "array", "object", "boolean"].contains(data_type)
+            })
+            .unwrap_or("string");
+
+        canonical.insert("type".to_string(), Value::from(data_type));
+        Self::copy_text(prop, &mut canonical, "description");
+        Self::copy_text(prop, &mut canonical, "$comment");
+        if let Some(values) = prop
+            .get("enum")
+            .and_then(Value::as_array)
+            .filter(|values| !values.is_empty())
+        {
+            canonical.insert("enum".to_string(), Value::Array(values.clone()));
+        }
+
+        match data_type {
+            "string" => {
+                Self::copy_count(prop, &mut canonical, "minLength");
+                Self::copy_count(prop, &mut canonical, "maxLength");
+                Self::copy_text(prop, &mut canonical, "pattern");
+                Self::copy_text(prop, &mut canonical, "format");
+            }
+            "integer" | "number" => {
+                for key in ["minimum", "maximum"] {
+                    if let Some(number) = prop.get(key).filter(|v| v.is_number()) {
+                        canonical.insert(key.to_string(), number.clone());
+                    }
+                }
+            }
+            "array" => {
+                let items = prop
+                    .get("items")
+                    .and_then(Value::as_object)
+                    .filter(|_| prop.get("byteArray") != Some(&Value::Bool(true)));
+                match items {
+                    Some(items) => {
+                        canonical.insert("items".to_string(), Value::Object(Self::schema(items)));
+                    }
+                    None => {
+                        canonical.insert("byteArray".to_string(), Value::Bool(true));
+                    }
+                }
+                Self::copy_count(prop, &mut canonical, "minItems");
+                Self::copy_count(prop, &mut canonical, "maxItems");
+                if items.is_none() {
+                    Self::copy_text(prop, &mut canonical, "contentMediaType");
+                }
+            }
+            "object" => {
+                let nested = Self::sorted_properties(prop.get("properties"));
+                if !nested.is_empty() {
+                    canonical.insert("properties".to_string(), Self::properties(&nested));
+                    let required =
+                        Self::in_property_order(&nested, &Self::listed(prop.get("required")));
+                    if !required.is_empty() {
+                        canonical.insert("required".to_string(), Value::Array(required));
+                    }
+                }
+                Self::copy_count(prop, &mut canonical, "minProperties");
+                Self::copy_count(prop, &mut canonical, "maxProperties");
+                if let Some(additional) =
+                    prop.get("additionalProperties").filter(|v| v.is_boolean())
+                {
+                    canonical.insert("additionalProperties".to_string(), additional.clone());
+                }
+            }
+            _ => {}
+        }
+
+        canonical
+    }
+
+    fn index(index: &Value) -> Option<Value> {
+        let index = index.as_object()?;
+        let name = index
+            .get("name")?
+            .as_str()
+            .filter(|name| !name.is_empty())?;
+        let properties: Vec<Value> = index
+            .get("properties")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|prop| {
+                prop.as_object()
+                    .and_then(|prop| prop.keys().next())
+                    .is_some_and(|field| !field.is_empty())
+            })
+            .cloned()
+            .collect();
+        if properties.is_empty() {
+            return None;
+        }
+
+        let mut canonical = Map::new();
+        canonical.insert("name".to_string(), Value::from(name));
+        canonical.insert("properties".to_string(), Value::Array(properties));
+        if index.get("unique").and_then(Value::as_bool) == Some(true) {
+            canonical.insert("unique".to_string(), Value::Bool(true));
+        }
+        Some(Value::Object(canonical))
+    }
+
+    /// Returns the listed properties in property order
+    fn in_property_order(
+        properties: &[(&String, &Map<String, Value>)],
+        listed: &[&str],
+    ) -> Vec<Value> {
+        properties
+            .iter()
+            .filter(|(name, _)| listed.contains(&name.as_str()))
+            .map(|(name, _)| Value::from(name.as_str()))
+            .collect()
+    }
+
+    fn listed(required: Option<&Value>) -> Vec<&str> {
+        required
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect()
+    }
+
+    fn position(prop: &Map<String, Value>) -> u64 {
+        prop.get("position").and_then(Value::as_u64).unwrap_or(0)
+    }
+
+    /// Copies a non-empty string
+    fn copy_text(from: &Map<String, Value>, to: &mut Map<String, Value>, key: &str) {
+        if let Some(text) = from
+            .get(key)
+            .and_then(Value::as_str)
+            .filter(|text| !text.is_empty())
+        {
+            to.insert(key.to_string(), Value::from(text));
+        }
+    }
+
+    /// Copies a non-negative integer that fits the form's counts
+    fn copy_count(from: &Map<String, Value>, to: &mut Map<String, Value>, key: &str) {
+        if let Some(count) = from
+            .get(key)
+            .and_then(Value::as_u64)
+            .filter(|count| u32::try_from(*count).is_ok())
+        {
+            to.insert(key.to_string(), Value::from(count));
+        }
+    }
+}
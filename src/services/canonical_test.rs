@@ -0,0 +1,284 @@
+#[cfg(test)]
+mod tests {
+    use super::super::canonical::CanonicalForm;
+    use super::super::gallery::GalleryService;
+    use super::super::json_generator::JsonGenerator;
+    use super::super::json_parser::JsonParser;
+    use crate::types::{DataType, DocumentType, Index, IndexProperties, Property};
+    use proptest::prelude::*;
+    use serde_json::{json, Number, Value};
+
+    /// Parses and regenerates a contract, as importing it into the form does
+    fn round_trip(json: &str) -> Value {
+        let document_types = JsonParser::parse_contract(json).unwrap();
+        JsonGenerator::generate_contract(&document_types)
+    }
+
+    fn assert_round_trip(json: &str) {
+        let contract: Value = serde_json::from_str(json).unwrap();
+        let canonical = CanonicalForm::of(&contract);
+        assert_eq!(round_trip(json), canonical);
+        // The canonical form is a fixed point
+        assert_eq!(CanonicalForm::of(&canonical), canonical);
+    }
+
+    #[test]
+    fn test_starter_contracts_round_trip() {
+        for contract in GalleryService::starter_contracts() {
+            assert_round_trip(contract.json);
+
+            // The bundled contracts are already canonical
+            let json: Value = serde_json::from_str(contract.json).unwrap();
+            assert_eq!(round_trip(contract.json), json, "{}", contract.id);
+        }
+    }
+
+    #[test]
+    fn test_nested_required_written_once() {
+        let json = r#"{"note": {"type": "object", "properties": {"author": {
+            "type": "object", "position": 0,
+            "properties": {"name": {"type": "string", "position": 0}},
+            "required": ["name", "name", "missing"]
+        }}, "additionalProperties": false}}"#;
+
+        assert_round_trip(json);
+        assert_eq!(
+            round_trip(json)["note"]["properties"]["author"]["required"],
+            json!(["name"])
+        );
+    }
+
+    #[test]
+    fn test_numbers_kept_exactly() {
+        let json = r#"{"note": {"type": "object", "properties": {
+            "big": {"type": "integer", "position": 0,
+                "minimum": -9223372036854775808, "maximum": 18446744073709551615},
+            "ratio": {"type": "number", "position": 1, "minimum": 0.5, "maximum": 1e300}
+        }, "additionalProperties": false}}"#;
+
+        assert_round_trip(json);
+        let note = JsonParser::parse_contract(json).unwrap().remove(0);
+        assert_eq!(note.properties[0].maximum, Some(Number::from(u64::MAX)));
+        assert_eq!(note.properties[1].minimum, Number::from_f64(0.5));
+    }
+
+    #[test]
+    fn test_typed_array_items() {
+        let json = r#"{"note": {"type": "object", "properties": {
+            "tags": {"position": 0, "type": "array", "items": {"type": "string", "maxLength": 20}, "maxItems": 5},
+            "hash": {"position": 1, "type": "array", "byteArray": true, "items": {"type": "string"}},
+            "links": {"position": 2, "type": "array", "items": {"type": "object",
+                "properties": {"url": {"position": 0, "type": "string", "format": "uri"}},
+                "required": ["url"], "additionalProperties": false}}
+        }, "additionalProperties": false}}"#;
+        assert_round_trip(json);
+
+        let document_types = JsonParser::parse_contract(json).unwrap();
+        let properties = &document_types[0].properties;
+        assert_eq!(properties[0].items.as_ref().unwrap().max_length, Some(20));
+        // byteArray takes precedence over items
+        assert!(properties[1].is_byte_array());
+        let links = properties[2].items.as_ref().unwrap();
+        assert!(links.properties.as_ref().unwrap()[0].required);
+    }
+
+    #[test]
+    fn test_transient_and_comment() {
+        let json = r#"{"note": {"type": "object", "properties": {
+            "draft": {"type": "string", "position": 0, "$comment": "Client cache only", "maxLength": 63},
+            "title": {"type": "string", "position": 1, "maxLength": 63}
+        }, "required": ["title"], "transient": ["draft", "missing"], "additionalProperties": false}}"#;
+        assert_round_trip(json);
+
+        let note = JsonParser::parse_contract(json).unwrap().remove(0);
+        assert!(note.properties[0].transient);
+        assert!(!note.properties[1].transient);
+        assert_eq!(
+            note.properties[0].comment.as_deref(),
+            Some("Client cache only")
+        );
+        assert_eq!(round_trip(json)["note"]["transient"], json!(["draft"]));
+    }
+
+    fn name() -> impl Strategy<Value = String> {
+        "[a-z][a-zA-Z0-9]{0,6}"
+    }
+
+    fn text() -> impl Strategy<Value = String> {
+        // Includes empty strings and commas, which the canonical form treats specially
+        prop_oneof![Just(String::new()), "[ a-z,]{1,8}"]
+    }
+
+    fn number() -> impl Strategy<Value = Number> {
+        prop_oneof![
+            any::<i64>().prop_map(Number::from),
+            any::<u64>().prop_map(Number::from),
+            any::<f64>().prop_filter_map("finite", Number::from_f64),
+        ]
+    }
+
+    fn data_type() -> impl Strategy<Value = DataType> {
+        proptest::sample::select(DataType::all())
+    }
+
+    fn enum_values() -> impl Strategy<Value = Option<Vec<Value>>> {
+        proptest::option::of(proptest::collection::vec(
+            prop_oneof![
+                name().prop_map(Value::from),
+                any::<i32>().prop_map(Value::from)
+            ],
+            0..3,
+        ))
+    }
+
+    /// A property with random parameters, including ones its type does not use
+    fn property(depth: u32) -> BoxedStrategy<Property> {
+        let (nested, items) = if depth == 0 {
+            (Just(None).boxed(), Just(None).boxed())
+        } else {
+            (
+                proptest::option::of(
+                    proptest::collection::vec(property(depth - 1), 0..3).prop_map(Box::new),
+                )
+                .boxed(),
+                proptest::option::of(property(depth - 1).prop_map(Box::new)).boxed(),
+            )
+        };
+        let basics = (
+            name(),
+            data_type(),
+            any::<bool>(),
+            0..4u64,
+            proptest::option::of(text()),
+            enum_values(),
+        );
+        let limits = (
+            proptest::option::of(any::<u32>()),
+            proptest::option::of(any::<u32>()),
+            proptest::option::of(number()),
+            proptest::option::of(number()),
+            proptest::option::of(any::<u32>()),
+            proptest::option::of(any::<u32>()),
+        );
+        let rest = (
+            proptest::option::of(text()),
+            proptest::option::of(text()),
+            proptest::option::of(text()),
+            nested,
+            proptest::option::of(any::<bool>()),
+            items,
+            proptest::option::of(text()),
+            any::<bool>(),
+        );
+
+        (basics, limits, rest)
+            .prop_map(|(basics, limits, rest)| {
+                let (name, data_type, required, position, description, enum_values) = basics;
+                let (min_length, max_length, minimum, maximum, min_items, max_items) = limits;
+                let (
+                    pattern,
+                    format,
+                    content_media_type,
+                    properties,
+                    additional_properties,
+                    items,
+                    comment,
+                    transient,
+                ) = rest;
+                Property {
+                    name,
+                    data_type,
+                    required,
+                    transient,
+                    position,
+                    description,
+                    comment,
+                    enum_values,
+                    min_length,
+                    max_length,
+                    pattern,
+                    format,
+                    minimum,
+                    maximum,
+                    min_items,
+                    max_items,
+                    content_media_type,
+                    properties,
+                    min_properties: min_items,
+                    max_properties: max_items,
+                    additional_properties,
+                    items,
+                    ..Default::default()
+                }
+            })
+            .boxed()
+    }
+
+    fn index() -> impl Strategy<Value = Index> {
+        let field = prop_oneof![Just(String::new()), name()];
+        let order = prop_oneof![Just("asc".to_string()), Just("desc".to_string())];
+        (
+            prop_oneof![Just(String::new()), name()],
+            proptest::collection::vec((field, order), 0..3),
+            any::<bool>(),
+        )
+            .prop_map(|(name, properties, unique)| Index {
+                name,
+                properties: properties
+                    .into_iter()
+                    .map(|(field, order)| IndexProperties::new(field, order))
+                    .collect(),
+                unique,
+            })
+    }
+
+    fn document_type() -> impl Strategy<Value = DocumentType> {
+        (
+            (name(), proptest::collection::vec(property(2), 0..5)),
+            proptest::collection::vec(index(), 0..3),
+            (any::<bool>(), any::<bool>(), any::<bool>()),
+            (text(), text(), text()),
+        )
+            .prop_map(
+                |((name, properties), indices, flags, (comment, description, keywords))| {
+                    let mut doc = DocumentType::new(name);
+                    doc.properties = properties;
+                    doc.indices = indices;
+                    (doc.created_at_required, doc.updated_at_required) = (flags.0, flags.1);
+                    doc.additionalProperties = flags.2;
+                    (doc.comment, doc.description, doc.keywords) = (comment, description, keywords);
+                    doc
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn test_generated_contracts_round_trip(
+            document_types in proptest::collection::vec(document_type(), 1..4),
+            extra_name in proptest::option::of(name()),
+        ) {
+            let mut contract = JsonGenerator::generate_contract(&document_types);
+            // Contracts written by hand may list unknown or repeated property names
+            if let Some(extra) = extra_name {
+                for doc in contract.as_object_mut().unwrap().values_mut() {
+                    let doc = doc.as_object_mut().unwrap();
+                    for key in ["required", "transient"] {
+                        let names = doc.entry(key).or_insert_with(|| json!([]));
+                        let names = names.as_array_mut().unwrap();
+                        let repeated = names.clone();
+                        names.extend(repeated);
+                        names.push(Value::from(extra.clone()));
+                    }
+                    doc.insert("documentsMutable".to_string(), Value::Bool(true));
+                }
+            }
+
+            let json = serde_json::to_string(&contract).unwrap();
+            let reparsed: Value = serde_json::from_str(&json).unwrap();
+            let canonical = CanonicalForm::of(&reparsed);
+            prop_assert_eq!(round_trip(&json), canonical.clone());
+            prop_assert_eq!(CanonicalForm::of(&canonical), canonical);
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use crate::services::{ChangeKind, ChangeTarget, ContractDiffService, JsonGenerator, JsonParser};
+use crate::types::{DocumentType, Property};
+
+/// One line of a side-by-side comparison, pairing the same element of both contracts
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonRow {
+    pub document_type: String,
+    /// Nested properties are named by their path, such as `author.name`
+    pub target: ChangeTarget,
+    /// Summary of the element in the left contract, `None` if it is missing there
+    pub left: Option<String>,
+    /// Summary of the element in the right contract, `None` if it is missing there
+    pub right: Option<String>,
+    /// Field-level changes, such as `maxLength: 63 → 100`
+    pub details: Vec<String>,
+}
+
+impl ComparisonRow {
+    /// Returns how the element changed, or `None` if both contracts have it unchanged
+    pub fn kind(&self) -> Option<ChangeKind> {
+        match (&self.left, &self.right) {
+            (None, _) => Some(ChangeKind::Added),
+            (_, None) => Some(ChangeKind::Removed),
+            _ if !self.details.is_empty() => Some(ChangeKind::Changed),
+            _ => None,
+        }
+    }
+
+    /// Returns a short description such as `property title`
+    pub fn label(&self) -> String {
+        match &self.target {
+            ChangeTarget::DocumentType => format!("document type {}", self.document_type),
+            ChangeTarget::Property(name) => format!("property {}", name),
+            ChangeTarget::Index(name) => format!("index {}", name),
+        }
+    }
+}
+
+/// Service for comparing two contracts side by side
+///
+/// Document types, properties and indices are aligned by name, so only the meaning
+/// of the contracts is compared, not their formatting or key order.
+pub struct ContractComparison;
+
+impl ContractComparison {
+    /// Parses two contract JSONs and compares them
+    pub fn compare_json(left: &str, right: &str) -> Result<Vec<ComparisonRow>, String> {
+        let left = JsonParser::parse_contract(left).map_err(|e| format!("Left contract: {}", e))?;
+        let right =
+            JsonParser::parse_contract(right).map_err(|e| format!("Right contract: {}", e))?;
+        Ok(Self::compare(&left, &right))
+    }
+
+    /// Lists every document type, property and index of both contracts, aligned by name
+    ///
+    /// Each document type row is followed by its properties and then its indices.
+    /// Elements only in the right contract come after those of the left one.
+    pub fn compare(left: &[DocumentType], right: &[DocumentType]) -> Vec<ComparisonRow> {
+        let mut rows = Vec::new();
+
+        for (left_doc, right_doc) in Self::align(left, right, |doc| &doc.name) {
+            let name = left_doc
+                .or(right_doc)
+                .map(|doc| doc.name.clone())
+                .unwrap_or_default();
+            let details = match (left_doc, right_doc) {
+                (Some(left_doc), Some(right_doc)) => {
+                    ContractDiffService::settings_details(left_doc, right_doc)
+                }
+                _ => Vec::new(),
+            };
+            rows.push(ComparisonRow {
+                document_type: name.clone(),
+                target: ChangeTarget::DocumentType,
+                left: left_doc.map(Self::describe_document_type),
+                right: right_doc.map(Self::describe_document_type),
+                details,
+            });
+
+            Self::compare_properties(
+                &mut rows,
+                &name,
+                "",
+                left_doc
+                    .map(|doc| doc.properties.as_slice())
+                    .unwrap_or_default(),
+                right_doc
+                    .map(|doc| doc.properties.as_slice())
+                    .unwrap_or_default(),
+            );
+
+            let left_indices = left_doc
+                .map(|doc| doc.indices.as_slice())
+                .unwrap_or_default();
+            let right_indices = right_doc
+                .map(|doc| doc.indices.as_slice())
+                .unwrap_or_default();
+            for (left_index, right_index) in
+                Self::align(left_indices, right_indices, |index| &index.name)
+            {
+                let left = left_index.map(ContractDiffService::index_summary);
+                let right = right_index.map(ContractDiffService::index_summary);
+                let details = match (&left, &right) {
+                    (Some(left), Some(right)) if left != right => {
+                        vec![format!("{} → {}", left, right)]
+                    }
+                    _ => Vec::new(),
+                };
+                rows.push(ComparisonRow {
+                    document_type: name.clone(),
+                    target: ChangeTarget::Index(
+                        left_index
+                            .or(right_index)
+                            .map(|index| index.name.clone())
+                            .unwrap_or_default(),
+                    ),
+                    left,
+                    right,
+                    details,
+                });
+            }
+        }
+
+        rows
+    }
+
+    /// Writes the changed rows as a plain text report for code review
+    pub fn report(rows: &[ComparisonRow]) -> String {
+        let changed = rows.iter().filter(|row| row.kind().is_some()).count();
+        let mut report = format!(
+            "Contract comparison: {} changed, {} unchanged\n",
+            changed,
+            rows.len() - changed
+        );
+
+        let mut document_type = None;
+        for row in rows {
+            let Some(kind) = row.kind() else {
+                continue;
+            };
+            if document_type != Some(&row.document_type) {
+                document_type = Some(&row.document_type);
+                report.push_str(&format!("\n{}\n", row.document_type));
+            }
+
+            let marker = match kind {
+                ChangeKind::Added => '+',
+                ChangeKind::Removed => '-',
+                ChangeKind::Changed => '~',
+            };
+            let summary = match kind {
+                ChangeKind::Added => row.right.as_deref(),
+                ChangeKind::Removed => row.left.as_deref(),
+                ChangeKind::Changed => None,
+            };
+            match summary {
+                Some(summary) => {
+                    report.push_str(&format!("  {} {}: {}\n", marker, row.label(), summary))
+                }
+                None => report.push_str(&format!("  {} {}\n", marker, row.label())),
+            }
+            for detail in &row.details {
+                report.push_str(&format!("      {}\n", detail));
+            }
+        }
+
+        if changed == 0 {
+            report.push_str("\nThe contracts are the same.\n");
+        }
+        report
+    }
+
+    /// Adds rows for properties and, below each object property, its inner properties
+    ///
+    /// Properties of the objects an array holds are listed under the array as `name[].inner`.
+    fn compare_properties(
+        rows: &mut Vec<ComparisonRow>,
+        document_type: &str,
+        parent: &str,
+        left: &[Property],
+        right: &[Property],
+    ) {
+        for (left_prop, right_prop) in Self::align(left, right, |prop| &prop.name) {
+            let name = left_prop
+                .or(right_prop)
+                .map(|prop| prop.name.as_str())
+                .unwrap_or_default();
+            let path = if parent.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", parent, name)
+            };
+            let details = match (left_prop, right_prop) {
+                (Some(left_prop), Some(right_prop)) => {
+                    ContractDiffService::property_details(left_prop, right_prop)
+                }
+                _ => Vec::new(),
+            };
+            rows.push(ComparisonRow {
+                document_type: document_type.to_string(),
+                target: ChangeTarget::Property(path.clone()),
+                left: left_prop.map(Self::describe_property),
+                right: right_prop.map(Self::describe_property),
+                details,
+            });
+
+            Self::compare_properties(
+                rows,
+                document_type,
+                &path,
+                Self::inner_properties(left_prop),
+                Self::inner_properties(right_prop),
+            );
+            let (left_items, right_items) = (Self::items(left_prop), Self::items(right_prop));
+            if left_items.is_some() || right_items.is_some() {
+                Self::compare_properties(
+                    rows,
+                    document_type,
+                    &format!("{}[]", path),
+                    Self::inner_properties(left_items),
+                    Self::inner_properties(right_items),
+                );
+            }
+        }
+    }
+
+    fn inner_properties(prop: Option<&Property>) -> &[Property] {
+        prop.and_then(|prop| prop.properties.as_deref())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    fn items(prop: Option<&Property>) -> Option<&Property> {
+        prop.and_then(|prop| prop.items.as_deref())
+    }
+
+    fn describe_document_type(doc_type: &DocumentType) -> String {
+        let properties = doc_type
+            .properties
+            .iter()
+            .filter(|p| !p.name.is_empty())
+            .count();
+        let indices = doc_type
+            .indices
+            .iter()
+            .filter(|i| !i.name.is_empty())
+            .count();
+        format!("{} properties, {} indices", properties, indices)
+    }
+
+    /// Describes a property's type and constraints, such as `string, required, maxLength: 63`
+    fn describe_property(prop: &Property) -> String {
+        let mut parts = vec![prop.data_type.as_str().to_string()];
+        if prop.required {
+            parts.push("required".to_string());
+        }
+        if prop.transient {
+            parts.push("transient".to_string());
+        }
+
+        let schema = JsonGenerator::generate_property(prop);
+        for (key, value) in schema.as_object().into_iter().flatten() {
+            match key.as_str() {
+                "type" | "description" | "required" | "properties" => {}
+                _ => parts.push(format!("{}: {}", key, value)),
+            }
+        }
+        parts.join(", ")
+    }
+
+    /// Pairs up named elements of both lists, left ones first
+    fn align<'a, T>(
+        left: &'a [T],
+        right: &'a [T],
+        name: impl Fn(&T) -> &String,
+    ) -> Vec<(Option<&'a T>, Option<&'a T>)> {
+        let mut pairs: Vec<(Option<&T>, Option<&T>)> = left
+            .iter()
+            .filter(|item| !name(item).is_empty())
+            .map(|item| {
+                (
+                    Some(item),
+                    right.iter().find(|other| name(other) == name(item)),
+                )
+            })
+            .collect();
+        pairs.extend(
+            right
+                .iter()
+                .filter(|item| {
+                    !name(item).is_empty() && !left.iter().any(|other| name(other) == name(item))
+                })
+                .map(|item| (None, Some(item))),
+        );
+        pairs
+    }
+}
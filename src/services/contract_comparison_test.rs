@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use super::super::contract_comparison::ContractComparison;
+    use super::super::contract_diff::{ChangeKind, ChangeTarget};
+
+    const OURS: &str = r#"{
+        "post": {"type": "object", "properties": {
+            "title": {"type": "string", "position": 0, "maxLength": 63},
+            "author": {"type": "object", "position": 1, "properties": {
+                "name": {"type": "string", "position": 0}
+            }},
+            "body": {"type": "string", "position": 2}
+        }, "required": ["title"],
+        "indices": [{"name": "byTitle", "properties": [{"title": "asc"}]}],
+        "additionalProperties": false}
+    }"#;
+
+    // The same contract as OURS written differently, with three changes
+    const THEIRS: &str = r#"{"post": {"additionalProperties": false, "required": ["title"],
+        "indices": [{"name": "byTitle", "properties": [{"title": "asc"}], "unique": true}],
+        "properties": {
+            "author": {"properties": {"name": {"position": 0, "type": "string", "maxLength": 40}},
+                "position": 1, "type": "object"},
+            "title": {"maxLength": 63, "position": 0, "type": "string"},
+            "tags": {"type": "string", "position": 3}
+        }, "type": "object"}}"#;
+
+    #[test]
+    fn test_rows_align_by_name() {
+        let rows = ContractComparison::compare_json(OURS, THEIRS).unwrap();
+        let summary: Vec<(String, Option<ChangeKind>)> =
+            rows.iter().map(|row| (row.label(), row.kind())).collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("document type post".to_string(), None),
+                ("property title".to_string(), None),
+                ("property author".to_string(), Some(ChangeKind::Changed)),
+                (
+                    "property author.name".to_string(),
+                    Some(ChangeKind::Changed)
+                ),
+                ("property body".to_string(), Some(ChangeKind::Removed)),
+                ("property tags".to_string(), Some(ChangeKind::Added)),
+                ("index byTitle".to_string(), Some(ChangeKind::Changed)),
+            ]
+        );
+        assert_eq!(
+            rows[3].target,
+            ChangeTarget::Property("author.name".to_string())
+        );
+        assert_eq!(rows[3].details, vec!["maxLength: none → 40"]);
+        assert_eq!(
+            rows[1].left.as_deref(),
+            Some("string, required, position: 0, maxLength: 63")
+        );
+
+        assert!(ContractComparison::compare_json(OURS, OURS)
+            .unwrap()
+            .iter()
+            .all(|row| row.kind().is_none()));
+    }
+
+    #[test]
+    fn test_rows_descend_into_array_items() {
+        let links = |url_length: u32| {
+            format!(
+                r#"{{"post": {{"type": "object", "additionalProperties": false, "properties": {{
+                    "links": {{"type": "array", "position": 0, "items": {{"type": "object",
+                        "properties": {{"url": {{"type": "string", "position": 0,
+                            "maxLength": {}}}}}, "additionalProperties": false}}}}
+                }}}}}}"#,
+                url_length
+            )
+        };
+        let rows = ContractComparison::compare_json(&links(100), &links(200)).unwrap();
+
+        assert_eq!(
+            rows.last().map(|row| &row.target),
+            Some(&ChangeTarget::Property("links[].url".to_string()))
+        );
+        assert_eq!(rows.last().unwrap().details, vec!["maxLength: 100 → 200"]);
+    }
+
+    #[test]
+    fn test_report() {
+        let rows = ContractComparison::compare_json(OURS, THEIRS).unwrap();
+
+        assert_eq!(
+            ContractComparison::report(&rows),
+            "Contract comparison: 5 changed, 2 unchanged
+
+post
+  ~ property author
+      nested properties changed
+  ~ property author.name
+      maxLength: none → 40
+  - property body: string, position: 2
+  + property tags: string, position: 3
+  ~ index byTitle
+      [title] → [title] unique
+"
+        );
+        assert!(ContractComparison::compare_json(OURS, "{")
+            .unwrap_err()
+            .starts_with("Right contract:"));
+    }
+}
@@ -0,0 +1,478 @@
+use crate::services::JsonGenerator;
+use crate::types::{DocumentType, Index, Property};
+use serde_json::Value;
+
+/// Whether something was added, removed or changed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl ChangeKind {
+    /// Returns the lowercase label of the kind
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Changed => "changed",
+        }
+    }
+}
+
+/// Part of a document type a change applies to
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeTarget {
+    /// The whole document type when added or removed, otherwise its own settings
+    DocumentType,
+    Property(String),
+    Index(String),
+}
+
+/// One hunk of a contract diff that can be accepted or rejected on its own
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractChange {
+    pub kind: ChangeKind,
+    pub document_type: String,
+    pub target: ChangeTarget,
+    /// Field-level changes, such as `maxLength: 63 → 100`
+    pub details: Vec<String>,
+}
+
+impl ContractChange {
+    /// Returns a short description such as `property post.title`
+    pub fn label(&self) -> String {
+        match &self.target {
+            ChangeTarget::DocumentType if self.kind == ChangeKind::Changed => {
+                format!("settings of {}", self.document_type)
+            }
+            ChangeTarget::DocumentType => format!("document type {}", self.document_type),
+            ChangeTarget::Property(name) => format!("property {}.{}", self.document_type, name),
+            ChangeTarget::Index(name) => format!("index {}.{}", self.document_type, name),
+        }
+    }
+
+    /// Returns a one-line summary such as `Added property post.title`
+    pub fn summary(&self) -> String {
+        let kind = self.kind.as_str();
+        format!(
+            "{}{} {}",
+            kind[..1].to_uppercase(),
+            &kind[1..],
+            self.label()
+        )
+    }
+}
+
+/// Service for comparing contracts and merging selected changes
+pub struct ContractDiffService;
+
+impl ContractDiffService {
+    /// Lists the changes that turn `current` into `proposed`
+    ///
+    /// Document types, properties and indices are matched by name, so a rename
+    /// shows up as a removal and an addition.
+    pub fn diff(current: &[DocumentType], proposed: &[DocumentType]) -> Vec<ContractChange> {
+        let mut changes = Vec::new();
+
+        for doc_type in Self::named(current) {
+            if Self::find_doc(proposed, &doc_type.name).is_none() {
+                changes.push(Self::change(
+                    ChangeKind::Removed,
+                    &doc_type.name,
+                    ChangeTarget::DocumentType,
+                    Vec::new(),
+                ));
+            }
+        }
+
+        for new_doc in Self::named(proposed) {
+            let Some(old_doc) = Self::find_doc(current, &new_doc.name) else {
+                changes.push(Self::change(
+                    ChangeKind::Added,
+                    &new_doc.name,
+                    ChangeTarget::DocumentType,
+                    Self::document_type_summary(new_doc),
+                ));
+                continue;
+            };
+
+            let settings = Self::settings_details(old_doc, new_doc);
+            if !settings.is_empty() {
+                changes.push(Self::change(
+                    ChangeKind::Changed,
+                    &new_doc.name,
+                    ChangeTarget::DocumentType,
+                    settings,
+                ));
+            }
+
+            Self::diff_properties(old_doc, new_doc, &mut changes);
+            Self::diff_indices(old_doc, new_doc, &mut changes);
+        }
+
+        changes
+    }
+
+    /// Applies the changes marked as accepted to `current`
+    ///
+    /// `accepted` is parallel to `changes`; missing entries count as rejected.
+    pub fn apply(
+        current: &[DocumentType],
+        proposed: &[DocumentType],
+        changes: &[ContractChange],
+        accepted: &[bool],
+    ) -> Vec<DocumentType> {
+        let mut result = current.to_vec();
+
+        for (change, _) in changes
+            .iter()
+            .zip(accepted.iter())
+            .filter(|(_, accepted)| **accepted)
+        {
+            let new_doc = Self::find_doc(proposed, &change.document_type);
+
+            match (&change.target, change.kind) {
+                (ChangeTarget::DocumentType, ChangeKind::Added) => {
+                    if let Some(new_doc) = new_doc {
+                        result.push(new_doc.clone());
+                    }
+                }
+                (ChangeTarget::DocumentType, ChangeKind::Removed) => {
+                    result.retain(|d| d.name != change.document_type);
+                }
+                (ChangeTarget::DocumentType, ChangeKind::Changed) => {
+                    if let (Some(doc), Some(new_doc)) = (
+                        Self::find_doc_mut(&mut result, &change.document_type),
+                        new_doc,
+                    ) {
+                        doc.description = new_doc.description.clone();
+                        doc.comment = new_doc.comment.clone();
+                        doc.keywords = new_doc.keywords.clone();
+                        doc.created_at_required = new_doc.created_at_required;
+                        doc.updated_at_required = new_doc.updated_at_required;
+                        doc.additionalProperties = new_doc.additionalProperties;
+                    }
+                }
+                (ChangeTarget::Property(name), kind) => {
+                    let new_prop =
+                        new_doc.and_then(|d| d.properties.iter().find(|p| &p.name == name));
+                    if let Some(doc) = Self::find_doc_mut(&mut result, &change.document_type) {
+                        Self::apply_property(doc, name, kind, new_prop);
+                    }
+                }
+                (ChangeTarget::Index(name), kind) => {
+                    let new_index =
+                        new_doc.and_then(|d| d.indices.iter().find(|i| &i.name == name));
+                    if let Some(doc) = Self::find_doc_mut(&mut result, &change.document_type) {
+                        match (kind, new_index) {
+                            (ChangeKind::Added, Some(new_index)) => {
+                                doc.indices.push(new_index.clone())
+                            }
+                            (ChangeKind::Changed, Some(new_index)) => {
+                                if let Some(index) =
+                                    doc.indices.iter_mut().find(|i| &i.name == name)
+                                {
+                                    *index = new_index.clone();
+                                }
+                            }
+                            _ => doc.indices.retain(|i| &i.name != name),
+                        }
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Adds, removes or replaces a property, keeping positions unique
+    fn apply_property(
+        doc: &mut DocumentType,
+        name: &str,
+        kind: ChangeKind,
+        new_prop: Option<&Property>,
+    ) {
+        let old_position = doc
+            .properties
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.position);
+        doc.properties.retain(|p| p.name != name);
+
+        let Some(new_prop) = new_prop.filter(|_| kind != ChangeKind::Removed) else {
+            return;
+        };
+
+        let mut prop = new_prop.clone();
+        if doc.properties.iter().any(|p| p.position == prop.position) {
+            // Positions must stay unique, so fall back to the old or next free position
+            prop.position = old_position.unwrap_or_else(|| {
+                doc.properties
+                    .iter()
+                    .map(|p| p.position + 1)
+                    .max()
+                    .unwrap_or(0)
+            });
+        }
+        doc.properties.push(prop);
+        doc.properties.sort_by_key(|p| p.position);
+    }
+
+    fn diff_properties(
+        old_doc: &DocumentType,
+        new_doc: &DocumentType,
+        changes: &mut Vec<ContractChange>,
+    ) {
+        for old_prop in old_doc.properties.iter().filter(|p| !p.name.is_empty()) {
+            if !new_doc.properties.iter().any(|p| p.name == old_prop.name) {
+                changes.push(Self::change(
+                    ChangeKind::Removed,
+                    &new_doc.name,
+                    ChangeTarget::Property(old_prop.name.clone()),
+                    Vec::new(),
+                ));
+            }
+        }
+
+        for new_prop in new_doc.properties.iter().filter(|p| !p.name.is_empty()) {
+            match old_doc.properties.iter().find(|p| p.name == new_prop.name) {
+                None => changes.push(Self::change(
+                    ChangeKind::Added,
+                    &new_doc.name,
+                    ChangeTarget::Property(new_prop.name.clone()),
+                    vec![Self::property_summary(new_prop)],
+                )),
+                Some(old_prop) => {
+                    let details = Self::property_details(old_prop, new_prop);
+                    if !details.is_empty() {
+                        changes.push(Self::change(
+                            ChangeKind::Changed,
+                            &new_doc.name,
+                            ChangeTarget::Property(new_prop.name.clone()),
+                            details,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    fn diff_indices(
+        old_doc: &DocumentType,
+        new_doc: &DocumentType,
+        changes: &mut Vec<ContractChange>,
+    ) {
+        for old_index in old_doc.indices.iter().filter(|i| !i.name.is_empty()) {
+            if !new_doc.indices.iter().any(|i| i.name == old_index.name) {
+                changes.push(Self::change(
+                    ChangeKind::Removed,
+                    &new_doc.name,
+                    ChangeTarget::Index(old_index.name.clone()),
+                    Vec::new(),
+                ));
+            }
+        }
+
+        for new_index in new_doc.indices.iter().filter(|i| !i.name.is_empty()) {
+            match old_doc.indices.iter().find(|i| i.name == new_index.name) {
+                None => changes.push(Self::change(
+                    ChangeKind::Added,
+                    &new_doc.name,
+                    ChangeTarget::Index(new_index.name.clone()),
+                    vec![Self::index_summary(new_index)],
+                )),
+                Some(old_index) => {
+                    let (old, new) = (
+                        Self::index_summary(old_index),
+                        Self::index_summary(new_index),
+                    );
+                    if old != new {
+                        changes.push(Self::change(
+                            ChangeKind::Changed,
+                            &new_doc.name,
+                            ChangeTarget::Index(new_index.name.clone()),
+                            vec![format!("{} → {}", old, new)],
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Compares the document type fields that are not properties or indices
+    pub fn settings_details(old_doc: &DocumentType, new_doc: &DocumentType) -> Vec<String> {
+        let mut details = Vec::new();
+        Self::compare_text(
+            &mut details,
+            "description",
+            &old_doc.description,
+            &new_doc.description,
+        );
+        Self::compare_text(&mut details, "$comment", &old_doc.comment, &new_doc.comment);
+        Self::compare_text(
+            &mut details,
+            "keywords",
+            &old_doc.keywords,
+            &new_doc.keywords,
+        );
+        Self::compare_flag(
+            &mut details,
+            "$createdAt required",
+            old_doc.created_at_required,
+            new_doc.created_at_required,
+        );
+        Self::compare_flag(
+            &mut details,
+            "$updatedAt required",
+            old_doc.updated_at_required,
+            new_doc.updated_at_required,
+        );
+        Self::compare_flag(
+            &mut details,
+            "additionalProperties",
+            old_doc.additionalProperties,
+            new_doc.additionalProperties,
+        );
+        details
+    }
+
+    /// Compares two properties by their generated schema
+    pub fn property_details(old_prop: &Property, new_prop: &Property) -> Vec<String> {
+        let mut details = Vec::new();
+        Self::compare_flag(
+            &mut details,
+            "required",
+            old_prop.required,
+            new_prop.required,
+        );
+        Self::compare_flag(
+            &mut details,
+            "transient",
+            old_prop.transient,
+            new_prop.transient,
+        );
+
+        let old_json = JsonGenerator::generate_property(old_prop);
+        let new_json = JsonGenerator::generate_property(new_prop);
+        let (Some(old_obj), Some(new_obj)) = (old_json.as_object(), new_json.as_object()) else {
+            return details;
+        };
+
+        let mut keys: Vec<&String> = old_obj.keys().collect();
+        keys.extend(new_obj.keys().filter(|k| !old_obj.contains_key(*k)));
+
+        for key in keys {
+            let (old_value, new_value) = (old_obj.get(key), new_obj.get(key));
+            if old_value == new_value {
+                continue;
+            }
+            if key == "properties" || key == "required" {
+                details.push(format!("nested {} changed", key));
+            } else {
+                details.push(format!(
+                    "{}: {} → {}",
+                    key,
+                    Self::display_value(old_value),
+                    Self::display_value(new_value)
+                ));
+            }
+        }
+
+        details
+    }
+
+    fn document_type_summary(doc_type: &DocumentType) -> Vec<String> {
+        let properties: Vec<&str> = doc_type
+            .properties
+            .iter()
+            .filter(|p| !p.name.is_empty())
+            .map(|p| p.name.as_str())
+            .collect();
+        let indices: Vec<&str> = doc_type
+            .indices
+            .iter()
+            .filter(|i| !i.name.is_empty())
+            .map(|i| i.name.as_str())
+            .collect();
+
+        let mut summary = vec![format!("properties: {}", Self::list_or_none(&properties))];
+        summary.push(format!("indices: {}", Self::list_or_none(&indices)));
+        summary
+    }
+
+    fn property_summary(prop: &Property) -> String {
+        let required = if prop.required { ", required" } else { "" };
+        format!("{}{}", prop.data_type.as_str(), required)
+    }
+
+    /// Describes an index's fields, such as `[title, body] unique`
+    pub fn index_summary(index: &Index) -> String {
+        let fields = index
+            .properties
+            .iter()
+            .map(|p| p.field())
+            .filter(|f| !f.is_empty())
+            .collect::<Vec<_>>()
+            .join(", ");
+        if index.unique {
+            format!("[{}] unique", fields)
+        } else {
+            format!("[{}]", fields)
+        }
+    }
+
+    fn compare_text(details: &mut Vec<String>, field: &str, old: &str, new: &str) {
+        if old != new {
+            details.push(format!("{}: \"{}\" → \"{}\"", field, old, new));
+        }
+    }
+
+    fn compare_flag(details: &mut Vec<String>, field: &str, old: bool, new: bool) {
+        if old != new {
+            details.push(format!("{}: {} → {}", field, old, new));
+        }
+    }
+
+    fn display_value(value: Option<&Value>) -> String {
+        value.map_or_else(|| "none".to_string(), |v| v.to_string())
+    }
+
+    fn list_or_none(items: &[&str]) -> String {
+        if items.is_empty() {
+            "none".to_string()
+        } else {
+            items.join(", ")
+        }
+    }
+
+    fn change(
+        kind: ChangeKind,
+        document_type: &str,
+        target: ChangeTarget,
+        details: Vec<String>,
+    ) -> ContractChange {
+        ContractChange {
+            kind,
+            document_type: document_type.to_string(),
+            target,
+            details,
+        }
+    }
+
+    fn named(document_types: &[DocumentType]) -> impl Iterator<Item = &DocumentType> {
+        document_types.iter().filter(|d| !d.name.is_empty())
+    }
+
+    fn find_doc<'a>(document_types: &'a [DocumentType], name: &str) -> Option<&'a DocumentType> {
+        document_types.iter().find(|d| d.name == name)
+    }
+
+    fn find_doc_mut<'a>(
+        document_types: &'a mut [DocumentType],
+        name: &str,
+    ) -> Option<&'a mut DocumentType> {
+        document_types.iter_mut().find(|d| d.name == name)
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use super::super::contract_diff::{ChangeKind, ChangeTarget, ContractDiffService};
+    use crate::types::{DataType, DocumentType, Index, Property};
+
+    fn property(name: &str, position: u64) -> Property {
+        let mut prop = Property::new(name.to_string(), DataType::String);
+        prop.position = position;
+        prop.max_length = Some(63);
+        prop
+    }
+
+    fn current() -> Vec<DocumentType> {
+        let mut note = DocumentType::new("note".to_string());
+        note.properties = vec![property("title", 0), property("body", 1)];
+        let mut index = Index::new("byTitle".to_string());
+        index.add_property("title".to_string());
+        note.indices.push(index);
+        vec![note]
+    }
+
+    #[test]
+    fn test_diff_lists_each_kind_of_change() {
+        let current = current();
+        let mut proposed = current.clone();
+        proposed[0].description = "A note".to_string();
+        proposed[0].properties[0].max_length = Some(40);
+        proposed[0].properties.remove(1);
+        proposed[0].properties.push(property("tags", 2));
+        proposed[0].indices[0].unique = true;
+        proposed.push(DocumentType::new("folder".to_string()));
+
+        let changes = ContractDiffService::diff(&current, &proposed);
+        let summaries: Vec<String> = changes.iter().map(|c| c.summary()).collect();
+
+        assert_eq!(
+            summaries,
+            vec![
+                "Changed settings of note",
+                "Removed property note.body",
+                "Changed property note.title",
+                "Added property note.tags",
+                "Changed index note.byTitle",
+                "Added document type folder",
+            ]
+        );
+        assert_eq!(changes[2].details, vec!["maxLength: 63 → 40"]);
+        assert!(ContractDiffService::diff(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_apply_only_accepted_changes() {
+        let current = current();
+        let mut proposed = current.clone();
+        proposed[0].properties[0].max_length = Some(40);
+        proposed[0].properties.remove(1);
+        proposed[0].properties.push(property("tags", 1));
+
+        let changes = ContractDiffService::diff(&current, &proposed);
+        let accepted: Vec<bool> = changes
+            .iter()
+            .map(|c| c.kind != ChangeKind::Removed)
+            .collect();
+        let result = ContractDiffService::apply(&current, &proposed, &changes, &accepted);
+
+        let props = &result[0].properties;
+        let names: Vec<&str> = props.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["title", "body", "tags"]);
+        assert_eq!(props[0].max_length, Some(40));
+        // `tags` reused the position of the kept `body`, so it moves to the next free one
+        assert_eq!(props[2].position, 2);
+    }
+
+    #[test]
+    fn test_apply_document_type_changes() {
+        let current = current();
+        let proposed = vec![DocumentType::new("folder".to_string())];
+
+        let changes = ContractDiffService::diff(&current, &proposed);
+        assert!(changes
+            .iter()
+            .all(|c| c.target == ChangeTarget::DocumentType));
+
+        let all = vec![true; changes.len()];
+        let result = ContractDiffService::apply(&current, &proposed, &changes, &all);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "folder");
+
+        let none = vec![false; changes.len()];
+        let result = ContractDiffService::apply(&current, &proposed, &changes, &none);
+        assert_eq!(result[0].name, "note");
+    }
+}
@@ -0,0 +1,91 @@
+use crate::services::JsonGenerator;
+use crate::types::DocumentType;
+use dpp::{
+    data_contract::DataContractFactory, platform_value::Value as PlatformValue,
+    prelude::Identifier, serialization::PlatformSerializableWithPlatformVersion,
+    version::PlatformVersion,
+};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+/// Service for fingerprinting a contract, to check that two copies are the same contract
+///
+/// The fingerprint is the hex-encoded SHA-256 of the contract as DPP serializes it:
+/// the document schemas are built into a `DataContract` and written with its platform
+/// serialization, the format contracts are stored and transmitted in. DPP keeps the key
+/// order of each schema, so the keys of every object are sorted first, and the contract
+/// is created for a zero owner ID and nonce, so its ID is the same on every machine.
+/// Whitespace and key order therefore do not change the fingerprint, while any change
+/// to the schemas does.
+pub struct ContractFingerprint;
+
+impl ContractFingerprint {
+    /// Returns the fingerprint of contract JSON, or why DPP could not build the contract
+    pub fn of(contract: &Value) -> Result<String, String> {
+        let bytes = Self::canonical_bytes(contract)?;
+        Ok(hex::encode(Sha256::digest(bytes)))
+    }
+
+    /// Returns the fingerprint of contract JSON text
+    pub fn of_json(json: &str) -> Result<String, String> {
+        let contract: Value =
+            serde_json::from_str(json).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+        Self::of(&contract)
+    }
+
+    /// Returns the fingerprint of the contract generated from document types
+    pub fn of_document_types(document_types: &[DocumentType]) -> Result<String, String> {
+        Self::of(&JsonGenerator::generate_contract(document_types))
+    }
+
+    /// Returns the DPP serialization of the contract that is hashed
+    pub fn canonical_bytes(contract: &Value) -> Result<Vec<u8>, String> {
+        if contract.as_object().is_none_or(Map::is_empty) {
+            return Err("Data contract must have at least one document type".to_string());
+        }
+        let platform_version = PlatformVersion::latest();
+        let factory = DataContractFactory::new(platform_version.protocol_version)
+            .map_err(|e| format!("Failed to create data contract factory: {}", e))?;
+        let created = factory
+            .create(
+                Identifier::default(),
+                u64::default(),
+                PlatformValue::from(Self::sorted(contract)),
+                None,
+                None,
+            )
+            .map_err(|e| format!("Invalid contract: {}", e))?;
+
+        created
+            .data_contract()
+            .serialize_to_bytes_with_platform_version(platform_version)
+            .map_err(|e| format!("Failed to serialize contract: {}", e))
+    }
+
+    /// Whether `text` is the fingerprint, ignoring case, surrounding whitespace and a `0x` prefix
+    pub fn matches(fingerprint: &str, text: &str) -> bool {
+        let text = text.trim();
+        let text = text
+            .strip_prefix("0x")
+            .or_else(|| text.strip_prefix("0X"))
+            .unwrap_or(text);
+        !text.is_empty() && text.eq_ignore_ascii_case(fingerprint)
+    }
+
+    /// Returns a copy of a value with the keys of every object sorted
+    fn sorted(value: &Value) -> Value {
+        match value {
+            Value::Object(obj) => {
+                let mut entries: Vec<(&String, &Value)> = obj.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                let sorted: Map<String, Value> = entries
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), Self::sorted(value)))
+                    .collect();
+                Value::Object(sorted)
+            }
+            Value::Array(items) => Value::Array(items.iter().map(Self::sorted).collect()),
+            other => other.clone(),
+        }
+    }
+}
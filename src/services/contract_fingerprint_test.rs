@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use super::super::contract_fingerprint::ContractFingerprint;
+    use super::super::json_parser::JsonParser;
+    use serde_json::json;
+
+    const WRITTEN: &str = r#"{
+        "post": {"type": "object", "additionalProperties": false,
+            "properties": {"title": {"position": 0, "type": "string", "maxLength": 63}}},
+        "comment": {"type": "object", "properties": {"body": {"type": "string", "position": 0}},
+            "additionalProperties": false}
+    }"#;
+
+    #[test]
+    fn test_fingerprint_ignores_layout() {
+        let reordered = json!({
+            "comment": {"additionalProperties": false, "type": "object",
+                "properties": {"body": {"position": 0, "type": "string"}}},
+            "post": {"properties": {"title": {"maxLength": 63, "type": "string", "position": 0}},
+                "type": "object", "additionalProperties": false}
+        });
+
+        let fingerprint = ContractFingerprint::of_json(WRITTEN).unwrap();
+        assert_eq!(fingerprint.len(), 64);
+        assert_eq!(ContractFingerprint::of(&reordered).unwrap(), fingerprint);
+        assert_eq!(
+            ContractFingerprint::canonical_bytes(&reordered).unwrap(),
+            ContractFingerprint::canonical_bytes(&serde_json::from_str(WRITTEN).unwrap()).unwrap()
+        );
+        // The form writes the same contract document types in its own order
+        let document_types = JsonParser::parse_contract(WRITTEN).unwrap();
+        assert_eq!(
+            ContractFingerprint::of_document_types(&document_types).unwrap(),
+            fingerprint
+        );
+
+        let mut changed = reordered.clone();
+        changed["post"]["properties"]["title"]["maxLength"] = json!(64);
+        assert_ne!(ContractFingerprint::of(&changed).unwrap(), fingerprint);
+    }
+
+    #[test]
+    fn test_fingerprint_needs_a_contract() {
+        assert!(ContractFingerprint::of(&json!({})).is_err());
+        assert!(ContractFingerprint::of_json("{")
+            .unwrap_err()
+            .starts_with("Failed to parse JSON"));
+    }
+
+    #[test]
+    fn test_fingerprint_matches() {
+        let fingerprint = ContractFingerprint::of_json(WRITTEN).unwrap();
+
+        assert!(ContractFingerprint::matches(&fingerprint, &fingerprint));
+        assert!(ContractFingerprint::matches(
+            &fingerprint,
+            &format!("  0x{}\n", fingerprint.to_uppercase())
+        ));
+        assert!(!ContractFingerprint::matches(&fingerprint, ""));
+        assert!(!ContractFingerprint::matches(
+            &fingerprint,
+            &fingerprint[1..]
+        ));
+    }
+}
@@ -0,0 +1,232 @@
+use crate::services::{JsonParser, ParsedContract, SchemaImport, SchemaSource};
+use serde_json::Value;
+
+/// Syntax outside strict JSON that an imported contract used
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RelaxedSyntax {
+    Comments,
+    TrailingCommas,
+    UnquotedKeys,
+    SingleQuotedStrings,
+    /// Hexadecimal numbers, a leading `+` or a bare decimal point
+    Json5Numbers,
+    /// Other JSON5 syntax, such as `Infinity` or strings continued over lines
+    Json5,
+    Yaml,
+}
+
+impl RelaxedSyntax {
+    /// Returns a short description for the import message
+    pub fn label(&self) -> &'static str {
+        match self {
+            RelaxedSyntax::Comments => "comments",
+            RelaxedSyntax::TrailingCommas => "trailing commas",
+            RelaxedSyntax::UnquotedKeys => "unquoted keys",
+            RelaxedSyntax::SingleQuotedStrings => "single-quoted strings",
+            RelaxedSyntax::Json5Numbers => "JSON5 numbers",
+            RelaxedSyntax::Json5 => "JSON5",
+            RelaxedSyntax::Yaml => "YAML",
+        }
+    }
+}
+
+/// Result of importing contract text
+#[derive(Debug, Clone, Default)]
+pub struct ImportedContract {
+    pub parsed: ParsedContract,
+    /// Relaxed syntax used, empty for strict JSON
+    pub relaxed: Vec<RelaxedSyntax>,
+    /// Schema format the contract was adapted from, if it was not a Dash contract
+    pub schema: Option<SchemaSource>,
+    /// Contract JSON converted from relaxed syntax or a schema, which the diagnostics refer to
+    pub normalized: Option<String>,
+}
+
+impl ImportedContract {
+    /// Describes how the text was read, or `None` for a Dash contract in strict JSON
+    pub fn summary(&self) -> Option<String> {
+        let read = if self.relaxed.contains(&RelaxedSyntax::Yaml) {
+            Some("Read as YAML".to_string())
+        } else if !self.relaxed.is_empty() {
+            let labels: Vec<&str> = self.relaxed.iter().map(RelaxedSyntax::label).collect();
+            Some(format!("Read as JSON5 ({})", labels.join(", ")))
+        } else {
+            None
+        };
+
+        match (read, self.schema) {
+            (Some(read), Some(schema)) => {
+                Some(format!("{}, adapted from {}.", read, schema.label()))
+            }
+            (Some(read), None) => Some(format!("{} and converted to JSON.", read)),
+            (None, Some(schema)) => Some(format!("Adapted from {}.", schema.label())),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Service for importing contracts written as JSON, JSON5 or YAML
+pub struct ContractImport;
+
+impl ContractImport {
+    /// Parses contract text, accepting JSON5 and YAML as well as strict JSON
+    ///
+    /// Relaxed sources are converted to JSON and then parsed like any other contract.
+    /// JSON Schema and OpenAPI documents are adapted by [`SchemaImport`].
+    pub fn parse(text: &str) -> ImportedContract {
+        let (value, relaxed) = if let Ok(value) = serde_json::from_str::<Value>(text) {
+            (value, Vec::new())
+        } else if let Ok(value) = json5::from_str::<Value>(text) {
+            let mut relaxed = Self::json5_syntax(text);
+            if relaxed.is_empty() {
+                relaxed.push(RelaxedSyntax::Json5);
+            }
+            (value, relaxed)
+        } else {
+            match serde_yaml::from_str::<Value>(text) {
+                // Plain text is a valid YAML string, so only mappings count as contracts
+                Ok(value) if value.is_object() => (value, vec![RelaxedSyntax::Yaml]),
+                // Report the problem as strict JSON, the format most contracts use
+                _ => return Self::strict(text),
+            }
+        };
+
+        if let Some(adapted) = SchemaImport::adapt(&value) {
+            return ImportedContract {
+                parsed: adapted.parsed,
+                relaxed,
+                schema: Some(adapted.source),
+                normalized: Some(adapted.text),
+            };
+        }
+        if relaxed.is_empty() {
+            return Self::strict(text);
+        }
+
+        let normalized = serde_json::to_string_pretty(&value).unwrap_or_default();
+        ImportedContract {
+            parsed: JsonParser::parse_with_diagnostics(&normalized),
+            relaxed,
+            schema: None,
+            normalized: Some(normalized),
+        }
+    }
+
+    fn strict(text: &str) -> ImportedContract {
+        ImportedContract {
+            parsed: JsonParser::parse_with_diagnostics(text),
+            ..Default::default()
+        }
+    }
+
+    /// Lists the JSON5 extensions used in a text
+    fn json5_syntax(text: &str) -> Vec<RelaxedSyntax> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut found = Vec::new();
+        let mut note = |syntax: RelaxedSyntax| {
+            if !found.contains(&syntax) {
+                found.push(syntax);
+            }
+        };
+
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if c == '/' && matches!(chars.get(i + 1), Some('/') | Some('*')) {
+                note(RelaxedSyntax::Comments);
+                i = Self::skip_comment(&chars, i);
+            } else if c == '"' || c == '\'' {
+                if c == '\'' {
+                    note(RelaxedSyntax::SingleQuotedStrings);
+                }
+                i = Self::skip_string(&chars, i);
+            } else if c == ',' {
+                if matches!(
+                    chars.get(Self::skip_trivia(&chars, i + 1)),
+                    Some('}') | Some(']')
+                ) {
+                    note(RelaxedSyntax::TrailingCommas);
+                }
+                i += 1;
+            } else if c.is_alphabetic() || c == '_' || c == '$' {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if chars.get(Self::skip_trivia(&chars, i)) == Some(&':') {
+                    note(RelaxedSyntax::UnquotedKeys);
+                } else if word == "Infinity" || word == "NaN" {
+                    note(RelaxedSyntax::Json5);
+                }
+            } else if c.is_ascii_digit() || c == '+' || c == '-' || c == '.' {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || "+-.".contains(chars[i]))
+                {
+                    i += 1;
+                }
+                let number: String = chars[start..i].iter().collect();
+                let digits = number.trim_start_matches(['+', '-']);
+                if number.starts_with('+')
+                    || digits.starts_with("0x")
+                    || digits.starts_with("0X")
+                    || digits.starts_with('.')
+                    || digits.ends_with('.')
+                {
+                    note(RelaxedSyntax::Json5Numbers);
+                }
+            } else {
+                i += 1;
+            }
+        }
+
+        found
+    }
+
+    /// Returns the index just past a comment starting at `start`
+    fn skip_comment(chars: &[char], start: usize) -> usize {
+        let mut i = start + 2;
+        if chars[start + 1] == '/' {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            return i;
+        }
+        while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+            i += 1;
+        }
+        (i + 2).min(chars.len())
+    }
+
+    /// Returns the index just past a string starting at `start`
+    fn skip_string(chars: &[char], start: usize) -> usize {
+        let quote = chars[start];
+        let mut i = start + 1;
+        while i < chars.len() {
+            match chars[i] {
+                '\\' => i += 2,
+                c if c == quote => return i + 1,
+                _ => i += 1,
+            }
+        }
+        chars.len()
+    }
+
+    /// Returns the index of the next character that is not whitespace or a comment
+    fn skip_trivia(chars: &[char], start: usize) -> usize {
+        let mut i = start;
+        while i < chars.len() {
+            if chars[i].is_whitespace() {
+                i += 1;
+            } else if chars[i] == '/' && matches!(chars.get(i + 1), Some('/') | Some('*')) {
+                i = Self::skip_comment(chars, i);
+            } else {
+                break;
+            }
+        }
+        i
+    }
+}
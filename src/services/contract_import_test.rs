@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use super::super::contract_import::{ContractImport, RelaxedSyntax};
+    use crate::types::DataType;
+
+    #[test]
+    fn test_strict_json_is_not_converted() {
+        let imported = ContractImport::parse(
+            r#"{"note": {"properties": {"message": {"type": "string", "position": 0}}}}"#,
+        );
+
+        assert!(imported.relaxed.is_empty());
+        assert!(imported.normalized.is_none());
+        assert_eq!(imported.parsed.document_types[0].name, "note");
+    }
+
+    #[test]
+    fn test_json5_reports_syntax_used() {
+        let json5 = r#"{
+  // Notes left by users
+  note: {
+    properties: {
+      'message': {type: "string", position: 0, maxLength: 0x100,},
+      "count": {"type": "integer", "position": +1},
+    },
+  },
+}"#;
+        let imported = ContractImport::parse(json5);
+
+        assert_eq!(
+            imported.relaxed,
+            vec![
+                RelaxedSyntax::Comments,
+                RelaxedSyntax::UnquotedKeys,
+                RelaxedSyntax::SingleQuotedStrings,
+                RelaxedSyntax::Json5Numbers,
+                RelaxedSyntax::TrailingCommas,
+            ]
+        );
+        assert!(!imported.parsed.has_errors());
+        let properties = &imported.parsed.document_types[0].properties;
+        assert_eq!(properties[0].max_length, Some(256));
+        assert_eq!(properties[1].data_type, DataType::Integer);
+    }
+
+    #[test]
+    fn test_yaml_import() {
+        let yaml = "note:\n  properties:\n    message:\n      type: string\n      position: 0\n";
+        let imported = ContractImport::parse(yaml);
+
+        assert_eq!(imported.relaxed, vec![RelaxedSyntax::Yaml]);
+        assert!(!imported.parsed.has_errors());
+        assert_eq!(
+            imported.parsed.document_types[0].properties[0].name,
+            "message"
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_refer_to_normalized_json() {
+        let imported =
+            ContractImport::parse("{note: {properties: {message: {position: 'first',},},},}");
+        let normalized = imported.normalized.unwrap();
+        let diagnostic = imported
+            .parsed
+            .diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.is_error())
+            .unwrap();
+
+        assert_eq!(&normalized[diagnostic.start..diagnostic.end], "\"first\"");
+    }
+
+    #[test]
+    fn test_unreadable_text_reports_json_error() {
+        let imported = ContractImport::parse("just some words");
+
+        assert!(imported.relaxed.is_empty());
+        assert!(imported.parsed.diagnostics[0]
+            .message
+            .starts_with("Invalid JSON"));
+    }
+}
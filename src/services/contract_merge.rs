@@ -0,0 +1,350 @@
+use crate::services::{ChangeTarget, ContractDiffService, JsonGenerator};
+use crate::types::{DocumentType, Property};
+
+/// Draft whose version of a conflicting element is kept
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MergeSide {
+    Ours,
+    Theirs,
+}
+
+/// An element both drafts changed in different ways
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    pub document_type: String,
+    pub target: ChangeTarget,
+    /// Document type setting in conflict, such as `description`
+    pub setting: Option<&'static str>,
+    /// Each version of the element, `None` where it is missing
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+    /// Version kept in the merged contract, `None` until one is chosen
+    pub resolution: Option<MergeSide>,
+}
+
+impl MergeConflict {
+    /// Returns a short description such as `property post.title`
+    pub fn label(&self) -> String {
+        match (&self.target, self.setting) {
+            (ChangeTarget::DocumentType, Some(setting)) => {
+                format!("{} of {}", setting, self.document_type)
+            }
+            (ChangeTarget::DocumentType, None) => format!("document type {}", self.document_type),
+            (ChangeTarget::Property(name), _) => {
+                format!("property {}.{}", self.document_type, name)
+            }
+            (ChangeTarget::Index(name), _) => format!("index {}.{}", self.document_type, name),
+        }
+    }
+}
+
+/// Three-way merge of two drafts of a contract edited from a common base
+///
+/// Document types, properties and indices are matched by name. A change made by
+/// only one draft is taken, and so is a change both drafts made the same way.
+/// Document type settings merge one by one, while properties and indices merge
+/// as a whole: both drafts changing the same property differently is a conflict.
+pub struct ContractMerge {
+    base: Vec<DocumentType>,
+    ours: Vec<DocumentType>,
+    theirs: Vec<DocumentType>,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl ContractMerge {
+    /// Merges `ours` and `theirs`, both edited from `base`
+    pub fn new(
+        base: Vec<DocumentType>,
+        ours: Vec<DocumentType>,
+        theirs: Vec<DocumentType>,
+    ) -> Self {
+        let mut merge = Self {
+            base,
+            ours,
+            theirs,
+            conflicts: Vec::new(),
+        };
+        merge.conflicts = merge.run(&[]).1;
+        merge
+    }
+
+    /// Keeps one draft's version of a conflicting element
+    pub fn resolve(&mut self, conflict: usize, side: MergeSide) {
+        if let Some(conflict) = self.conflicts.get_mut(conflict) {
+            conflict.resolution = Some(side);
+        }
+    }
+
+    /// Returns the number of conflicts with no version chosen yet
+    pub fn unresolved(&self) -> usize {
+        self.conflicts
+            .iter()
+            .filter(|conflict| conflict.resolution.is_none())
+            .count()
+    }
+
+    /// Returns the merged contract with each conflict resolved as chosen,
+    /// or `None` while any conflict is unresolved
+    pub fn result(&self) -> Option<Vec<DocumentType>> {
+        let resolutions: Vec<Option<MergeSide>> = self
+            .conflicts
+            .iter()
+            .map(|conflict| conflict.resolution)
+            .collect();
+        if resolutions.contains(&None) {
+            return None;
+        }
+        Some(self.run(&resolutions).0)
+    }
+
+    fn run(&self, resolutions: &[Option<MergeSide>]) -> (Vec<DocumentType>, Vec<MergeConflict>) {
+        let mut merger = Merger {
+            resolutions,
+            conflicts: Vec::new(),
+        };
+        let merged = merger.document_types(&self.base, &self.ours, &self.theirs);
+        (merged, merger.conflicts)
+    }
+}
+
+/// One pass over the three versions, finding conflicts in a stable order
+struct Merger<'a> {
+    /// Resolutions of the conflicts found so far, by the order they are found in
+    resolutions: &'a [Option<MergeSide>],
+    conflicts: Vec<MergeConflict>,
+}
+
+impl Merger<'_> {
+    fn document_types(
+        &mut self,
+        base: &[DocumentType],
+        ours: &[DocumentType],
+        theirs: &[DocumentType],
+    ) -> Vec<DocumentType> {
+        let mut merged = Vec::new();
+
+        for name in names(ours, theirs, |doc| &doc.name) {
+            let [base_doc, our_doc, their_doc] =
+                [base, ours, theirs].map(|docs| find(docs, &name, |doc| &doc.name));
+
+            let doc = match (our_doc, their_doc) {
+                (Some(our_doc), Some(their_doc)) => {
+                    let empty = DocumentType::new(name.clone());
+                    Some(self.document_type(base_doc.unwrap_or(&empty), our_doc, their_doc))
+                }
+                // Added by one draft, or removed by one and possibly changed by the other
+                _ => self.element(
+                    &name,
+                    ChangeTarget::DocumentType,
+                    [base_doc, our_doc, their_doc],
+                    |doc| JsonGenerator::generate_contract(std::slice::from_ref(doc)).to_string(),
+                ),
+            };
+            merged.extend(doc);
+        }
+
+        merged
+    }
+
+    fn document_type(
+        &mut self,
+        base: &DocumentType,
+        ours: &DocumentType,
+        theirs: &DocumentType,
+    ) -> DocumentType {
+        let name = ours.name.as_str();
+        let mut merged = DocumentType::new(name.to_string());
+
+        merged.description = self.setting(
+            name,
+            "description",
+            [&base.description, &ours.description, &theirs.description],
+        );
+        merged.comment = self.setting(
+            name,
+            "$comment",
+            [&base.comment, &ours.comment, &theirs.comment],
+        );
+        merged.keywords = self.setting(
+            name,
+            "keywords",
+            [&base.keywords, &ours.keywords, &theirs.keywords],
+        );
+        merged.created_at_required = self.setting(
+            name,
+            "$createdAt required",
+            [
+                &base.created_at_required,
+                &ours.created_at_required,
+                &theirs.created_at_required,
+            ],
+        );
+        merged.updated_at_required = self.setting(
+            name,
+            "$updatedAt required",
+            [
+                &base.updated_at_required,
+                &ours.updated_at_required,
+                &theirs.updated_at_required,
+            ],
+        );
+        merged.additionalProperties = self.setting(
+            name,
+            "additionalProperties",
+            [
+                &base.additionalProperties,
+                &ours.additionalProperties,
+                &theirs.additionalProperties,
+            ],
+        );
+
+        for property in names(&ours.properties, &theirs.properties, |prop| &prop.name) {
+            let versions =
+                [base, ours, theirs].map(|doc| find(&doc.properties, &property, |p| &p.name));
+            merged.properties.extend(self.element(
+                name,
+                ChangeTarget::Property(property.clone()),
+                versions,
+                describe_property,
+            ));
+        }
+        unique_positions(&mut merged.properties);
+
+        for index in names(&ours.indices, &theirs.indices, |index| &index.name) {
+            let versions = [base, ours, theirs].map(|doc| find(&doc.indices, &index, |i| &i.name));
+            merged.indices.extend(self.element(
+                name,
+                ChangeTarget::Index(index.clone()),
+                versions,
+                ContractDiffService::index_summary,
+            ));
+        }
+
+        merged.update_required_properties();
+        if merged.created_at_required {
+            merged.required.push("$createdAt".to_string());
+        }
+        if merged.updated_at_required {
+            merged.required.push("$updatedAt".to_string());
+        }
+        merged
+    }
+
+    /// Merges a document type setting
+    fn setting<T: Clone + PartialEq + ToString>(
+        &mut self,
+        document_type: &str,
+        setting: &'static str,
+        [base, ours, theirs]: [&T; 3],
+    ) -> T {
+        if ours == theirs || theirs == base {
+            return ours.clone();
+        }
+        if ours == base {
+            return theirs.clone();
+        }
+
+        let side = self.conflict(MergeConflict {
+            document_type: document_type.to_string(),
+            target: ChangeTarget::DocumentType,
+            setting: Some(setting),
+            base: Some(base.to_string()),
+            ours: Some(ours.to_string()),
+            theirs: Some(theirs.to_string()),
+            resolution: None,
+        });
+        match side {
+            MergeSide::Ours => ours.clone(),
+            MergeSide::Theirs => theirs.clone(),
+        }
+    }
+
+    /// Merges an element that may be missing from any version, comparing versions by `describe`
+    fn element<T: Clone>(
+        &mut self,
+        document_type: &str,
+        target: ChangeTarget,
+        [base, ours, theirs]: [Option<&T>; 3],
+        describe: impl Fn(&T) -> String,
+    ) -> Option<T> {
+        let [base_text, our_text, their_text] = [base, ours, theirs].map(|v| v.map(&describe));
+        if our_text == their_text || their_text == base_text {
+            return ours.cloned();
+        }
+        if our_text == base_text {
+            return theirs.cloned();
+        }
+
+        let side = self.conflict(MergeConflict {
+            document_type: document_type.to_string(),
+            target,
+            setting: None,
+            base: base_text,
+            ours: our_text,
+            theirs: their_text,
+            resolution: None,
+        });
+        match side {
+            MergeSide::Ours => ours.cloned(),
+            MergeSide::Theirs => theirs.cloned(),
+        }
+    }
+
+    /// Records a conflict and returns the side chosen for it
+    ///
+    /// An unresolved conflict continues with our version, so the pass still finds
+    /// the conflicts after it, but `ContractMerge::result` does not return that contract.
+    fn conflict(&mut self, mut conflict: MergeConflict) -> MergeSide {
+        if let Some(side) = self.resolutions.get(self.conflicts.len()) {
+            conflict.resolution = *side;
+        }
+        let side = conflict.resolution.unwrap_or(MergeSide::Ours);
+        self.conflicts.push(conflict);
+        side
+    }
+}
+
+/// Lists the names used in either list, those of `ours` first
+fn names<T>(ours: &[T], theirs: &[T], name: impl Fn(&T) -> &String) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for item in ours.iter().chain(theirs) {
+        let item_name = name(item);
+        if !item_name.is_empty() && !names.contains(item_name) {
+            names.push(item_name.clone());
+        }
+    }
+    names
+}
+
+fn find<'a, T>(items: &'a [T], name: &str, item_name: impl Fn(&T) -> &String) -> Option<&'a T> {
+    items.iter().find(|item| item_name(item) == name)
+}
+
+fn describe_property(prop: &Property) -> String {
+    let mut description = JsonGenerator::generate_property(prop).to_string();
+    if prop.required {
+        description.push_str(", required");
+    }
+    if prop.transient {
+        description.push_str(", transient");
+    }
+    description
+}
+
+/// Moves properties added by both drafts at the same position to the next free one
+fn unique_positions(properties: &mut [Property]) {
+    for i in 0..properties.len() {
+        if properties[..i]
+            .iter()
+            .any(|prop| prop.position == properties[i].position)
+        {
+            properties[i].position = properties
+                .iter()
+                .map(|prop| prop.position + 1)
+                .max()
+                .unwrap_or(0);
+        }
+    }
+    properties.sort_by_key(|prop| prop.position);
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use super::super::contract_diff::ChangeTarget;
+    use super::super::contract_merge::{ContractMerge, MergeSide};
+    use super::super::json_parser::JsonParser;
+    use crate::types::{DataType, DocumentType, Property};
+
+    fn parse(json: &str) -> Vec<DocumentType> {
+        JsonParser::parse_contract(json).unwrap()
+    }
+
+    fn base() -> Vec<DocumentType> {
+        parse(
+            r#"{"post": {"type": "object", "properties": {
+                "title": {"type": "string", "position": 0, "maxLength": 63},
+                "body": {"type": "string", "position": 1}
+            }, "required": ["title"], "additionalProperties": false}}"#,
+        )
+    }
+
+    #[test]
+    fn test_changes_to_different_elements_merge() {
+        let mut ours = base();
+        ours[0].description = "A blog post".to_string();
+        ours[0].properties[0].max_length = Some(100);
+        ours[0].properties.remove(1);
+
+        let mut theirs = base();
+        theirs[0].properties.push({
+            let mut tags = Property::new("tags".to_string(), DataType::String);
+            tags.position = 2;
+            tags
+        });
+        theirs.push(DocumentType::new("comment".to_string()));
+
+        let merge = ContractMerge::new(base(), ours, theirs);
+        assert!(merge.conflicts.is_empty());
+
+        let merged = merge.result().unwrap();
+        let post = &merged[0];
+        assert_eq!(post.description, "A blog post");
+        let names: Vec<&str> = post.properties.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["title", "tags"]);
+        assert_eq!(post.properties[0].max_length, Some(100));
+        assert_eq!(post.required, vec!["title"]);
+        assert_eq!(merged[1].name, "comment");
+    }
+
+    #[test]
+    fn test_conflicts_are_resolved_by_choice() {
+        let mut ours = base();
+        ours[0].properties[0].max_length = Some(100);
+        ours[0].comment = "ours".to_string();
+        let mut new_ours = Property::new("summary".to_string(), DataType::String);
+        new_ours.position = 2;
+        ours[0].properties.push(new_ours);
+
+        let mut theirs = base();
+        theirs[0].properties[0].max_length = Some(40);
+        theirs[0].comment = "theirs".to_string();
+        let mut new_theirs = Property::new("tags".to_string(), DataType::String);
+        new_theirs.position = 2;
+        theirs[0].properties.push(new_theirs);
+
+        let mut merge = ContractMerge::new(base(), ours, theirs);
+        let labels: Vec<String> = merge.conflicts.iter().map(|c| c.label()).collect();
+        assert_eq!(labels, vec!["$comment of post", "property post.title"]);
+        assert_eq!(
+            merge.conflicts[1].target,
+            ChangeTarget::Property("title".to_string())
+        );
+
+        // No version is kept until every conflict has a choice
+        assert_eq!(merge.unresolved(), 2);
+        assert!(merge.conflicts.iter().all(|c| c.resolution.is_none()));
+        assert!(merge.result().is_none());
+
+        merge.resolve(1, MergeSide::Theirs);
+        assert_eq!(merge.unresolved(), 1);
+        assert!(merge.result().is_none());
+
+        merge.resolve(0, MergeSide::Ours);
+        assert_eq!(merge.unresolved(), 0);
+        let merged = merge.result().unwrap();
+        assert_eq!(merged[0].comment, "ours");
+        assert_eq!(merged[0].properties[0].max_length, Some(40));
+
+        // Both added a property at position 2, so one moves to the next free position
+        let positions: Vec<(&str, u64)> = merged[0]
+            .properties
+            .iter()
+            .map(|p| (p.name.as_str(), p.position))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![("title", 0), ("body", 1), ("summary", 2), ("tags", 3)]
+        );
+    }
+}
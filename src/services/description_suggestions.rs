@@ -0,0 +1,188 @@
+use crate::services::JsonGenerator;
+use crate::types::{DocumentType, PathStep, Property};
+use serde_json::{json, Map, Value};
+
+/// A document type or property, addressed by a dotted key such as `note.author.name`
+///
+/// Fields inside the objects an array holds are keyed through the array, as in `note.links[].url`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DescribedField {
+    pub key: String,
+    pub doc_index: usize,
+    /// Steps from the document type's properties down; empty for the document type itself
+    pub property_path: Vec<PathStep>,
+}
+
+/// A suggested description waiting to be accepted or dismissed
+#[derive(Debug, Clone, PartialEq)]
+pub struct DescriptionSuggestion {
+    pub key: String,
+    pub text: String,
+}
+
+/// Service for finding undocumented fields and filling in suggested descriptions
+pub struct DescriptionService;
+
+impl DescriptionService {
+    /// Lists document types and properties, including nested ones, that have no description
+    pub fn undocumented(document_types: &[DocumentType]) -> Vec<DescribedField> {
+        Self::fields(document_types)
+            .into_iter()
+            .filter(|(_, described)| !described)
+            .map(|(field, _)| field)
+            .collect()
+    }
+
+    /// Builds the JSON object sent to the AI, giving each field's definition as context
+    pub fn request_fields(document_types: &[DocumentType], fields: &[DescribedField]) -> String {
+        let mut request = Map::new();
+
+        for field in fields {
+            let Some(doc) = document_types.get(field.doc_index) else {
+                continue;
+            };
+            let context = if field.property_path.is_empty() {
+                let names: Vec<&str> = doc.properties.iter().map(|p| p.name.as_str()).collect();
+                json!({ "documentType": true, "properties": names })
+            } else {
+                let Some(prop) = Self::property(&doc.properties, &field.property_path) else {
+                    continue;
+                };
+                let mut definition = JsonGenerator::generate_property(prop);
+                if let Some(obj) = definition.as_object_mut() {
+                    // Nested properties are listed as fields of their own
+                    obj.remove("position");
+                    obj.remove("properties");
+                    if let Some(items) = obj.get_mut("items").and_then(Value::as_object_mut) {
+                        items.remove("properties");
+                    }
+                }
+                definition
+            };
+            request.insert(field.key.clone(), context);
+        }
+
+        serde_json::to_string_pretty(&Value::Object(request)).unwrap_or_default()
+    }
+
+    /// Keeps non-empty suggestions for the requested fields, in request order
+    pub fn suggestions(
+        fields: &[DescribedField],
+        response: &[(String, String)],
+    ) -> Vec<DescriptionSuggestion> {
+        fields
+            .iter()
+            .filter_map(|field| {
+                let (_, text) = response.iter().find(|(key, _)| *key == field.key)?;
+                let text = text.trim();
+                (!text.is_empty()).then(|| DescriptionSuggestion {
+                    key: field.key.clone(),
+                    text: text.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Sets the description of the field with the given key
+    ///
+    /// Returns false if the field no longer exists, such as after it was renamed.
+    pub fn apply(document_types: &mut [DocumentType], key: &str, text: &str) -> bool {
+        let Some((field, _)) = Self::fields(document_types)
+            .into_iter()
+            .find(|(field, _)| field.key == key)
+        else {
+            return false;
+        };
+        let doc = &mut document_types[field.doc_index];
+
+        if field.property_path.is_empty() {
+            doc.description = text.to_string();
+            return true;
+        }
+        match Self::property_mut(&mut doc.properties, &field.property_path) {
+            Some(prop) => {
+                prop.description = Some(text.to_string());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Lists every named field with whether it already has a description
+    fn fields(document_types: &[DocumentType]) -> Vec<(DescribedField, bool)> {
+        let mut fields = Vec::new();
+
+        for (doc_index, doc) in document_types.iter().enumerate() {
+            if doc.name.is_empty() {
+                continue;
+            }
+            let field = DescribedField {
+                key: doc.name.clone(),
+                doc_index,
+                property_path: Vec::new(),
+            };
+            fields.push((field, !doc.description.trim().is_empty()));
+            Self::collect_properties(&doc.properties, &doc.name, doc_index, &[], &mut fields);
+        }
+
+        fields
+    }
+
+    fn collect_properties(
+        properties: &[Property],
+        parent_key: &str,
+        doc_index: usize,
+        parent_path: &[PathStep],
+        fields: &mut Vec<(DescribedField, bool)>,
+    ) {
+        for (i, prop) in properties.iter().enumerate() {
+            if prop.name.is_empty() {
+                continue;
+            }
+            let key = format!("{}.{}", parent_key, prop.name);
+            let mut property_path = parent_path.to_vec();
+            property_path.push(PathStep::Property(i));
+
+            let described = prop
+                .description
+                .as_ref()
+                .is_some_and(|description| !description.trim().is_empty());
+            let field = DescribedField {
+                key: key.clone(),
+                doc_index,
+                property_path: property_path.clone(),
+            };
+            fields.push((field, described));
+
+            if let Some(nested) = &prop.properties {
+                Self::collect_properties(nested, &key, doc_index, &property_path, fields);
+            }
+            if let Some(nested) = prop
+                .items
+                .as_ref()
+                .and_then(|items| items.properties.as_ref())
+            {
+                property_path.push(PathStep::Items);
+                let key = format!("{}[]", key);
+                Self::collect_properties(nested, &key, doc_index, &property_path, fields);
+            }
+        }
+    }
+
+    fn property<'a>(properties: &'a [Property], path: &[PathStep]) -> Option<&'a Property> {
+        let (&PathStep::Property(first), rest) = path.split_first()? else {
+            return None;
+        };
+        properties.get(first)?.nested(rest)
+    }
+
+    fn property_mut<'a>(
+        properties: &'a mut [Property],
+        path: &[PathStep],
+    ) -> Option<&'a mut Property> {
+        let (&PathStep::Property(first), rest) = path.split_first()? else {
+            return None;
+        };
+        properties.get_mut(first)?.nested_mut(rest)
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use super::super::description_suggestions::DescriptionService;
+    use crate::types::{DataType, DocumentType, Property};
+
+    fn contract() -> Vec<DocumentType> {
+        let mut note = DocumentType::new("note".to_string());
+        let mut title = Property::new("title".to_string(), DataType::String);
+        title.description = Some("Title of the note".to_string());
+        let mut author = Property::new("author".to_string(), DataType::Object);
+        author.position = 1;
+        author.properties = Some(Box::new(vec![Property::new(
+            "name".to_string(),
+            DataType::String,
+        )]));
+        let mut links = Property::new("links".to_string(), DataType::Array);
+        links.position = 2;
+        links.set_items(Some(DataType::Object));
+        if let Some(items) = links.items.as_mut() {
+            items.properties = Some(Box::new(vec![Property::new(
+                "url".to_string(),
+                DataType::String,
+            )]));
+        }
+        note.properties = vec![title, author, links];
+
+        let mut folder = DocumentType::new("folder".to_string());
+        folder.description = "A folder of notes".to_string();
+        vec![note, folder, DocumentType::default()]
+    }
+
+    #[test]
+    fn test_finds_undocumented_fields_including_nested() {
+        let keys: Vec<String> = DescriptionService::undocumented(&contract())
+            .into_iter()
+            .map(|field| field.key)
+            .collect();
+
+        assert_eq!(
+            keys,
+            vec![
+                "note",
+                "note.author",
+                "note.author.name",
+                "note.links",
+                "note.links[].url"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_describes_fields_inside_array_items() {
+        let mut document_types = contract();
+        let fields = DescriptionService::undocumented(&document_types);
+        let url: Vec<_> = fields
+            .into_iter()
+            .filter(|field| field.key == "note.links[].url")
+            .collect();
+
+        let request = DescriptionService::request_fields(&document_types, &url);
+        assert!(request.contains("\"note.links[].url\""));
+        assert!(request.contains("\"type\": \"string\""));
+
+        assert!(DescriptionService::apply(
+            &mut document_types,
+            "note.links[].url",
+            "Address of the link"
+        ));
+        let items = document_types[0].properties[2].items.as_ref().unwrap();
+        let inner = items.properties.as_ref().unwrap();
+        assert_eq!(inner[0].description.as_deref(), Some("Address of the link"));
+    }
+
+    #[test]
+    fn test_applies_accepted_suggestions_by_key() {
+        let mut document_types = contract();
+        let fields = DescriptionService::undocumented(&document_types);
+        let response = vec![
+            ("note.author.name".to_string(), " Display name ".to_string()),
+            ("note".to_string(), String::new()),
+            ("unknown".to_string(), "Ignored".to_string()),
+        ];
+
+        let suggestions = DescriptionService::suggestions(&fields, &response);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].text, "Display name");
+
+        assert!(DescriptionService::apply(
+            &mut document_types,
+            &suggestions[0].key,
+            &suggestions[0].text
+        ));
+        let nested = document_types[0].properties[1].properties.as_ref().unwrap();
+        assert_eq!(nested[0].description.as_deref(), Some("Display name"));
+        assert!(!DescriptionService::apply(&mut document_types, "note.body", "Gone"));
+    }
+}
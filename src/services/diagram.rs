@@ -0,0 +1,464 @@
+use crate::types::{DocumentType, Index, Property};
+use crate::utils::to_snake_case;
+
+/// Box width in the SVG diagram
+const BOX_WIDTH: usize = 260;
+/// Height of the document type name header
+const HEADER_HEIGHT: usize = 28;
+/// Height of each property or index row
+const ROW_HEIGHT: usize = 18;
+/// Gap between boxes
+const GAP: usize = 60;
+
+/// Text formats the diagram can be exported to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiagramFormat {
+    Svg,
+    Mermaid,
+    Graphviz,
+}
+
+impl DiagramFormat {
+    /// Returns the file name and MIME type used when downloading
+    pub fn file_info(&self) -> (&'static str, &'static str) {
+        match self {
+            DiagramFormat::Svg => ("contract.svg", "image/svg+xml"),
+            DiagramFormat::Mermaid => ("contract.mmd", "text/plain"),
+            DiagramFormat::Graphviz => ("contract.dot", "text/vnd.graphviz"),
+        }
+    }
+}
+
+/// A reference from an identifier property to another document type
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramEdge {
+    pub from: String,
+    pub property: String,
+    pub to: String,
+    /// Whether the target was inferred from the property name rather than declared
+    pub inferred: bool,
+}
+
+/// Service for rendering document types and their references as diagrams
+pub struct DiagramService;
+
+impl DiagramService {
+    /// Finds references between document types
+    ///
+    /// Identifier properties point to the document type declared in
+    /// `Property::ref_document_type`, or else to a document type whose name
+    /// matches the end of the property name without its `Id`/`Ref` suffix,
+    /// so `replyToPostId` points to `post`.
+    pub fn edges(document_types: &[DocumentType]) -> Vec<DiagramEdge> {
+        let mut edges = Vec::new();
+
+        for doc_type in document_types.iter().filter(|d| !d.name.is_empty()) {
+            for prop in doc_type
+                .properties
+                .iter()
+                .filter(|p| !p.name.is_empty() && p.is_identifier())
+            {
+                if let Some(target) = Self::declared_target(document_types, prop) {
+                    edges.push(DiagramEdge {
+                        from: doc_type.name.clone(),
+                        property: prop.name.clone(),
+                        to: target,
+                        inferred: false,
+                    });
+                } else if let Some(target) = Self::infer_target(document_types, &prop.name) {
+                    edges.push(DiagramEdge {
+                        from: doc_type.name.clone(),
+                        property: prop.name.clone(),
+                        to: target,
+                        inferred: true,
+                    });
+                }
+            }
+        }
+
+        edges
+    }
+
+    /// Returns the declared target of a property if it names an existing document type
+    fn declared_target(document_types: &[DocumentType], prop: &Property) -> Option<String> {
+        let declared = prop.ref_document_type.as_deref()?;
+        document_types
+            .iter()
+            .find(|d| d.name == declared)
+            .map(|d| d.name.clone())
+    }
+
+    /// Infers the referenced document type from a property name
+    pub fn infer_target(document_types: &[DocumentType], property_name: &str) -> Option<String> {
+        let mut words: Vec<String> = to_snake_case(property_name)
+            .split('_')
+            .map(str::to_string)
+            .collect();
+
+        match words.last().map(String::as_str) {
+            Some("id") | Some("ref") => {
+                words.pop();
+            }
+            _ => return None,
+        }
+
+        // Try the longest suffix first, so `forumThreadId` prefers `forumThread` over `thread`
+        for start in 0..words.len() {
+            let candidate = words[start..].concat();
+            if let Some(doc_type) = document_types
+                .iter()
+                .find(|d| !d.name.is_empty() && Self::normalize(&d.name) == candidate)
+            {
+                return Some(doc_type.name.clone());
+            }
+        }
+
+        None
+    }
+
+    fn normalize(name: &str) -> String {
+        to_snake_case(name).replace('_', "")
+    }
+
+    /// Renders the diagram in the given format
+    pub fn render(document_types: &[DocumentType], format: DiagramFormat) -> String {
+        match format {
+            DiagramFormat::Svg => Self::to_svg(document_types),
+            DiagramFormat::Mermaid => Self::to_mermaid(document_types),
+            DiagramFormat::Graphviz => Self::to_graphviz(document_types),
+        }
+    }
+
+    /// Renders a Mermaid `erDiagram`
+    pub fn to_mermaid(document_types: &[DocumentType]) -> String {
+        let mut output = String::from("erDiagram\n");
+
+        for doc_type in document_types.iter().filter(|d| !d.name.is_empty()) {
+            output.push_str(&format!("    {} {{\n", Self::mermaid_name(&doc_type.name)));
+            for prop in doc_type.properties.iter().filter(|p| !p.name.is_empty()) {
+                let key = if prop.is_identifier() { " FK" } else { "" };
+                let comment = if prop.required { " \"required\"" } else { "" };
+                output.push_str(&format!(
+                    "        {} {}{}{}\n",
+                    Self::type_label(prop),
+                    Self::mermaid_name(&prop.name),
+                    key,
+                    comment
+                ));
+            }
+            output.push_str("    }\n");
+
+            for index in Self::indices(doc_type) {
+                output.push_str(&format!(
+                    "    %% {} index {}: {}\n",
+                    doc_type.name,
+                    index.name,
+                    Self::index_label(index)
+                ));
+            }
+        }
+
+        for edge in Self::edges(document_types) {
+            output.push_str(&format!(
+                "    {} }}o--|| {} : {}\n",
+                Self::mermaid_name(&edge.from),
+                Self::mermaid_name(&edge.to),
+                Self::mermaid_name(&edge.property)
+            ));
+        }
+
+        output
+    }
+
+    /// Renders a Graphviz `digraph` with one HTML-like table per document type
+    pub fn to_graphviz(document_types: &[DocumentType]) -> String {
+        let mut output = String::from(
+            "digraph contract {\n    rankdir=LR;\n    node [shape=plaintext, fontname=\"Helvetica\"];\n",
+        );
+
+        for doc_type in document_types.iter().filter(|d| !d.name.is_empty()) {
+            let mut rows = format!(
+                "<tr><td bgcolor=\"#008de4\"><font color=\"white\"><b>{}</b></font></td></tr>",
+                Self::escape_xml(&doc_type.name)
+            );
+            for (i, prop) in doc_type
+                .properties
+                .iter()
+                .filter(|p| !p.name.is_empty())
+                .enumerate()
+            {
+                rows.push_str(&format!(
+                    "<tr><td port=\"p{}\" align=\"left\">{}</td></tr>",
+                    i,
+                    Self::escape_xml(&Self::property_label(prop))
+                ));
+            }
+            for index in Self::indices(doc_type) {
+                rows.push_str(&format!(
+                    "<tr><td align=\"left\"><i>{}</i></td></tr>",
+                    Self::escape_xml(&format!("{}: {}", index.name, Self::index_label(index)))
+                ));
+            }
+            output.push_str(&format!(
+                "    {} [label=<<table border=\"0\" cellborder=\"1\" cellspacing=\"0\">{}</table>>];\n",
+                Self::dot_id(&doc_type.name),
+                rows
+            ));
+        }
+
+        for edge in Self::edges(document_types) {
+            let port = Self::property_row(document_types, &edge.from, &edge.property)
+                .map(|row| format!(":\"p{}\"", row))
+                .unwrap_or_default();
+            let style = if edge.inferred { " [style=dashed]" } else { "" };
+            output.push_str(&format!(
+                "    {}{} -> {}{};\n",
+                Self::dot_id(&edge.from),
+                port,
+                Self::dot_id(&edge.to),
+                style
+            ));
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    /// Renders a standalone SVG with boxes laid out on a grid
+    pub fn to_svg(document_types: &[DocumentType]) -> String {
+        let doc_types: Vec<&DocumentType> = document_types
+            .iter()
+            .filter(|d| !d.name.is_empty())
+            .collect();
+        let columns = (1..).find(|c| c * c >= doc_types.len()).unwrap_or(1).max(1);
+
+        // Row heights are the tallest box in each grid row
+        let heights: Vec<usize> = doc_types.iter().map(|d| Self::box_height(d)).collect();
+        let row_heights: Vec<usize> = heights
+            .chunks(columns)
+            .map(|chunk| chunk.iter().copied().max().unwrap_or(0))
+            .collect();
+
+        let mut positions = Vec::new();
+        let mut y = GAP / 2;
+        for (row, row_height) in row_heights.iter().enumerate() {
+            for column in 0..columns {
+                if row * columns + column < doc_types.len() {
+                    positions.push((GAP / 2 + column * (BOX_WIDTH + GAP), y));
+                }
+            }
+            y += row_height + GAP;
+        }
+
+        let width = GAP + columns * (BOX_WIDTH + GAP);
+        let height = y.max(GAP);
+
+        let mut output = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"Helvetica, Arial, sans-serif\" font-size=\"12\">\n\
+             <defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto-start-reverse\"><path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"#012060\"/></marker></defs>\n",
+            w = width,
+            h = height
+        );
+
+        for edge in Self::edges(document_types) {
+            let from = doc_types.iter().position(|d| d.name == edge.from);
+            let to = doc_types.iter().position(|d| d.name == edge.to);
+            let row = Self::property_row(document_types, &edge.from, &edge.property);
+            if let (Some(from), Some(to), Some(row)) = (from, to, row) {
+                let (fx, fy) = positions[from];
+                let (tx, ty) = positions[to];
+                let start_y = fy + HEADER_HEIGHT + row * ROW_HEIGHT + ROW_HEIGHT / 2;
+                let start_x = if tx >= fx { fx + BOX_WIDTH } else { fx };
+                let dash = if edge.inferred {
+                    " stroke-dasharray=\"4 3\""
+                } else {
+                    ""
+                };
+                let path = if from == to {
+                    // Self-reference loops out of the right side back to the header
+                    let right = fx + BOX_WIDTH;
+                    format!(
+                        "M {} {} C {} {}, {} {}, {} {}",
+                        right,
+                        start_y,
+                        right + GAP / 2,
+                        start_y,
+                        right + GAP / 2,
+                        fy + HEADER_HEIGHT / 2,
+                        right,
+                        fy + HEADER_HEIGHT / 2
+                    )
+                } else if ty == fy {
+                    // Boxes in the same grid row connect side to side at the header
+                    let end_x = if tx > fx { tx } else { tx + BOX_WIDTH };
+                    format!(
+                        "M {} {} L {} {}",
+                        start_x,
+                        start_y,
+                        end_x,
+                        ty + HEADER_HEIGHT / 2
+                    )
+                } else {
+                    let end_y = if ty > fy { ty } else { ty + heights[to] };
+                    format!(
+                        "M {} {} L {} {}",
+                        start_x,
+                        start_y,
+                        tx + BOX_WIDTH / 2,
+                        end_y
+                    )
+                };
+                output.push_str(&format!(
+                    "<path d=\"{}\" fill=\"none\" stroke=\"#012060\"{} marker-end=\"url(#arrow)\"/>\n",
+                    path, dash
+                ));
+            }
+        }
+
+        for (doc_type, (x, y)) in doc_types.iter().zip(positions.iter()) {
+            Self::write_svg_box(&mut output, doc_type, *x, *y);
+        }
+
+        output.push_str("</svg>\n");
+        output
+    }
+
+    fn write_svg_box(output: &mut String, doc_type: &DocumentType, x: usize, y: usize) {
+        let height = Self::box_height(doc_type);
+        output.push_str(&format!(
+            "<g><rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" rx=\"6\" fill=\"#ffffff\" stroke=\"#008de4\"/>\n\
+             <rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{hh}\" rx=\"6\" fill=\"#008de4\"/>\n\
+             <text x=\"{tx}\" y=\"{ty}\" fill=\"#ffffff\" font-weight=\"bold\">{name}</text>\n",
+            x = x,
+            y = y,
+            w = BOX_WIDTH,
+            h = height,
+            hh = HEADER_HEIGHT,
+            tx = x + 10,
+            ty = y + 19,
+            name = Self::escape_xml(&doc_type.name)
+        ));
+
+        let mut row_y = y + HEADER_HEIGHT;
+        for prop in doc_type.properties.iter().filter(|p| !p.name.is_empty()) {
+            output.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" fill=\"#444544\">{}</text>\n",
+                x + 10,
+                row_y + 13,
+                Self::escape_xml(&Self::property_label(prop))
+            ));
+            row_y += ROW_HEIGHT;
+        }
+
+        let indices = Self::indices(doc_type);
+        if !indices.is_empty() {
+            output.push_str(&format!(
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#b6b6b6\"/>\n",
+                x,
+                row_y + 3,
+                x + BOX_WIDTH,
+                row_y + 3
+            ));
+            row_y += 6;
+            for index in indices {
+                output.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\" fill=\"#012060\" font-style=\"italic\">{}</text>\n",
+                    x + 10,
+                    row_y + 13,
+                    Self::escape_xml(&format!("{}: {}", index.name, Self::index_label(index)))
+                ));
+                row_y += ROW_HEIGHT;
+            }
+        }
+
+        output.push_str("</g>\n");
+    }
+
+    fn box_height(doc_type: &DocumentType) -> usize {
+        let properties = doc_type
+            .properties
+            .iter()
+            .filter(|p| !p.name.is_empty())
+            .count();
+        let indices = Self::indices(doc_type).len();
+        let separator = if indices > 0 { 6 } else { 0 };
+        HEADER_HEIGHT + (properties + indices) * ROW_HEIGHT + separator + 6
+    }
+
+    /// Returns the row of a named property among the non-empty properties
+    fn property_row(
+        document_types: &[DocumentType],
+        doc_name: &str,
+        prop_name: &str,
+    ) -> Option<usize> {
+        document_types
+            .iter()
+            .find(|d| d.name == doc_name)?
+            .properties
+            .iter()
+            .filter(|p| !p.name.is_empty())
+            .position(|p| p.name == prop_name)
+    }
+
+    fn indices(doc_type: &DocumentType) -> Vec<&Index> {
+        doc_type
+            .indices
+            .iter()
+            .filter(|index| !index.name.is_empty() && !index.properties.is_empty())
+            .collect()
+    }
+
+    fn index_label(index: &Index) -> String {
+        let fields = index
+            .properties
+            .iter()
+            .map(|p| p.field())
+            .filter(|f| !f.is_empty())
+            .collect::<Vec<_>>()
+            .join(", ");
+        if index.unique {
+            format!("{} (unique)", fields)
+        } else {
+            fields
+        }
+    }
+
+    fn property_label(prop: &Property) -> String {
+        let required = if prop.required { " *" } else { "" };
+        format!("{}: {}{}", prop.name, Self::type_label(prop), required)
+    }
+
+    fn type_label(prop: &Property) -> &'static str {
+        if prop.is_identifier() {
+            "identifier"
+        } else {
+            prop.data_type.as_str()
+        }
+    }
+
+    /// Replaces characters Mermaid does not accept in names
+    fn mermaid_name(name: &str) -> String {
+        name.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
+    /// Quotes a name as a Graphviz ID
+    fn dot_id(name: &str) -> String {
+        format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    fn escape_xml(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+}
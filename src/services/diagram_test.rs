@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use super::super::diagram::{DiagramFormat, DiagramService};
+    use super::super::gallery::GalleryService;
+    use super::super::json_parser::JsonParser;
+    use crate::types::DocumentType;
+
+    fn document_types(names: &[&str]) -> Vec<DocumentType> {
+        names
+            .iter()
+            .map(|name| DocumentType {
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_infer_target_from_property_name() {
+        let doc_types = document_types(&["post", "forumThread", "thread"]);
+
+        assert_eq!(
+            DiagramService::infer_target(&doc_types, "replyToPostId"),
+            Some("post".to_string())
+        );
+        assert_eq!(
+            DiagramService::infer_target(&doc_types, "forumThreadRef"),
+            Some("forumThread".to_string())
+        );
+        assert_eq!(
+            DiagramService::infer_target(&doc_types, "threadId"),
+            Some("thread".to_string())
+        );
+        assert_eq!(DiagramService::infer_target(&doc_types, "post"), None);
+        assert_eq!(DiagramService::infer_target(&doc_types, "authorId"), None);
+    }
+
+    #[test]
+    fn test_declared_reference_overrides_inference() {
+        let mut doc_types = GalleryService::find("social-feed")
+            .unwrap()
+            .document_types()
+            .unwrap();
+
+        let edges = DiagramService::edges(&doc_types);
+        let reply = edges
+            .iter()
+            .find(|e| e.property == "replyToPostId")
+            .unwrap();
+        assert_eq!(reply.to, "post");
+        assert!(reply.inferred);
+
+        let post = doc_types.iter_mut().find(|d| d.name == "post").unwrap();
+        let prop = post
+            .properties
+            .iter_mut()
+            .find(|p| p.name == "replyToPostId")
+            .unwrap();
+        prop.ref_document_type = Some("like".to_string());
+
+        let edges = DiagramService::edges(&doc_types);
+        let reply = edges
+            .iter()
+            .find(|e| e.property == "replyToPostId")
+            .unwrap();
+        assert_eq!(reply.to, "like");
+        assert!(!reply.inferred);
+    }
+
+    /// A post replying to another post, and a like of a post
+    fn contract() -> Vec<DocumentType> {
+        JsonParser::parse_contract(
+            r#"{
+                "post": {"type": "object", "properties": {
+                    "text": {"type": "string", "position": 0, "maxLength": 63},
+                    "replyToPostId": {"type": "array", "position": 1, "byteArray": true,
+                        "minItems": 32, "maxItems": 32,
+                        "contentMediaType": "application/x.dash.dpp.identifier"}
+                }, "indices": [{"name": "byText", "properties": [{"text": "asc"}]}],
+                "required": ["text"], "additionalProperties": false},
+                "like": {"type": "object", "properties": {
+                    "postId": {"type": "array", "position": 0, "byteArray": true,
+                        "minItems": 32, "maxItems": 32,
+                        "contentMediaType": "application/x.dash.dpp.identifier"}
+                }, "additionalProperties": false}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_formats_include_nodes_and_edges() {
+        let doc_types = contract();
+
+        let mermaid = DiagramService::render(&doc_types, DiagramFormat::Mermaid);
+        assert!(mermaid.starts_with("erDiagram\n"));
+        assert!(mermaid.contains("    post {\n        string text \"required\"\n"));
+        assert!(mermaid.contains("        identifier replyToPostId FK\n"));
+        assert!(mermaid.contains("    %% post index byText: text\n"));
+        assert!(mermaid.contains("    post }o--|| post : replyToPostId\n"));
+        assert!(mermaid.contains("    like }o--|| post : postId\n"));
+
+        let dot = DiagramService::render(&doc_types, DiagramFormat::Graphviz);
+        assert!(dot.contains("    \"post\" [label=<"));
+        assert!(dot.contains("    \"like\" [label=<"));
+        assert!(dot.contains("<td port=\"p1\" align=\"left\">replyToPostId: identifier</td>"));
+        // Inferred references are dashed
+        assert!(dot.contains("    \"post\":\"p1\" -> \"post\" [style=dashed];\n"));
+        assert!(dot.contains("    \"like\":\"p0\" -> \"post\" [style=dashed];\n"));
+
+        let svg = DiagramService::render(&doc_types, DiagramFormat::Svg);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains(">post</text>"));
+        assert!(svg.contains(">like</text>"));
+        assert!(svg.contains(">text: string *</text>"));
+        assert!(svg.contains(">byText: text</text>"));
+        assert_eq!(svg.matches("marker-end=\"url(#arrow)\"").count(), 2);
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_names_escaped() {
+        let mut doc_types = contract();
+        doc_types[0].name = "say \"hi\" <b>&".to_string();
+        doc_types[1].properties[0].ref_document_type = Some(doc_types[0].name.clone());
+
+        let svg = DiagramService::to_svg(&doc_types);
+        assert!(svg.contains(">say &quot;hi&quot; &lt;b&gt;&amp;</text>"));
+        assert!(!svg.contains("<b>"));
+
+        let dot = DiagramService::to_graphviz(&doc_types);
+        assert!(dot.contains("    \"say \\\"hi\\\" <b>&\" [label=<"));
+        assert!(dot.contains("<b>say &quot;hi&quot; &lt;b&gt;&amp;</b>"));
+        assert!(dot.contains("    \"like\":\"p0\" -> \"say \\\"hi\\\" <b>&\";\n"));
+
+        let mermaid = DiagramService::to_mermaid(&doc_types);
+        assert!(mermaid.contains("    say__hi___b__ {\n"));
+    }
+}
@@ -0,0 +1,309 @@
+use crate::types::{DataType, DocumentType, Index, Property};
+
+/// Stylesheet embedded in standalone HTML documentation
+const HTML_STYLE: &str = "body{font-family:Inter,sans-serif;color:#444544;max-width:960px;margin:40px auto;padding:0 20px}\
+table{border-collapse:collapse;width:100%;margin:12px 0}\
+th,td{border:1px solid #ddd;padding:6px 10px;text-align:left;vertical-align:top;font-size:14px}\
+th{background:#f5f6f7}code{background:#f5f6f7;padding:1px 4px;border-radius:3px}\
+h1,h2,h3,h4{color:#012060}";
+
+/// A property table, possibly for a nested object
+struct PropertyTable<'a> {
+    path: String,
+    properties: &'a [Property],
+}
+
+/// Service for generating human-readable documentation from document types
+pub struct DocsGenerator;
+
+impl DocsGenerator {
+    /// Generates Markdown documentation for a contract
+    pub fn to_markdown(document_types: &[DocumentType]) -> String {
+        let mut output = String::from("# Data contract\n");
+
+        for doc_type in document_types.iter().filter(|d| !d.name.is_empty()) {
+            output.push_str(&format!("\n## `{}`\n\n", doc_type.name));
+
+            if !doc_type.description.is_empty() {
+                output.push_str(&format!("{}\n\n", doc_type.description));
+            }
+            if !doc_type.keywords.is_empty() {
+                output.push_str(&format!("**Keywords:** {}\n\n", doc_type.keywords));
+            }
+            if !doc_type.comment.is_empty() {
+                // Every line is quoted, or the blockquote ends at the first line break
+                for line in doc_type.comment.lines() {
+                    output.push_str(format!("> {}", line).trim_end());
+                    output.push('\n');
+                }
+                output.push('\n');
+            }
+
+            let system = Self::system_required(doc_type);
+            if !system.is_empty() {
+                output.push_str(&format!("**Required system fields:** {}\n\n", system));
+            }
+
+            for table in Self::property_tables(&doc_type.name, &doc_type.properties) {
+                output.push_str(&format!("### Properties of `{}`\n\n", table.path));
+                if table.properties.is_empty() {
+                    output.push_str("_No properties._\n\n");
+                    continue;
+                }
+                output.push_str(
+                    "| Name | Type | Required | Constraints | Position | Description |\n",
+                );
+                output.push_str("|---|---|---|---|---|---|\n");
+                for prop in table.properties.iter().filter(|p| !p.name.is_empty()) {
+                    output.push_str(&format!(
+                        "| `{}` | {} | {} | {} | {} | {} |\n",
+                        prop.name,
+                        prop.data_type.as_str(),
+                        if prop.required { "yes" } else { "no" },
+                        Self::markdown_cell(&Self::constraints(prop).join(", ")),
+                        prop.position,
+                        Self::markdown_cell(prop.description.as_deref().unwrap_or_default()),
+                    ));
+                }
+                output.push('\n');
+            }
+
+            output.push_str("### Indices\n\n");
+            let indices = Self::indices(doc_type);
+            if indices.is_empty() {
+                output.push_str("_No indices._\n");
+            } else {
+                output.push_str("| Name | Properties | Unique |\n");
+                output.push_str("|---|---|---|\n");
+                for index in indices {
+                    output.push_str(&format!(
+                        "| `{}` | {} | {} |\n",
+                        index.name,
+                        Self::index_fields(index)
+                            .iter()
+                            .map(|f| format!("`{}`", f))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        if index.unique { "yes" } else { "no" },
+                    ));
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Generates a standalone HTML page documenting a contract
+    pub fn to_html(document_types: &[DocumentType]) -> String {
+        let mut body = String::from("<h1>Data contract</h1>\n");
+
+        for doc_type in document_types.iter().filter(|d| !d.name.is_empty()) {
+            body.push_str(&format!(
+                "<h2><code>{}</code></h2>\n",
+                Self::escape_html(&doc_type.name)
+            ));
+
+            if !doc_type.description.is_empty() {
+                body.push_str(&format!(
+                    "<p>{}</p>\n",
+                    Self::escape_html(&doc_type.description)
+                ));
+            }
+            if !doc_type.keywords.is_empty() {
+                body.push_str(&format!(
+                    "<p><strong>Keywords:</strong> {}</p>\n",
+                    Self::escape_html(&doc_type.keywords)
+                ));
+            }
+            if !doc_type.comment.is_empty() {
+                body.push_str(&format!(
+                    "<blockquote>{}</blockquote>\n",
+                    Self::escape_html(&doc_type.comment)
+                ));
+            }
+
+            let system = Self::system_required(doc_type);
+            if !system.is_empty() {
+                body.push_str(&format!(
+                    "<p><strong>Required system fields:</strong> {}</p>\n",
+                    Self::escape_html(&system)
+                ));
+            }
+
+            for table in Self::property_tables(&doc_type.name, &doc_type.properties) {
+                body.push_str(&format!(
+                    "<h3>Properties of <code>{}</code></h3>\n",
+                    Self::escape_html(&table.path)
+                ));
+                if table.properties.is_empty() {
+                    body.push_str("<p><em>No properties.</em></p>\n");
+                    continue;
+                }
+                body.push_str("<table>\n<tr><th>Name</th><th>Type</th><th>Required</th><th>Constraints</th><th>Position</th><th>Description</th></tr>\n");
+                for prop in table.properties.iter().filter(|p| !p.name.is_empty()) {
+                    body.push_str(&format!(
+                        "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                        Self::escape_html(&prop.name),
+                        prop.data_type.as_str(),
+                        if prop.required { "yes" } else { "no" },
+                        Self::escape_html(&Self::constraints(prop).join(", ")),
+                        prop.position,
+                        Self::escape_html(prop.description.as_deref().unwrap_or_default()),
+                    ));
+                }
+                body.push_str("</table>\n");
+            }
+
+            body.push_str("<h3>Indices</h3>\n");
+            let indices = Self::indices(doc_type);
+            if indices.is_empty() {
+                body.push_str("<p><em>No indices.</em></p>\n");
+            } else {
+                body.push_str(
+                    "<table>\n<tr><th>Name</th><th>Properties</th><th>Unique</th></tr>\n",
+                );
+                for index in indices {
+                    body.push_str(&format!(
+                        "<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>\n",
+                        Self::escape_html(&index.name),
+                        Self::index_fields(index)
+                            .iter()
+                            .map(|f| format!("<code>{}</code>", Self::escape_html(f)))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        if index.unique { "yes" } else { "no" },
+                    ));
+                }
+                body.push_str("</table>\n");
+            }
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\" />\n<title>Data contract</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+            HTML_STYLE, body
+        )
+    }
+
+    /// Flattens a property list into one table per object, parents first
+    fn property_tables<'a>(path: &str, properties: &'a [Property]) -> Vec<PropertyTable<'a>> {
+        let mut tables = vec![PropertyTable {
+            path: path.to_string(),
+            properties,
+        }];
+
+        for prop in properties.iter().filter(|p| !p.name.is_empty()) {
+            Self::nested_tables(&format!("{}.{}", path, prop.name), prop, &mut tables);
+        }
+
+        tables
+    }
+
+    /// Adds the tables of an object property, or of the object items of an array as `path[]`
+    fn nested_tables<'a>(path: &str, prop: &'a Property, tables: &mut Vec<PropertyTable<'a>>) {
+        match prop.data_type {
+            DataType::Object => {
+                let nested = prop.properties.as_deref().map(Vec::as_slice).unwrap_or(&[]);
+                tables.extend(Self::property_tables(path, nested));
+            }
+            DataType::Array => {
+                if let Some(ref items) = prop.items {
+                    Self::nested_tables(&format!("{}[]", path), items, tables);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Lists the validation constraints of a property in schema terms
+    fn constraints(prop: &Property) -> Vec<String> {
+        let mut constraints = Vec::new();
+
+        if let Some(min) = prop.min_length {
+            constraints.push(format!("minLength: {}", min));
+        }
+        if let Some(max) = prop.max_length {
+            constraints.push(format!("maxLength: {}", max));
+        }
+        if let Some(ref pattern) = prop.pattern {
+            constraints.push(format!("pattern: {}", pattern));
+        }
+        if let Some(ref format) = prop.format {
+            constraints.push(format!("format: {}", format));
+        }
+        if let Some(min) = &prop.minimum {
+            constraints.push(format!("minimum: {}", min));
+        }
+        if let Some(max) = &prop.maximum {
+            constraints.push(format!("maximum: {}", max));
+        }
+        if prop.enum_values.as_ref().is_some_and(|v| !v.is_empty()) {
+            constraints.push(format!("one of: {}", prop.enum_values_display()));
+        }
+        if prop.is_byte_array() {
+            constraints.push("byteArray".to_string());
+        }
+        if let Some(ref items) = prop.items {
+            constraints.push(format!("items: {}", items.data_type.as_str()));
+        }
+        if let Some(min) = prop.min_items {
+            constraints.push(format!("minItems: {}", min));
+        }
+        if let Some(max) = prop.max_items {
+            constraints.push(format!("maxItems: {}", max));
+        }
+        if let Some(ref media_type) = prop.content_media_type {
+            constraints.push(format!("contentMediaType: {}", media_type));
+        }
+        if let Some(min) = prop.min_properties {
+            constraints.push(format!("minProperties: {}", min));
+        }
+        if let Some(max) = prop.max_properties {
+            constraints.push(format!("maxProperties: {}", max));
+        }
+
+        constraints
+    }
+
+    /// Lists the required system fields of a document type
+    fn system_required(doc_type: &DocumentType) -> String {
+        let mut fields = Vec::new();
+        if doc_type.created_at_required {
+            fields.push("$createdAt");
+        }
+        if doc_type.updated_at_required {
+            fields.push("$updatedAt");
+        }
+        fields.join(", ")
+    }
+
+    /// Returns the indices that would be emitted in the contract
+    fn indices(doc_type: &DocumentType) -> Vec<&Index> {
+        doc_type
+            .indices
+            .iter()
+            .filter(|index| !index.name.is_empty() && !index.properties.is_empty())
+            .collect()
+    }
+
+    fn index_fields(index: &Index) -> Vec<&str> {
+        index
+            .properties
+            .iter()
+            .map(|p| p.field())
+            .filter(|f| !f.is_empty())
+            .collect()
+    }
+
+    /// Escapes a value for use inside a Markdown table cell
+    fn markdown_cell(value: &str) -> String {
+        value.replace('|', "\\|").replace('\n', " ")
+    }
+
+    fn escape_html(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use super::super::docs_generator::DocsGenerator;
+    use super::super::json_parser::JsonParser;
+    use crate::types::DocumentType;
+
+    fn document_types() -> Vec<DocumentType> {
+        JsonParser::parse_contract(
+            r#"{"post": {"type": "object", "description": "Posts <b>& \"replies\"</b>",
+                "properties": {
+                    "title": {"type": "string", "position": 0, "minLength": 1, "maxLength": 63,
+                        "description": "Shown as <h1> | bold"},
+                    "status": {"type": "string", "position": 1, "enum": ["draft", "live"]},
+                    "hash": {"type": "array", "position": 2, "byteArray": true, "minItems": 32, "maxItems": 32},
+                    "author": {"type": "object", "position": 3,
+                        "properties": {"name": {"type": "string", "position": 0}},
+                        "additionalProperties": false}
+                },
+                "indices": [{"name": "byTitle", "properties": [{"title": "asc"}], "unique": true}],
+                "required": ["title", "$createdAt"], "additionalProperties": false}}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = DocsGenerator::to_markdown(&document_types());
+
+        assert!(markdown.contains("## `post`\n\nPosts <b>& \"replies\"</b>\n"));
+        assert!(markdown.contains("**Required system fields:** $createdAt\n"));
+        assert!(markdown.contains(
+            "| `title` | string | yes | minLength: 1, maxLength: 63 | 0 | Shown as <h1> \\| bold |\n"
+        ));
+        assert!(markdown.contains("| `status` | string | no | one of: draft, live | 1 |  |\n"));
+        assert!(markdown.contains("byteArray, minItems: 32, maxItems: 32"));
+        assert!(markdown.contains("### Properties of `post.author`\n"));
+        assert!(markdown.contains("| `byTitle` | `title` | yes |\n"));
+    }
+
+    #[test]
+    fn test_html_escapes_user_text() {
+        let html = DocsGenerator::to_html(&document_types());
+
+        assert!(html.contains("<p>Posts &lt;b&gt;&amp; &quot;replies&quot;&lt;/b&gt;</p>\n"));
+        assert!(html.contains("<td>Shown as &lt;h1&gt; | bold</td>"));
+        assert!(!html.contains("<h1> |"));
+        assert!(html.contains("<td>minLength: 1, maxLength: 63</td>"));
+        assert!(html.contains("<h3>Properties of <code>post.author</code></h3>\n"));
+        assert!(html.contains(
+            "<tr><td><code>byTitle</code></td><td><code>title</code></td><td>yes</td></tr>\n"
+        ));
+    }
+
+    #[test]
+    fn test_markdown_items_and_comments() {
+        let document_types = JsonParser::parse_contract(
+            r#"{"post": {"type": "object", "properties": {
+                "links": {"type": "array", "position": 0, "items": {"type": "object",
+                    "properties": {"url": {"type": "string", "position": 0, "format": "uri"}},
+                    "required": ["url"], "additionalProperties": false}}
+            }, "$comment": "First line\n\nThird line", "additionalProperties": false}}"#,
+        )
+        .unwrap();
+        let markdown = DocsGenerator::to_markdown(&document_types);
+
+        assert!(markdown.contains("> First line\n>\n> Third line\n\n"));
+        assert!(markdown.contains("### Properties of `post.links[]`\n"));
+        assert!(markdown.contains("| `url` | string | yes | format: uri | 0 |  |\n"));
+    }
+}
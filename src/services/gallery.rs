@@ -0,0 +1,67 @@
+use super::JsonParser;
+use crate::types::DocumentType;
+
+/// A complete example contract bundled with the app
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StarterContract {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub json: &'static str,
+}
+
+impl StarterContract {
+    /// Parses the bundled JSON into document types for the form
+    pub fn document_types(&self) -> Result<Vec<DocumentType>, String> {
+        JsonParser::parse_contract(self.json)
+    }
+}
+
+/// Starter contracts modeled on real Dash Platform contracts
+const STARTER_CONTRACTS: &[StarterContract] = &[
+    StarterContract {
+        id: "dpns",
+        name: "Names (DPNS-like)",
+        description: "Human-readable names under a parent domain, registered with a preorder commitment and resolving to an identity.",
+        json: include_str!("starter_contracts/dpns.json"),
+    },
+    StarterContract {
+        id: "dashpay",
+        name: "DashPay contacts",
+        description: "User profiles, encrypted contact info and contact requests that exchange payment keys between identities.",
+        json: include_str!("starter_contracts/dashpay.json"),
+    },
+    StarterContract {
+        id: "social-feed",
+        name: "Social feed",
+        description: "Short posts with replies, likes and follows, indexed for timelines.",
+        json: include_str!("starter_contracts/social_feed.json"),
+    },
+    StarterContract {
+        id: "marketplace",
+        name: "Marketplace",
+        description: "Listings browsable by category and price, orders placed against listings, and one review per buyer.",
+        json: include_str!("starter_contracts/marketplace.json"),
+    },
+    StarterContract {
+        id: "token-gated-forum",
+        name: "Token-gated forum",
+        description: "Forums that require holding a minimum balance of a token, with threads and replies.",
+        json: include_str!("starter_contracts/token_gated_forum.json"),
+    },
+];
+
+/// Service for the gallery of starter contracts
+pub struct GalleryService;
+
+impl GalleryService {
+    /// Returns all starter contracts in display order
+    pub fn starter_contracts() -> &'static [StarterContract] {
+        STARTER_CONTRACTS
+    }
+
+    /// Finds a starter contract by its id
+    pub fn find(id: &str) -> Option<&'static StarterContract> {
+        STARTER_CONTRACTS.iter().find(|contract| contract.id == id)
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use super::super::gallery::GalleryService;
+    use super::super::json_generator::JsonGenerator;
+    use super::super::validation::ValidationService;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_starter_contract_ids_are_unique() {
+        let ids: HashSet<&str> = GalleryService::starter_contracts()
+            .iter()
+            .map(|contract| contract.id)
+            .collect();
+
+        assert_eq!(ids.len(), GalleryService::starter_contracts().len());
+        assert!(GalleryService::find("dpns").is_some());
+        assert!(GalleryService::find("missing").is_none());
+    }
+
+    #[test]
+    fn test_starter_contracts_have_descriptions() {
+        for contract in GalleryService::starter_contracts() {
+            assert!(!contract.name.is_empty(), "{} has no name", contract.id);
+            assert!(
+                !contract.description.is_empty(),
+                "{} has no description",
+                contract.id
+            );
+        }
+    }
+
+    #[test]
+    fn test_starter_contracts_parse() {
+        for contract in GalleryService::starter_contracts() {
+            let document_types = contract
+                .document_types()
+                .unwrap_or_else(|e| panic!("{} failed to parse: {}", contract.id, e));
+
+            assert!(
+                !document_types.is_empty(),
+                "{} has no document types",
+                contract.id
+            );
+        }
+    }
+
+    #[test]
+    fn test_starter_contracts_pass_validation() {
+        for contract in GalleryService::starter_contracts() {
+            let errors = ValidationService::validate_schema(contract.json)
+                .unwrap_or_else(|e| panic!("{} failed to validate: {}", contract.id, e));
+
+            assert!(
+                errors.is_empty(),
+                "{} has validation errors: {:?}",
+                contract.id,
+                errors
+            );
+        }
+    }
+
+    #[test]
+    fn test_loaded_starter_contracts_pass_validation() {
+        // The form regenerates the contract after loading, so that output must validate too
+        for contract in GalleryService::starter_contracts() {
+            let document_types = contract.document_types().unwrap();
+            let json = JsonGenerator::generate_contract(&document_types);
+            let json_str = serde_json::to_string(&json).unwrap();
+
+            let errors = ValidationService::validate_schema(&json_str).unwrap();
+            assert!(
+                errors.is_empty(),
+                "{} has validation errors after loading: {:?}",
+                contract.id,
+                errors
+            );
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::services::JsonParser;
+use serde_json::Value;
+
+/// Service for finding a data contract in free-form model output
+pub struct JsonExtractor;
+
+impl JsonExtractor {
+    /// Returns the first candidate JSON text that parses as a data contract
+    ///
+    /// Candidates are tried in order: the whole text, fenced code blocks, then
+    /// every balanced top-level `{...}` object. If a candidate is an object that
+    /// wraps a contract, such as `{"contract": {...}}`, the wrapped value is used.
+    pub fn extract_contract(text: &str) -> Result<String, String> {
+        Self::extract_matching(text, "data contract", Self::looks_like_contract)
+    }
+
+    /// Returns the first candidate JSON object of description texts keyed by field
+    ///
+    /// Candidates are tried like in `extract_contract`, so wrappers such as
+    /// `{"descriptions": {...}}` are unwrapped too.
+    pub fn extract_descriptions(text: &str) -> Result<Vec<(String, String)>, String> {
+        let json = Self::extract_matching(text, "list of descriptions", |value| {
+            value
+                .as_object()
+                .is_some_and(|obj| !obj.is_empty() && obj.values().all(Value::is_string))
+        })?;
+        let value: Value = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+
+        Ok(value
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(key, text)| Some((key.clone(), text.as_str()?.to_string())))
+            .collect())
+    }
+
+    /// Returns the first candidate, or object wrapped by one, that `accept` approves of
+    fn extract_matching(
+        text: &str,
+        what: &str,
+        accept: impl Fn(&Value) -> bool,
+    ) -> Result<String, String> {
+        let mut first_error = None;
+
+        for candidate in Self::candidates(text) {
+            let value: Value = match serde_json::from_str(&candidate) {
+                Ok(value) => value,
+                Err(e) => {
+                    first_error.get_or_insert_with(|| format!("Invalid JSON: {}", e));
+                    continue;
+                }
+            };
+
+            if accept(&value) {
+                return Ok(candidate);
+            }
+
+            if let Some(inner) = value
+                .as_object()
+                .into_iter()
+                .flat_map(|obj| obj.values())
+                .find(|inner| accept(inner))
+            {
+                return Ok(inner.to_string());
+            }
+
+            first_error.get_or_insert_with(|| format!("JSON does not look like a {}", what));
+        }
+
+        Err(match first_error {
+            Some(error) => format!("No {} found in the response ({})", what, error),
+            None => "No JSON found in the response".to_string(),
+        })
+    }
+
+    /// Lists candidate JSON texts in order of preference, without duplicates
+    pub fn candidates(text: &str) -> Vec<String> {
+        let mut candidates: Vec<String> = Vec::new();
+        let mut push = |candidate: &str| {
+            let candidate = candidate.trim();
+            if candidate.starts_with('{') && !candidates.iter().any(|c| c == candidate) {
+                candidates.push(candidate.to_string());
+            }
+        };
+
+        push(text);
+        for block in Self::fenced_blocks(text) {
+            push(block);
+        }
+        for object in Self::balanced_objects(text) {
+            push(object);
+        }
+
+        candidates
+    }
+
+    /// Returns true if every top-level value is a document type definition
+    fn looks_like_contract(value: &Value) -> bool {
+        let Some(obj) = value.as_object() else {
+            return false;
+        };
+
+        !obj.is_empty()
+            && obj.values().all(|doc| {
+                doc.get("properties").is_some_and(Value::is_object)
+                    || doc.get("type").and_then(Value::as_str) == Some("object")
+            })
+            && JsonParser::parse_contract(&value.to_string()).is_ok()
+    }
+
+    /// Returns the contents of Markdown code fences
+    fn fenced_blocks(text: &str) -> Vec<&str> {
+        let mut blocks = Vec::new();
+        let mut rest = text;
+
+        while let Some(start) = rest.find("```") {
+            let after_fence = &rest[start + 3..];
+            // Skip the info string, such as `json`
+            let content_start = after_fence.find('\n').map_or(after_fence.len(), |i| i + 1);
+            let content = &after_fence[content_start..];
+            match content.find("```") {
+                Some(end) => {
+                    blocks.push(&content[..end]);
+                    rest = &content[end + 3..];
+                }
+                None => break,
+            }
+        }
+
+        blocks
+    }
+
+    /// Returns every top-level `{...}` span, skipping braces inside strings
+    fn balanced_objects(text: &str) -> Vec<&str> {
+        let mut objects = Vec::new();
+        let mut depth = 0usize;
+        let mut start = None;
+        let mut in_string = false;
+        let mut escaped = false;
+
+        for (i, c) in text.char_indices() {
+            if in_string {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+
+            match c {
+                // Quotes only delimit strings inside an object; prose may contain stray quotes
+                '"' if depth > 0 => in_string = true,
+                '{' => {
+                    if depth == 0 {
+                        start = Some(i);
+                    }
+                    depth += 1;
+                }
+                '}' if depth > 0 => {
+                    depth -= 1;
+                    if depth == 0 {
+                        if let Some(s) = start.take() {
+                            objects.push(&text[s..=i]);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // An unmatched `{` in prose would swallow the rest, so rescan after it
+        if let Some(s) = start {
+            objects.extend(Self::balanced_objects(&text[s + 1..]));
+        }
+
+        objects
+    }
+}
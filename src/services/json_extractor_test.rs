@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use super::super::json_extractor::JsonExtractor;
+    use serde_json::Value;
+
+    const CONTRACT: &str = r#"{"note":{"type":"object","properties":{"title":{"type":"string","position":0}},"additionalProperties":false}}"#;
+
+    fn assert_extracts_contract(text: &str) {
+        let extracted = JsonExtractor::extract_contract(text)
+            .unwrap_or_else(|e| panic!("failed to extract from {:?}: {}", text, e));
+        let expected: Value = serde_json::from_str(CONTRACT).unwrap();
+        let actual: Value = serde_json::from_str(&extracted).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_extracts_plain_json() {
+        assert_extracts_contract(CONTRACT);
+    }
+
+    #[test]
+    fn test_extracts_from_prose_with_braces() {
+        assert_extracts_contract(&format!(
+            "Here is the contract {{as requested}}:\n{}\nUse {{ and }} carefully.",
+            CONTRACT
+        ));
+        assert_extracts_contract(&format!("An unmatched {{ brace, then {}", CONTRACT));
+    }
+
+    #[test]
+    fn test_prefers_contract_among_several_blocks() {
+        let text = format!(
+            "First an example of a query:\n```json\n{{\"where\": [[\"title\", \"==\", \"}}\"]]}}\n```\n\
+             And the contract:\n```json\n{}\n```",
+            CONTRACT
+        );
+        assert_extracts_contract(&text);
+    }
+
+    #[test]
+    fn test_unwraps_wrapped_contract() {
+        assert_extracts_contract(&format!("{{\"contract\": {}}}", CONTRACT));
+    }
+
+    #[test]
+    fn test_reports_missing_contract() {
+        assert!(JsonExtractor::extract_contract("Sorry, I can't help with that.").is_err());
+        assert!(JsonExtractor::extract_contract("{\"answer\": 42}").is_err());
+    }
+
+    #[test]
+    fn test_extracts_wrapped_descriptions() {
+        let text = "```json\n{\"descriptions\": {\"note\": \"A note\", \"note.title\": \"Its title\"}}\n```";
+        let descriptions = JsonExtractor::extract_descriptions(text).unwrap();
+
+        assert_eq!(
+            descriptions,
+            vec![
+                ("note".to_string(), "A note".to_string()),
+                ("note.title".to_string(), "Its title".to_string()),
+            ]
+        );
+        assert!(JsonExtractor::extract_descriptions(CONTRACT).is_err());
+    }
+}
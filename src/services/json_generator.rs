@@ -1,7 +1,14 @@
-use crate::types::{DataType, DocumentType, Index, Property};
+use crate::types::{
+    DataType, DocumentType, Index, KeyOrder, OutputFormat, Property, PropertyOrder,
+};
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
 use serde_json::{Map, Value};
 
 /// Service for generating JSON from internal data structures
+///
+/// Output is always in the canonical form described on `CanonicalForm`, so
+/// generating from parsed JSON gives the canonical form of that JSON.
 pub struct JsonGenerator;
 
 impl JsonGenerator {
@@ -19,6 +26,142 @@ impl JsonGenerator {
         Value::Object(contract)
     }
 
+    /// Generates contract JSON text laid out as `format` asks
+    ///
+    /// The canonical form from `generate_contract` is only reordered, indented and,
+    /// with `explicit_defaults`, given the default values it leaves out.
+    pub fn generate_text(document_types: &[DocumentType], format: &OutputFormat) -> String {
+        let mut contract = Self::generate_contract(document_types);
+
+        if format.explicit_defaults {
+            for doc_type in contract
+                .as_object_mut()
+                .into_iter()
+                .flat_map(|c| c.values_mut())
+            {
+                Self::add_defaults(doc_type);
+            }
+        }
+
+        let mut doc_types: Vec<(String, Value)> = match contract {
+            Value::Object(doc_types) => doc_types
+                .into_iter()
+                .map(|(name, doc_type)| (name, Self::arrange(doc_type, format)))
+                .collect(),
+            _ => Vec::new(),
+        };
+        if format.key_order != KeyOrder::AsAuthored {
+            doc_types.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+
+        Self::write(
+            &Value::Object(doc_types.into_iter().collect()),
+            format.indent,
+        )
+    }
+
+    /// Adds the default values the canonical form leaves out to a schema and its inner schemas
+    fn add_defaults(schema: &mut Value) {
+        let Some(schema) = schema.as_object_mut() else {
+            return;
+        };
+
+        if schema.get("type") == Some(&Value::from("object")) {
+            schema
+                .entry("additionalProperties")
+                .or_insert(Value::Bool(false));
+        }
+        for index in schema
+            .get_mut("indices")
+            .and_then(Value::as_array_mut)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_object_mut)
+        {
+            index.entry("unique").or_insert(Value::Bool(false));
+        }
+
+        if let Some(items) = schema.get_mut("items") {
+            Self::add_defaults(items);
+        }
+        for property in schema
+            .get_mut("properties")
+            .and_then(Value::as_object_mut)
+            .into_iter()
+            .flat_map(|properties| properties.values_mut())
+        {
+            Self::add_defaults(property);
+        }
+    }
+
+    /// Orders the keys of a value, with `properties` objects ordered by the property order
+    fn arrange(value: Value, format: &OutputFormat) -> Value {
+        match value {
+            Value::Object(object) => {
+                let mut entries: Vec<(String, Value)> = object
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let value = match (key.as_str(), value) {
+                            ("properties", Value::Object(properties)) => {
+                                Self::arrange_properties(properties, format)
+                            }
+                            (_, value) => Self::arrange(value, format),
+                        };
+                        (key, value)
+                    })
+                    .collect();
+                if format.key_order == KeyOrder::Alphabetical {
+                    entries.sort_by(|a, b| a.0.cmp(&b.0));
+                }
+                Value::Object(entries.into_iter().collect())
+            }
+            Value::Array(values) => Value::Array(
+                values
+                    .into_iter()
+                    .map(|value| Self::arrange(value, format))
+                    .collect(),
+            ),
+            value => value,
+        }
+    }
+
+    /// Orders the entries of a `properties` object
+    fn arrange_properties(properties: Map<String, Value>, format: &OutputFormat) -> Value {
+        let mut entries: Vec<(String, Value)> = properties
+            .into_iter()
+            .map(|(name, property)| (name, Self::arrange(property, format)))
+            .collect();
+
+        match format.property_order {
+            PropertyOrder::Position => entries.sort_by_key(|(_, property)| {
+                property
+                    .get("position")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0)
+            }),
+            PropertyOrder::Name => entries.sort_by(|a, b| a.0.cmp(&b.0)),
+        }
+
+        Value::Object(entries.into_iter().collect())
+    }
+
+    /// Writes JSON with `indent` spaces per level, or on a single line for 0
+    fn write(value: &Value, indent: usize) -> String {
+        if indent == 0 {
+            return serde_json::to_string(value).unwrap_or_default();
+        }
+
+        let indent = " ".repeat(indent);
+        let mut serializer = serde_json::Serializer::with_formatter(
+            Vec::new(),
+            PrettyFormatter::with_indent(indent.as_bytes()),
+        );
+        match value.serialize(&mut serializer) {
+            Ok(()) => String::from_utf8(serializer.into_inner()).unwrap_or_default(),
+            Err(_) => String::new(),
+        }
+    }
+
     /// Generates JSON for a single document type
     fn generate_document_type(doc_type: &DocumentType) -> Value {
         let mut doc_obj = Map::new();
@@ -36,8 +179,8 @@ impl JsonGenerator {
         // They exist at the platform level and are only referenced in required array
 
         // Generate indices
-        if !doc_type.indices.is_empty() {
-            let indices = Self::generate_indices(&doc_type.indices);
+        let indices = Self::generate_indices(&doc_type.indices);
+        if !indices.is_empty() {
             doc_obj.insert("indices".to_string(), Value::Array(indices));
         }
 
@@ -61,6 +204,17 @@ impl JsonGenerator {
             doc_obj.insert("required".to_string(), Value::Array(required_values));
         }
 
+        // Generate transient array
+        let transient: Vec<Value> = doc_type
+            .properties
+            .iter()
+            .filter(|prop| prop.transient && !prop.name.is_empty())
+            .map(|prop| Value::String(prop.name.clone()))
+            .collect();
+        if !transient.is_empty() {
+            doc_obj.insert("transient".to_string(), Value::Array(transient));
+        }
+
         // Set additionalProperties
         doc_obj.insert(
             "additionalProperties".to_string(),
@@ -110,12 +264,20 @@ impl JsonGenerator {
     }
 
     /// Generates JSON for a single property
-    fn generate_property(prop: &Property) -> Value {
+    pub fn generate_property(prop: &Property) -> Value {
         let mut prop_obj = Map::new();
 
         // Set position
         prop_obj.insert("position".to_string(), Value::Number(prop.position.into()));
 
+        prop_obj.extend(Self::generate_schema(prop));
+        Value::Object(prop_obj)
+    }
+
+    /// Generates the schema of a property or of array items, without a position
+    fn generate_schema(prop: &Property) -> Map<String, Value> {
+        let mut prop_obj = Map::new();
+
         // Set type
         prop_obj.insert(
             "type".to_string(),
@@ -132,6 +294,20 @@ impl JsonGenerator {
             }
         }
 
+        // Add comment if present (internal documentation)
+        if let Some(ref comment) = prop.comment {
+            if !comment.is_empty() {
+                prop_obj.insert("$comment".to_string(), Value::String(comment.clone()));
+            }
+        }
+
+        // Add allowed values if present
+        if let Some(ref enum_values) = prop.enum_values {
+            if !enum_values.is_empty() {
+                prop_obj.insert("enum".to_string(), Value::Array(enum_values.clone()));
+            }
+        }
+
         // Add type-specific properties
         match prop.data_type {
             DataType::String => {
@@ -151,7 +327,7 @@ impl JsonGenerator {
             }
         }
 
-        Value::Object(prop_obj)
+        prop_obj
     }
 
     /// Adds string-specific properties
@@ -176,18 +352,27 @@ impl JsonGenerator {
 
     /// Adds number/integer-specific properties
     fn add_number_properties(prop_obj: &mut Map<String, Value>, prop: &Property) {
-        if let Some(minimum) = prop.minimum {
-            prop_obj.insert("minimum".to_string(), Value::Number(minimum.into()));
+        if let Some(ref minimum) = prop.minimum {
+            prop_obj.insert("minimum".to_string(), Value::Number(minimum.clone()));
         }
-        if let Some(maximum) = prop.maximum {
-            prop_obj.insert("maximum".to_string(), Value::Number(maximum.into()));
+        if let Some(ref maximum) = prop.maximum {
+            prop_obj.insert("maximum".to_string(), Value::Number(maximum.clone()));
         }
     }
 
     /// Adds array-specific properties
     fn add_array_properties(prop_obj: &mut Map<String, Value>, prop: &Property) {
-        // Arrays must have byteArray: true in Dash Platform
-        prop_obj.insert("byteArray".to_string(), Value::Bool(true));
+        match prop.items {
+            Some(ref items) => {
+                prop_obj.insert(
+                    "items".to_string(),
+                    Value::Object(Self::generate_schema(items)),
+                );
+            }
+            None => {
+                prop_obj.insert("byteArray".to_string(), Value::Bool(true));
+            }
+        }
 
         if let Some(min_items) = prop.min_items {
             prop_obj.insert("minItems".to_string(), Value::Number(min_items.into()));
@@ -196,7 +381,7 @@ impl JsonGenerator {
             prop_obj.insert("maxItems".to_string(), Value::Number(max_items.into()));
         }
         if let Some(ref content_media_type) = prop.content_media_type {
-            if !content_media_type.is_empty() {
+            if !content_media_type.is_empty() && prop.items.is_none() {
                 prop_obj.insert(
                     "contentMediaType".to_string(),
                     Value::String(content_media_type.clone()),
@@ -239,14 +424,6 @@ impl JsonGenerator {
             );
         }
 
-        if let Some(ref required) = prop.rec_required {
-            if !required.is_empty() {
-                let required_values: Vec<Value> =
-                    required.iter().map(|s| Value::String(s.clone())).collect();
-                prop_obj.insert("required".to_string(), Value::Array(required_values));
-            }
-        }
-
         if let Some(additional_properties) = prop.additional_properties {
             prop_obj.insert(
                 "additionalProperties".to_string(),
@@ -259,7 +436,9 @@ impl JsonGenerator {
     fn generate_indices(indices: &[Index]) -> Vec<Value> {
         indices
             .iter()
-            .filter(|index| !index.name.is_empty() && !index.properties.is_empty())
+            .filter(|index| {
+                !index.name.is_empty() && index.properties.iter().any(|prop| !prop.0.is_empty())
+            })
             .map(Self::generate_index)
             .collect()
     }
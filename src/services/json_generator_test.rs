@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use super::super::json_generator::JsonGenerator;
+    use super::super::json_parser::JsonParser;
+    use crate::types::{DocumentType, KeyOrder, OutputFormat, PropertyOrder};
+
+    fn document_types() -> Vec<DocumentType> {
+        JsonParser::parse_contract(
+            r#"{
+                "post": {"type": "object", "properties": {
+                    "title": {"type": "string", "position": 0, "maxLength": 63},
+                    "author": {"type": "object", "position": 1,
+                        "properties": {"name": {"type": "string", "position": 0}}}
+                }, "indices": [{"name": "byTitle", "properties": [{"title": "asc"}]}],
+                "additionalProperties": false},
+                "comment": {"type": "object", "properties": {
+                    "body": {"type": "string", "position": 0}
+                }, "additionalProperties": false}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_default_format_is_pretty_canonical_json() {
+        let document_types = document_types();
+        let contract = JsonGenerator::generate_contract(&document_types);
+
+        assert_eq!(
+            JsonGenerator::generate_text(&document_types, &OutputFormat::default()),
+            serde_json::to_string_pretty(&contract).unwrap()
+        );
+        assert_eq!(
+            JsonGenerator::generate_text(&document_types, &OutputFormat::default().compact()),
+            serde_json::to_string(&contract).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_key_and_property_order() {
+        let document_types = document_types();
+        let format = OutputFormat {
+            key_order: KeyOrder::Alphabetical,
+            property_order: PropertyOrder::Name,
+            indent: 0,
+            explicit_defaults: false,
+        };
+
+        assert_eq!(
+            JsonGenerator::generate_text(&document_types, &format),
+            concat!(
+                r#"{"comment":{"additionalProperties":false,"properties":{"body":{"position":0,"type":"string"}},"type":"object"},"#,
+                r#""post":{"additionalProperties":false,"indices":[{"name":"byTitle","properties":[{"title":"asc"}]}],"#,
+                r#""properties":{"author":{"position":1,"properties":{"name":{"position":0,"type":"string"}},"type":"object"},"#,
+                r#""title":{"maxLength":63,"position":0,"type":"string"}},"type":"object"}}"#
+            )
+        );
+
+        let dpp = OutputFormat {
+            key_order: KeyOrder::Dpp,
+            indent: 4,
+            ..OutputFormat::default()
+        };
+        let text = JsonGenerator::generate_text(&document_types, &dpp);
+        assert!(text.starts_with("{\n    \"comment\": {\n        \"type\": \"object\""));
+    }
+
+    #[test]
+    fn test_explicit_defaults() {
+        let format = OutputFormat {
+            explicit_defaults: true,
+            indent: 0,
+            ..OutputFormat::default()
+        };
+        let text = JsonGenerator::generate_text(&document_types(), &format);
+
+        assert!(text.contains(
+            r#""properties":{"name":{"position":0,"type":"string"}},"additionalProperties":false}"#
+        ));
+        assert!(text.contains(r#"[{"title":"asc"}],"unique":false}"#));
+    }
+}
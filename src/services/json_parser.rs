@@ -1,56 +1,192 @@
-use crate::types::{DataType, DocumentType, Index, IndexProperties, Property};
-use serde_json::Value;
+use crate::services::SourceLocations;
+use crate::types::{
+    DataType, Diagnostic, DocumentType, Index, IndexProperties, Property, Severity,
+};
+use serde_json::{Map, Value};
+
+/// Keys of a document type that the form keeps
+const DOCUMENT_TYPE_KEYS: &[&str] = &[
+    "type",
+    "properties",
+    "indices",
+    "required",
+    "transient",
+    "additionalProperties",
+    "description",
+    "keywords",
+    "$comment",
+];
+
+/// Keys of an index that the form keeps
+const INDEX_KEYS: &[&str] = &["name", "properties", "unique"];
+
+/// Keys of a property of any type that the form keeps
+const PROPERTY_KEYS: &[&str] = &["type", "position", "description", "$comment", "enum"];
+
+/// Document types parsed from a contract, with every problem found along the way
+#[derive(Debug, Clone, Default)]
+pub struct ParsedContract {
+    pub document_types: Vec<DocumentType>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ParsedContract {
+    /// Returns true if any problem prevents importing the contract
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(Diagnostic::is_error)
+    }
+}
+
+/// Collects diagnostics, locating them in the source text
+struct Diagnostics<'a> {
+    text: &'a str,
+    locations: SourceLocations,
+    found: Vec<Diagnostic>,
+}
+
+impl Diagnostics<'_> {
+    fn error(&mut self, pointer: &str, message: String) {
+        self.push(Severity::Error, pointer, message);
+    }
+
+    fn warning(&mut self, pointer: &str, message: String) {
+        self.push(Severity::Warning, pointer, message);
+    }
+
+    fn push(&mut self, severity: Severity, pointer: &str, message: String) {
+        let diagnostic = self
+            .locations
+            .diagnostic(self.text, severity, pointer, message);
+        self.found.push(diagnostic);
+    }
+}
 
 /// Service for parsing JSON into internal data structures
 pub struct JsonParser;
 
 impl JsonParser {
     /// Parses a JSON string into document types
+    ///
+    /// Fails with every error found, one per line. Warnings are ignored.
     pub fn parse_contract(json_str: &str) -> Result<Vec<DocumentType>, String> {
-        let json_value: Value =
-            serde_json::from_str(json_str).map_err(|e| format!("Invalid JSON: {}", e))?;
+        let parsed = Self::parse_with_diagnostics(json_str);
+        if parsed.has_errors() {
+            return Err(parsed
+                .diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.is_error())
+                .map(Diagnostic::display_message)
+                .collect::<Vec<_>>()
+                .join("\n"));
+        }
+        Ok(parsed.document_types)
+    }
 
-        let obj = json_value
-            .as_object()
-            .ok_or("Root level must be an object")?;
+    /// Parses a JSON string into document types, reporting every problem with its location
+    pub fn parse_with_diagnostics(json_str: &str) -> ParsedContract {
+        let json_value: Value = match serde_json::from_str(json_str) {
+            Ok(value) => value,
+            Err(e) => {
+                // serde_json appends the location, which the diagnostic already has
+                let message = e.to_string();
+                let message = message.split(" at line ").next().unwrap_or(&message);
+                let start = SourceLocations::offset(json_str, e.line(), e.column());
+                let (line, column) = SourceLocations::line_column(json_str, start);
+                return ParsedContract {
+                    document_types: Vec::new(),
+                    diagnostics: vec![Diagnostic {
+                        severity: Severity::Error,
+                        message: format!("Invalid JSON: {}", message),
+                        pointer: String::new(),
+                        line,
+                        column,
+                        start,
+                        end: start,
+                    }],
+                };
+            }
+        };
 
+        let mut diagnostics = Diagnostics {
+            text: json_str,
+            locations: SourceLocations::scan(json_str),
+            found: Vec::new(),
+        };
         let mut document_types = Vec::new();
 
-        for (name, doc_def) in obj {
-            let doc_type = Self::parse_document_type(name, doc_def)?;
-            document_types.push(doc_type);
+        match json_value.as_object() {
+            Some(obj) => {
+                for (name, doc_def) in obj {
+                    let pointer = SourceLocations::child("", name);
+                    if let Some(doc_type) =
+                        Self::parse_document_type(name, doc_def, &pointer, &mut diagnostics)
+                    {
+                        document_types.push(doc_type);
+                    }
+                }
+            }
+            None => diagnostics.error("", "Root level must be an object".to_string()),
         }
 
-        Ok(document_types)
+        ParsedContract {
+            document_types,
+            diagnostics: diagnostics.found,
+        }
     }
 
     /// Parses a single document type from JSON
-    fn parse_document_type(name: &str, doc_def: &Value) -> Result<DocumentType, String> {
-        let doc_obj = doc_def
-            .as_object()
-            .ok_or_else(|| format!("Document type '{}' must be an object", name))?;
+    fn parse_document_type(
+        name: &str,
+        doc_def: &Value,
+        pointer: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> Option<DocumentType> {
+        let Some(doc_obj) = doc_def.as_object() else {
+            diagnostics.error(
+                pointer,
+                format!("Document type '{}' must be an object", name),
+            );
+            return None;
+        };
 
         let mut doc_type = DocumentType::new(name.to_string());
+        Self::warn_dropped_keys(
+            doc_obj,
+            DOCUMENT_TYPE_KEYS,
+            "document types",
+            pointer,
+            diagnostics,
+        );
 
         // Parse properties
         if let Some(properties) = doc_obj.get("properties") {
-            doc_type.properties = Self::parse_properties(properties)?;
+            let pointer = SourceLocations::child(pointer, "properties");
+            doc_type.properties = Self::parse_properties(properties, &pointer, diagnostics);
         }
 
         // Parse indices
         if let Some(indices) = doc_obj.get("indices") {
-            doc_type.indices = Self::parse_indices(indices)?;
+            let pointer = SourceLocations::child(pointer, "indices");
+            doc_type.indices = Self::parse_indices(indices, &pointer, diagnostics);
         }
 
         // Parse required array
         if let Some(required) = doc_obj.get("required") {
-            doc_type.required = Self::parse_required_array(required)?;
+            let pointer = SourceLocations::child(pointer, "required");
+            doc_type.required = Self::parse_name_array(required, "Required", &pointer, diagnostics);
 
             // Check for system properties
             doc_type.created_at_required = doc_type.required.contains(&"$createdAt".to_string());
             doc_type.updated_at_required = doc_type.required.contains(&"$updatedAt".to_string());
         }
 
+        // Parse transient array
+        let mut transient = Vec::new();
+        if let Some(list) = doc_obj.get("transient") {
+            let pointer = SourceLocations::child(pointer, "transient");
+            transient = Self::parse_name_array(list, "Transient", &pointer, diagnostics);
+        }
+
         // Parse additionalProperties
         if let Some(additional_props) = doc_obj.get("additionalProperties") {
             doc_type.additionalProperties = additional_props.as_bool().unwrap_or(false);
@@ -76,19 +212,25 @@ impl JsonParser {
             doc_type.comment = comment.to_string();
         }
 
-        // Update required flags for properties
+        // Update required and transient flags for properties
         for property in &mut doc_type.properties {
             property.required = doc_type.required.contains(&property.name);
+            property.transient = transient.contains(&property.name);
         }
 
-        Ok(doc_type)
+        Some(doc_type)
     }
 
     /// Parses properties object
-    fn parse_properties(properties: &Value) -> Result<Vec<Property>, String> {
-        let props_obj = properties
-            .as_object()
-            .ok_or("Properties must be an object")?;
+    fn parse_properties(
+        properties: &Value,
+        pointer: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<Property> {
+        let Some(props_obj) = properties.as_object() else {
+            diagnostics.error(pointer, "Properties must be an object".to_string());
+            return Vec::new();
+        };
 
         let mut props = Vec::new();
 
@@ -98,51 +240,72 @@ impl JsonParser {
                 continue;
             }
 
-            let property = Self::parse_property(name, prop_def)?;
-            props.push(property);
+            let pointer = SourceLocations::child(pointer, name);
+            if let Some(property) = Self::parse_property(name, prop_def, &pointer, diagnostics) {
+                props.push(property);
+            }
         }
 
         // Sort by position
         props.sort_by_key(|p| p.position);
 
-        Ok(props)
+        props
     }
 
     /// Parses a single property from JSON
-    fn parse_property(name: &str, prop_def: &Value) -> Result<Property, String> {
-        let prop_obj = prop_def
-            .as_object()
-            .ok_or_else(|| format!("Property '{}' must be an object", name))?;
+    fn parse_property(
+        name: &str,
+        prop_def: &Value,
+        pointer: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> Option<Property> {
+        let Some(prop_obj) = prop_def.as_object() else {
+            diagnostics.error(pointer, format!("Property '{}' must be an object", name));
+            return None;
+        };
 
         let mut property = Property::new(name.to_string(), DataType::String);
 
         // Parse position
         if let Some(position) = prop_obj.get("position") {
-            property.position = position
-                .as_u64()
-                .ok_or_else(|| format!("Position for property '{}' must be a number", name))?;
-        }
-
-        // Parse type
-        if let Some(prop_type) = prop_obj.get("type") {
-            let type_str = prop_type
-                .as_str()
-                .ok_or_else(|| format!("Type for property '{}' must be a string", name))?;
-
-            property.data_type = match type_str {
-                "string" => DataType::String,
-                "integer" => DataType::Integer,
-                "number" => DataType::Number,
-                "array" => DataType::Array,
-                "object" => DataType::Object,
-                "boolean" => DataType::Boolean,
-                _ => {
-                    return Err(format!(
-                        "Unknown type '{}' for property '{}'",
+            match position.as_u64() {
+                Some(position) => property.position = position,
+                None => diagnostics.error(
+                    &SourceLocations::child(pointer, "position"),
+                    format!(
+                        "Position for property '{}' must be a non-negative integer",
+                        name
+                    ),
+                ),
+            }
+        }
+
+        // Parse type, importing unknown or missing types as strings
+        let type_pointer = SourceLocations::child(pointer, "type");
+        match prop_obj.get("type") {
+            Some(Value::String(type_str)) => match type_str.as_str() {
+                "string" => property.data_type = DataType::String,
+                "integer" => property.data_type = DataType::Integer,
+                "number" => property.data_type = DataType::Number,
+                "array" => property.data_type = DataType::Array,
+                "object" => property.data_type = DataType::Object,
+                "boolean" => property.data_type = DataType::Boolean,
+                _ => diagnostics.warning(
+                    &type_pointer,
+                    format!(
+                        "Unknown type '{}' for property '{}', imported as a string",
                         type_str, name
-                    ))
-                }
-            };
+                    ),
+                ),
+            },
+            Some(_) => diagnostics.error(
+                &type_pointer,
+                format!("Type for property '{}' must be a string", name),
+            ),
+            None => diagnostics.warning(
+                pointer,
+                format!("Property '{}' has no type, imported as a string", name),
+            ),
         }
 
         // Parse description
@@ -152,36 +315,70 @@ impl JsonParser {
             }
         }
 
+        // Parse comment
+        if let Some(comment) = prop_obj.get("$comment").and_then(|v| v.as_str()) {
+            property.comment = Some(comment.to_string());
+        }
+
+        // Parse allowed values
+        if let Some(enum_values) = prop_obj.get("enum").and_then(|v| v.as_array()) {
+            property.enum_values = Some(enum_values.clone());
+        }
+
+        let type_keys: &[&str] = match property.data_type {
+            DataType::String => &["minLength", "maxLength", "pattern", "format"],
+            DataType::Integer | DataType::Number => &["minimum", "maximum"],
+            DataType::Array => &[
+                "byteArray",
+                "items",
+                "minItems",
+                "maxItems",
+                "contentMediaType",
+            ],
+            DataType::Object => &[
+                "properties",
+                "required",
+                "minProperties",
+                "maxProperties",
+                "additionalProperties",
+            ],
+            DataType::Boolean => &[],
+        };
+        let supported = [PROPERTY_KEYS, type_keys].concat();
+        let context = format!("{} properties", property.data_type.as_str());
+        Self::warn_dropped_keys(prop_obj, &supported, &context, pointer, diagnostics);
+
         // Parse type-specific properties
         match property.data_type {
             DataType::String => {
-                Self::parse_string_properties(&mut property, prop_obj);
+                Self::parse_string_properties(&mut property, prop_obj, pointer, diagnostics);
             }
             DataType::Integer | DataType::Number => {
-                Self::parse_number_properties(&mut property, prop_obj);
+                Self::parse_number_properties(&mut property, prop_obj, pointer, diagnostics);
             }
             DataType::Array => {
-                Self::parse_array_properties(&mut property, prop_obj);
+                Self::parse_array_properties(&mut property, prop_obj, pointer, diagnostics);
             }
             DataType::Object => {
-                Self::parse_object_properties(&mut property, prop_obj)?;
+                Self::parse_object_properties(&mut property, prop_obj, pointer, diagnostics);
             }
             DataType::Boolean => {
                 // Boolean has no additional properties
             }
         }
 
-        Ok(property)
+        Some(property)
     }
 
     /// Parses string-specific properties
-    fn parse_string_properties(property: &mut Property, prop_obj: &serde_json::Map<String, Value>) {
-        if let Some(min_length) = prop_obj.get("minLength").and_then(|v| v.as_u64()) {
-            property.min_length = Some(min_length as u32);
-        }
-        if let Some(max_length) = prop_obj.get("maxLength").and_then(|v| v.as_u64()) {
-            property.max_length = Some(max_length as u32);
-        }
+    fn parse_string_properties(
+        property: &mut Property,
+        prop_obj: &Map<String, Value>,
+        pointer: &str,
+        diagnostics: &mut Diagnostics,
+    ) {
+        property.min_length = Self::parse_count(prop_obj, "minLength", pointer, diagnostics);
+        property.max_length = Self::parse_count(prop_obj, "maxLength", pointer, diagnostics);
         if let Some(pattern) = prop_obj.get("pattern").and_then(|v| v.as_str()) {
             property.pattern = Some(pattern.to_string());
         }
@@ -191,61 +388,95 @@ impl JsonParser {
     }
 
     /// Parses number/integer-specific properties
-    fn parse_number_properties(property: &mut Property, prop_obj: &serde_json::Map<String, Value>) {
-        if let Some(minimum) = prop_obj.get("minimum").and_then(|v| v.as_i64()) {
-            property.minimum = Some(minimum as i32);
-        }
-        if let Some(maximum) = prop_obj.get("maximum").and_then(|v| v.as_i64()) {
-            property.maximum = Some(maximum as i32);
-        }
+    fn parse_number_properties(
+        property: &mut Property,
+        prop_obj: &Map<String, Value>,
+        pointer: &str,
+        diagnostics: &mut Diagnostics,
+    ) {
+        property.minimum = Self::parse_bound(prop_obj, "minimum", pointer, diagnostics);
+        property.maximum = Self::parse_bound(prop_obj, "maximum", pointer, diagnostics);
     }
 
     /// Parses array-specific properties
-    fn parse_array_properties(property: &mut Property, prop_obj: &serde_json::Map<String, Value>) {
-        if let Some(byte_array) = prop_obj.get("byteArray").and_then(|v| v.as_bool()) {
-            property.byte_array = Some(byte_array);
-        }
-        if let Some(min_items) = prop_obj.get("minItems").and_then(|v| v.as_u64()) {
-            property.min_items = Some(min_items as u32);
-        }
-        if let Some(max_items) = prop_obj.get("maxItems").and_then(|v| v.as_u64()) {
-            property.max_items = Some(max_items as u32);
+    fn parse_array_properties(
+        property: &mut Property,
+        prop_obj: &Map<String, Value>,
+        pointer: &str,
+        diagnostics: &mut Diagnostics,
+    ) {
+        let byte_array = prop_obj.get("byteArray").and_then(|v| v.as_bool());
+        match prop_obj.get("items") {
+            // Arrays without an items schema hold bytes
+            Some(items) if byte_array != Some(true) => {
+                let items_pointer = SourceLocations::child(pointer, "items");
+                let items_name = format!("{} items", property.name);
+                property.items =
+                    Self::parse_property(&items_name, items, &items_pointer, diagnostics).map(
+                        |items| {
+                            Box::new(Property {
+                                name: String::new(),
+                                position: 0,
+                                ..items
+                            })
+                        },
+                    );
+            }
+            Some(_) => {
+                diagnostics.warning(
+                    &SourceLocations::child(pointer, "items"),
+                    "'items' is not supported for byte arrays and will be dropped on export"
+                        .to_string(),
+                );
+                property.byte_array = byte_array;
+            }
+            None => property.byte_array = byte_array,
         }
+        property.min_items = Self::parse_count(prop_obj, "minItems", pointer, diagnostics);
+        property.max_items = Self::parse_count(prop_obj, "maxItems", pointer, diagnostics);
         if let Some(content_media_type) = prop_obj.get("contentMediaType").and_then(|v| v.as_str())
         {
-            property.content_media_type = Some(content_media_type.to_string());
+            if property.items.is_none() {
+                property.content_media_type = Some(content_media_type.to_string());
+            } else {
+                diagnostics.warning(
+                    &SourceLocations::child(pointer, "contentMediaType"),
+                    "'contentMediaType' is only supported for byte arrays and will be dropped on export"
+                        .to_string(),
+                );
+            }
         }
     }
 
     /// Parses object-specific properties
     fn parse_object_properties(
         property: &mut Property,
-        prop_obj: &serde_json::Map<String, Value>,
-    ) -> Result<(), String> {
+        prop_obj: &Map<String, Value>,
+        pointer: &str,
+        diagnostics: &mut Diagnostics,
+    ) {
         if let Some(nested_props) = prop_obj.get("properties") {
-            let mut nested_properties = Self::parse_properties(nested_props)?;
-            
+            let nested_pointer = SourceLocations::child(pointer, "properties");
+            let mut nested_properties =
+                Self::parse_properties(nested_props, &nested_pointer, diagnostics);
+
             // Update required flags for nested properties if there's a required array
             if let Some(required) = prop_obj.get("required") {
-                let required_list = Self::parse_required_array(required)?;
+                let required_pointer = SourceLocations::child(pointer, "required");
+                let required_list =
+                    Self::parse_name_array(required, "Required", &required_pointer, diagnostics);
                 for nested_prop in &mut nested_properties {
                     nested_prop.required = required_list.contains(&nested_prop.name);
                 }
             }
-            
-            property.properties = Some(Box::new(nested_properties));
-        }
 
-        if let Some(min_properties) = prop_obj.get("minProperties").and_then(|v| v.as_u64()) {
-            property.min_properties = Some(min_properties as u32);
-        }
-        if let Some(max_properties) = prop_obj.get("maxProperties").and_then(|v| v.as_u64()) {
-            property.max_properties = Some(max_properties as u32);
+            property.properties = Some(Box::new(nested_properties));
         }
 
-        if let Some(required) = prop_obj.get("required") {
-            property.rec_required = Some(Self::parse_required_array(required)?);
-        }
+        property.min_properties =
+            Self::parse_count(prop_obj, "minProperties", pointer, diagnostics);
+        property.max_properties =
+            Self::parse_count(prop_obj, "maxProperties", pointer, diagnostics);
 
         if let Some(additional_properties) = prop_obj
             .get("additionalProperties")
@@ -253,41 +484,117 @@ impl JsonParser {
         {
             property.additional_properties = Some(additional_properties);
         }
-
-        Ok(())
     }
 
-    /// Parses indices array
-    fn parse_indices(indices: &Value) -> Result<Vec<Index>, String> {
-        let indices_array = indices.as_array().ok_or("Indices must be an array")?;
+    /// Warns about each key the form does not keep, which exporting would delete
+    fn warn_dropped_keys(
+        obj: &Map<String, Value>,
+        supported: &[&str],
+        context: &str,
+        pointer: &str,
+        diagnostics: &mut Diagnostics,
+    ) {
+        for key in obj.keys().filter(|key| !supported.contains(&key.as_str())) {
+            diagnostics.warning(
+                &SourceLocations::child(pointer, key),
+                format!(
+                    "'{}' is not supported for {} and will be dropped on export",
+                    key, context
+                ),
+            );
+        }
+    }
 
-        let mut parsed_indices = Vec::new();
+    /// Parses a length or count limit, warning about values the form cannot hold
+    fn parse_count(
+        prop_obj: &Map<String, Value>,
+        key: &str,
+        pointer: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> Option<u32> {
+        let value = prop_obj.get(key)?;
+        let pointer = SourceLocations::child(pointer, key);
+
+        let Some(count) = value.as_u64() else {
+            diagnostics.warning(
+                &pointer,
+                format!("{} must be a non-negative integer, ignored", key),
+            );
+            return None;
+        };
+        match u32::try_from(count) {
+            Ok(count) => Some(count),
+            Err(_) => {
+                diagnostics.warning(
+                    &pointer,
+                    format!("{} {} is larger than {}, ignored", key, count, u32::MAX),
+                );
+                None
+            }
+        }
+    }
 
-        for (i, index_def) in indices_array.iter().enumerate() {
-            let index = Self::parse_index(index_def)
-                .map_err(|e| format!("Error parsing index {}: {}", i, e))?;
-            parsed_indices.push(index);
+    /// Parses a minimum or maximum, kept exactly as written
+    fn parse_bound(
+        prop_obj: &Map<String, Value>,
+        key: &str,
+        pointer: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> Option<serde_json::Number> {
+        match prop_obj.get(key)? {
+            Value::Number(bound) => Some(bound.clone()),
+            _ => {
+                diagnostics.warning(
+                    &SourceLocations::child(pointer, key),
+                    format!("{} must be a number, ignored", key),
+                );
+                None
+            }
         }
+    }
 
-        Ok(parsed_indices)
+    /// Parses indices array
+    fn parse_indices(indices: &Value, pointer: &str, diagnostics: &mut Diagnostics) -> Vec<Index> {
+        let Some(indices_array) = indices.as_array() else {
+            diagnostics.error(pointer, "Indices must be an array".to_string());
+            return Vec::new();
+        };
+
+        indices_array
+            .iter()
+            .enumerate()
+            .filter_map(|(i, index_def)| {
+                let pointer = SourceLocations::child(pointer, &i.to_string());
+                Self::parse_index(index_def, &pointer, diagnostics)
+            })
+            .collect()
     }
 
     /// Parses a single index from JSON
-    fn parse_index(index_def: &Value) -> Result<Index, String> {
-        let index_obj = index_def.as_object().ok_or("Index must be an object")?;
+    fn parse_index(
+        index_def: &Value,
+        pointer: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> Option<Index> {
+        let Some(index_obj) = index_def.as_object() else {
+            diagnostics.error(pointer, "Index must be an object".to_string());
+            return None;
+        };
 
         let mut index = Index::default();
+        Self::warn_dropped_keys(index_obj, INDEX_KEYS, "indices", pointer, diagnostics);
 
         // Parse name
         if let Some(name) = index_obj.get("name").and_then(|v| v.as_str()) {
             index.name = name.to_string();
         } else {
-            return Err("Index must have a name".to_string());
+            diagnostics.error(pointer, "Index must have a name".to_string());
         }
 
         // Parse properties
         if let Some(properties) = index_obj.get("properties") {
-            index.properties = Self::parse_index_properties(properties)?;
+            let pointer = SourceLocations::child(pointer, "properties");
+            index.properties = Self::parse_index_properties(properties, &pointer, diagnostics);
         }
 
         // Parse unique flag
@@ -295,53 +602,78 @@ impl JsonParser {
             index.unique = unique;
         }
 
-        Ok(index)
+        Some(index)
     }
 
     /// Parses index properties array
-    fn parse_index_properties(properties: &Value) -> Result<Vec<IndexProperties>, String> {
-        let props_array = properties
-            .as_array()
-            .ok_or("Index properties must be an array")?;
+    fn parse_index_properties(
+        properties: &Value,
+        pointer: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<IndexProperties> {
+        let Some(props_array) = properties.as_array() else {
+            diagnostics.error(pointer, "Index properties must be an array".to_string());
+            return Vec::new();
+        };
 
         let mut index_props = Vec::new();
 
-        for prop_def in props_array {
-            let prop_obj = prop_def
-                .as_object()
-                .ok_or("Index property must be an object")?;
+        for (i, prop_def) in props_array.iter().enumerate() {
+            let pointer = SourceLocations::child(pointer, &i.to_string());
+            let Some(prop_obj) = prop_def.as_object() else {
+                diagnostics.error(&pointer, "Index property must be an object".to_string());
+                continue;
+            };
 
             // Each object should have exactly one key-value pair
-            if prop_obj.len() != 1 {
-                return Err(
-                    "Index property object must have exactly one key-value pair".to_string()
+            let (field_name, order_value) = match prop_obj.iter().next() {
+                Some(pair) if prop_obj.len() == 1 => pair,
+                _ => {
+                    diagnostics.error(
+                        &pointer,
+                        "Index property object must have exactly one key-value pair".to_string(),
+                    );
+                    continue;
+                }
+            };
+            let Some(order) = order_value.as_str() else {
+                diagnostics.error(
+                    &SourceLocations::child(&pointer, field_name),
+                    "Index property order must be a string".to_string(),
                 );
-            }
-
-            let (field_name, order_value) = prop_obj.iter().next().unwrap();
-            let order = order_value
-                .as_str()
-                .ok_or("Index property order must be a string")?;
+                continue;
+            };
 
             index_props.push(IndexProperties::new(field_name.clone(), order.to_string()));
         }
 
-        Ok(index_props)
+        index_props
     }
 
-    /// Parses required array
-    fn parse_required_array(required: &Value) -> Result<Vec<String>, String> {
-        let required_array = required.as_array().ok_or("Required must be an array")?;
+    /// Parses an array of property names, such as `required` or `transient`
+    fn parse_name_array(
+        names: &Value,
+        label: &str,
+        pointer: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<String> {
+        let Some(names_array) = names.as_array() else {
+            diagnostics.error(pointer, format!("{} must be an array", label));
+            return Vec::new();
+        };
 
-        let mut required_props = Vec::new();
+        let mut props = Vec::new();
 
-        for req_def in required_array {
-            let prop_name = req_def
-                .as_str()
-                .ok_or("Required property name must be a string")?;
-            required_props.push(prop_name.to_string());
+        for (i, name_def) in names_array.iter().enumerate() {
+            match name_def.as_str() {
+                Some(prop_name) => props.push(prop_name.to_string()),
+                None => diagnostics.error(
+                    &SourceLocations::child(pointer, &i.to_string()),
+                    format!("{} property name must be a string", label),
+                ),
+            }
         }
 
-        Ok(required_props)
+        props
     }
 }
@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod tests {
+    use super::super::json_parser::JsonParser;
+    use super::super::source_locations::SourceLocations;
+    use crate::types::{DataType, Severity};
+
+    const BROKEN: &str = r#"{
+  "note": {
+    "type": "object",
+    "properties": {
+      "message": {"type": "text", "position": 0, "maxLength": 4294967296},
+      "author": {"type": "string", "position": "first"}
+    },
+    "indices": [
+      "byMessage",
+      {"properties": [{"message": "asc"}]}
+    ]
+  }
+}"#;
+
+    #[test]
+    fn test_reports_every_problem_with_location() {
+        let parsed = JsonParser::parse_with_diagnostics(BROKEN);
+        let found: Vec<(Severity, &str, usize, usize)> = parsed
+            .diagnostics
+            .iter()
+            .map(|d| (d.severity, d.pointer.as_str(), d.line, d.column))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                (Severity::Warning, "/note/properties/message/type", 5, 27),
+                (
+                    Severity::Warning,
+                    "/note/properties/message/maxLength",
+                    5,
+                    63
+                ),
+                (Severity::Error, "/note/properties/author/position", 6, 48),
+                (Severity::Error, "/note/indices/0", 9, 7),
+                (Severity::Error, "/note/indices/1", 10, 7),
+            ]
+        );
+        assert!(parsed.has_errors());
+
+        // The span covers the value, so the import box can underline it
+        let diagnostic = &parsed.diagnostics[2];
+        assert_eq!(&BROKEN[diagnostic.start..diagnostic.end], "\"first\"");
+
+        let error = JsonParser::parse_contract(BROKEN).unwrap_err();
+        assert_eq!(error.lines().count(), 3);
+        assert!(error.starts_with("Line 6, column 48 (/note/properties/author/position):"));
+    }
+
+    #[test]
+    fn test_warnings_still_import() {
+        let json = r#"{"note": {"properties": {
+            "message": {"type": "text", "position": 0},
+            "count": {"type": "integer", "position": 1, "minimum": "0", "maximum": 12.5}
+        }}}"#;
+
+        let parsed = JsonParser::parse_with_diagnostics(json);
+        assert!(!parsed.has_errors());
+        assert_eq!(parsed.diagnostics.len(), 2);
+        assert!(parsed.diagnostics[0]
+            .message
+            .contains("Unknown type 'text'"));
+
+        let document_types = JsonParser::parse_contract(json).unwrap();
+        let properties = &document_types[0].properties;
+        assert_eq!(properties[0].data_type, DataType::String);
+        assert_eq!(properties[1].minimum, None);
+        assert_eq!(properties[1].maximum.as_ref().unwrap().as_f64(), Some(12.5));
+    }
+
+    #[test]
+    fn test_dropped_keys_reported() {
+        let json = r#"{"note": {
+            "type": "object", "documentsMutable": false, "$defs": {},
+            "properties": {
+                "body": {"type": "string", "position": 0, "contentEncoding": "base64", "minimum": 1},
+                "hash": {"type": "array", "position": 1, "byteArray": true, "items": {"type": "integer"}}
+            },
+            "indices": [{"name": "byBody", "properties": [{"body": "asc"}], "nullSearchable": false}],
+            "additionalProperties": false
+        }}"#;
+
+        let parsed = JsonParser::parse_with_diagnostics(json);
+        assert!(!parsed.has_errors());
+        let dropped: Vec<(Severity, &str)> = parsed
+            .diagnostics
+            .iter()
+            .map(|d| (d.severity, d.pointer.as_str()))
+            .collect();
+        assert_eq!(
+            dropped,
+            vec![
+                (Severity::Warning, "/note/documentsMutable"),
+                (Severity::Warning, "/note/$defs"),
+                (Severity::Warning, "/note/properties/body/contentEncoding"),
+                (Severity::Warning, "/note/properties/body/minimum"),
+                (Severity::Warning, "/note/properties/hash/items"),
+                (Severity::Warning, "/note/indices/0/nullSearchable"),
+            ]
+        );
+        assert_eq!(
+            parsed.diagnostics[0].message,
+            "'documentsMutable' is not supported for document types and will be dropped on export"
+        );
+        assert!(parsed.diagnostics[3]
+            .message
+            .contains("not supported for string properties"));
+    }
+
+    #[test]
+    fn test_invalid_json_location() {
+        let json = "{\n  \"note\": {\n    \"type\": \"object\",\n  }\n}";
+        let parsed = JsonParser::parse_with_diagnostics(json);
+
+        assert_eq!(parsed.diagnostics.len(), 1);
+        let diagnostic = &parsed.diagnostics[0];
+        assert_eq!((diagnostic.line, diagnostic.column), (4, 3));
+        assert!(diagnostic
+            .message
+            .starts_with("Invalid JSON: trailing comma"));
+    }
+
+    #[test]
+    fn test_source_locations() {
+        let text = r#"{"a/b": [1, {"c~": "é"}], "d": "x"}"#;
+        let locations = SourceLocations::scan(text);
+
+        let (start, end) = locations.find("/a~1b/1/c~0");
+        assert_eq!(&text[start..end], "\"é\"");
+        assert_eq!(SourceLocations::line_column(text, end), (1, 23));
+        // Missing values fall back to the nearest enclosing value
+        let (start, end) = locations.find("/d/missing");
+        assert_eq!(&text[start..end], "\"x\"");
+    }
+}
@@ -1,13 +1,95 @@
+pub mod ai_transport;
+pub mod canonical;
+pub mod contract_comparison;
+pub mod contract_diff;
+pub mod contract_fingerprint;
+pub mod contract_import;
+pub mod contract_merge;
+pub mod description_suggestions;
+pub mod diagram;
+pub mod docs_generator;
+pub mod gallery;
+pub mod json_extractor;
 pub mod json_generator;
 pub mod json_parser;
+pub mod offline_generator;
 /// Services for external interactions and business logic
 pub mod openai;
+pub mod prompt_library;
+pub mod reply_schema;
+pub mod rust_generator;
+pub mod schema_import;
+pub mod scoped_edit;
+pub mod source_locations;
+pub mod typescript_generator;
 pub mod validation;
 
+#[cfg(test)]
+mod canonical_test;
+#[cfg(test)]
+mod contract_comparison_test;
+#[cfg(test)]
+mod contract_diff_test;
+#[cfg(test)]
+mod contract_fingerprint_test;
+#[cfg(test)]
+mod contract_import_test;
+#[cfg(test)]
+mod contract_merge_test;
+#[cfg(test)]
+mod description_suggestions_test;
+#[cfg(test)]
+mod diagram_test;
+#[cfg(test)]
+mod docs_generator_test;
+#[cfg(test)]
+mod gallery_test;
+#[cfg(test)]
+mod json_extractor_test;
+#[cfg(test)]
+mod json_generator_test;
+#[cfg(test)]
+mod json_parser_test;
+#[cfg(test)]
+mod offline_generator_test;
+#[cfg(test)]
+mod openai_test;
+#[cfg(test)]
+mod prompt_library_test;
+#[cfg(test)]
+mod rust_generator_test;
+#[cfg(test)]
+mod schema_import_test;
+#[cfg(test)]
+mod scoped_edit_test;
+#[cfg(test)]
+mod typescript_generator_test;
 #[cfg(test)]
 mod validation_test;
 
+pub use ai_transport::{
+    AiTransport, FetchTransport, FixtureTransport, RecordingTransport, TransportResponse,
+};
+pub use canonical::CanonicalForm;
+pub use contract_comparison::{ComparisonRow, ContractComparison};
+pub use contract_diff::{ChangeKind, ChangeTarget, ContractChange, ContractDiffService};
+pub use contract_fingerprint::ContractFingerprint;
+pub use contract_import::{ContractImport, ImportedContract, RelaxedSyntax};
+pub use contract_merge::{ContractMerge, MergeConflict, MergeSide};
+pub use description_suggestions::{DescribedField, DescriptionService, DescriptionSuggestion};
+pub use diagram::{DiagramFormat, DiagramService};
+pub use docs_generator::DocsGenerator;
+pub use gallery::{GalleryService, StarterContract};
+pub use json_extractor::JsonExtractor;
 pub use json_generator::JsonGenerator;
-pub use json_parser::JsonParser;
-pub use openai::OpenAiService;
+pub use json_parser::{JsonParser, ParsedContract};
+pub use offline_generator::OfflineGenerator;
+pub use openai::{AiRequestHandle, AiResponseError, OpenAiService, StreamEvent};
+pub use prompt_library::PromptLibrary;
+pub use reply_schema::ReplyShape;
+pub use rust_generator::RustGenerator;
+pub use schema_import::{AdaptedSchema, SchemaImport, SchemaSource};
+pub use scoped_edit::{EditScope, ScopedEditService};
+pub use source_locations::SourceLocations;
+pub use typescript_generator::TypeScriptGenerator;
 pub use validation::ValidationService;
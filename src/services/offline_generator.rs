@@ -0,0 +1,277 @@
+use crate::types::{DataType, DocumentType, Index, Property};
+use crate::utils::{to_pascal_case, IDENTIFIER_MEDIA_TYPE, MAX_INDEXED_STRING_LENGTH};
+
+/// Kind of field a type hint or field name maps to
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldKind {
+    Text,
+    LongText,
+    Email,
+    Url,
+    Amount,
+    Number,
+    Boolean,
+    Timestamp,
+    Hash,
+    Reference,
+}
+
+/// Type hints accepted after a field name, such as `price:amount`
+const TYPE_HINTS: &[(&[&str], FieldKind)] = &[
+    (&["string", "name", "title", "short"], FieldKind::Text),
+    (&["text", "long", "body", "content"], FieldKind::LongText),
+    (&["email"], FieldKind::Email),
+    (&["url", "uri", "link"], FieldKind::Url),
+    (
+        &["amount", "price", "count", "quantity", "integer", "int"],
+        FieldKind::Amount,
+    ),
+    (&["number", "float", "decimal"], FieldKind::Number),
+    (&["bool", "boolean", "flag"], FieldKind::Boolean),
+    (
+        &["date", "datetime", "time", "timestamp"],
+        FieldKind::Timestamp,
+    ),
+    (&["hash", "bytes"], FieldKind::Hash),
+    (&["ref", "id", "identifier"], FieldKind::Reference),
+];
+
+/// A field as written in the description
+struct FieldSpec {
+    name: String,
+    hint: Option<String>,
+    required: bool,
+}
+
+/// Service for building a starter contract from a structured description, without AI
+///
+/// The description lists one entity per line or `;`-separated segment, such as
+/// `post: title!, body:text, author:ownerRef; comment: post:ref, body`.
+/// A trailing `!` marks a field as required.
+pub struct OfflineGenerator;
+
+impl OfflineGenerator {
+    /// Builds document types from a structured description
+    ///
+    /// References to listed entities are linked through `ref_document_type`, which
+    /// contract JSON cannot hold, so callers should use the document types directly.
+    pub fn document_types(description: &str) -> Result<Vec<DocumentType>, String> {
+        let entities = Self::parse(description)?;
+        let entity_names: Vec<&str> = entities.iter().map(|(name, _)| name.as_str()).collect();
+
+        Ok(entities
+            .iter()
+            .map(|(name, fields)| Self::document_type(name, fields, &entity_names))
+            .collect())
+    }
+
+    /// Splits the description into entity names and their fields
+    fn parse(description: &str) -> Result<Vec<(String, Vec<FieldSpec>)>, String> {
+        let mut entities: Vec<(String, Vec<FieldSpec>)> = Vec::new();
+
+        for segment in description.split(['\n', ';']) {
+            let segment = segment.trim().trim_start_matches(['-', '*']).trim();
+            if segment.is_empty() {
+                continue;
+            }
+            let (name, fields) = segment.split_once(':').ok_or_else(|| {
+                format!("Expected `entity: field, field` but found \"{}\"", segment)
+            })?;
+            let name = name.trim();
+            if !Self::is_valid_name(name) {
+                return Err(format!("\"{}\" is not a valid document type name", name));
+            }
+            if entities.iter().any(|(existing, _)| existing == name) {
+                return Err(format!("Document type \"{}\" is listed twice", name));
+            }
+
+            let mut specs: Vec<FieldSpec> = Vec::new();
+            for field in fields.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+                let (field, required) = match field.strip_suffix('!') {
+                    Some(field) => (field.trim(), true),
+                    None => (field, false),
+                };
+                let (field_name, hint) = match field.split_once(':') {
+                    Some((field_name, hint)) => (field_name.trim(), Some(hint.trim().to_string())),
+                    None => (field, None),
+                };
+                if !Self::is_valid_name(field_name) {
+                    return Err(format!(
+                        "\"{}\" in {} is not a valid field name",
+                        field_name, name
+                    ));
+                }
+                if let Some(hint) = hint.as_deref().filter(|h| Self::hint_kind(h).is_none()) {
+                    return Err(format!(
+                        "Unknown type hint \"{}\" for {}.{}",
+                        hint, name, field_name
+                    ));
+                }
+                if specs.iter().any(|spec| spec.name == field_name) {
+                    return Err(format!(
+                        "Field \"{}\" is listed twice in {}",
+                        field_name, name
+                    ));
+                }
+                specs.push(FieldSpec {
+                    name: field_name.to_string(),
+                    hint,
+                    required,
+                });
+            }
+            if specs.is_empty() {
+                return Err(format!("Document type \"{}\" has no fields", name));
+            }
+
+            entities.push((name.to_string(), specs));
+        }
+
+        if entities.is_empty() {
+            return Err(
+                "Describe at least one entity, such as `post: title, body:text`".to_string(),
+            );
+        }
+        Ok(entities)
+    }
+
+    fn document_type(name: &str, fields: &[FieldSpec], entity_names: &[&str]) -> DocumentType {
+        let mut doc = DocumentType::new(name.to_string());
+        doc.created_at_required = true;
+
+        for (position, spec) in fields.iter().enumerate() {
+            let kind = Self::kind(spec);
+            let mut prop = Self::property(&spec.name, kind);
+            prop.position = position as u64;
+            prop.required = spec.required;
+
+            if kind == FieldKind::Reference {
+                prop.ref_document_type = Self::reference_target(spec, entity_names);
+
+                let mut index = Index::new(format!("by{}", to_pascal_case(&spec.name)));
+                index.add_property(spec.name.clone());
+                doc.add_index(index);
+            }
+            doc.add_property(prop);
+        }
+
+        doc.update_required_properties();
+        doc
+    }
+
+    /// Returns the kind a type hint stands for
+    fn hint_kind(hint: &str) -> Option<FieldKind> {
+        let lower = hint.to_lowercase();
+        if let Some((_, kind)) = TYPE_HINTS
+            .iter()
+            .find(|(names, _)| names.contains(&lower.as_str()))
+        {
+            return Some(*kind);
+        }
+        // Hints such as `ownerRef` or `postId` name what is referenced
+        (hint.ends_with("Ref") || hint.ends_with("Id")).then_some(FieldKind::Reference)
+    }
+
+    /// Returns the kind from the type hint, or guesses it from the field name
+    fn kind(spec: &FieldSpec) -> FieldKind {
+        if let Some(kind) = spec.hint.as_deref().and_then(Self::hint_kind) {
+            return kind;
+        }
+
+        let name = spec.name.as_str();
+        let lower = name.to_lowercase();
+        if name.ends_with("Id") || name.ends_with("Ref") {
+            FieldKind::Reference
+        } else if lower.ends_with("email") {
+            FieldKind::Email
+        } else if lower.ends_with("url") || lower.ends_with("uri") {
+            FieldKind::Url
+        } else if ["amount", "price", "count", "quantity", "balance", "total"]
+            .iter()
+            .any(|suffix| lower.ends_with(suffix))
+        {
+            FieldKind::Amount
+        } else if name.ends_with("At") || lower.ends_with("date") {
+            FieldKind::Timestamp
+        } else if lower.ends_with("hash") {
+            FieldKind::Hash
+        } else if name.starts_with("is") && name.chars().nth(2).is_some_and(char::is_uppercase) {
+            FieldKind::Boolean
+        } else if ["description", "body", "content", "bio", "text"].contains(&lower.as_str()) {
+            FieldKind::LongText
+        } else {
+            FieldKind::Text
+        }
+    }
+
+    /// Creates a property with the defaults for its kind
+    fn property(name: &str, kind: FieldKind) -> Property {
+        let data_type = match kind {
+            FieldKind::Text | FieldKind::LongText | FieldKind::Email | FieldKind::Url => {
+                DataType::String
+            }
+            FieldKind::Amount | FieldKind::Timestamp => DataType::Integer,
+            FieldKind::Number => DataType::Number,
+            FieldKind::Boolean => DataType::Boolean,
+            FieldKind::Hash | FieldKind::Reference => DataType::Array,
+        };
+        let mut prop = Property::new(name.to_string(), data_type);
+
+        match kind {
+            FieldKind::Text => prop.max_length = Some(MAX_INDEXED_STRING_LENGTH),
+            FieldKind::LongText => prop.max_length = Some(2048),
+            FieldKind::Email => {
+                prop.max_length = Some(254);
+                prop.format = Some("email".to_string());
+            }
+            FieldKind::Url => {
+                prop.max_length = Some(2048);
+                prop.format = Some("uri".to_string());
+            }
+            FieldKind::Amount | FieldKind::Timestamp | FieldKind::Number => {
+                prop.minimum = Some(0.into())
+            }
+            FieldKind::Boolean => {}
+            FieldKind::Hash | FieldKind::Reference => {
+                prop.byte_array = Some(true);
+                prop.min_items = Some(32);
+                prop.max_items = Some(32);
+                if kind == FieldKind::Reference {
+                    prop.content_media_type = Some(IDENTIFIER_MEDIA_TYPE.to_string());
+                }
+            }
+        }
+
+        prop
+    }
+
+    /// Finds the entity a reference points to, from its hint or its field name
+    fn reference_target(spec: &FieldSpec, entity_names: &[&str]) -> Option<String> {
+        let strip = |word: &str| -> String {
+            let lower = word.to_lowercase();
+            ["ref", "id", "identifier"]
+                .iter()
+                .find_map(|suffix| lower.strip_suffix(suffix))
+                .unwrap_or(&lower)
+                .to_string()
+        };
+
+        [spec.hint.as_deref(), Some(spec.name.as_str())]
+            .into_iter()
+            .flatten()
+            .map(strip)
+            .filter(|target| !target.is_empty())
+            .find_map(|target| {
+                entity_names
+                    .iter()
+                    .find(|entity| entity.to_lowercase() == target)
+                    .map(|entity| entity.to_string())
+            })
+    }
+
+    fn is_valid_name(name: &str) -> bool {
+        name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use super::super::contract_diff::ContractDiffService;
+    use super::super::diagram::DiagramService;
+    use super::super::offline_generator::OfflineGenerator;
+    use crate::types::DataType;
+
+    #[test]
+    fn test_generates_defaults_from_hints_and_names() {
+        let document_types = OfflineGenerator::document_types(
+            "post: title!, body:text, author:ownerRef, url; comment: post:ref, authorEmail, likeCount",
+        )
+        .unwrap();
+
+        let post = &document_types[0];
+        assert!(post.created_at_required);
+        assert!(post.properties[0].required);
+        assert_eq!(post.properties[0].max_length, Some(63));
+        assert_eq!(post.properties[1].max_length, Some(2048));
+        assert!(post.properties[2].is_identifier());
+        // `owner` is not a listed entity, so nothing is linked
+        assert_eq!(post.properties[2].ref_document_type, None);
+        assert_eq!(post.properties[3].format.as_deref(), Some("uri"));
+        assert_eq!(post.indices.len(), 1);
+        assert_eq!(post.indices[0].name, "byAuthor");
+        assert_eq!(post.indices[0].properties[0].field(), "author");
+
+        let comment = &document_types[1];
+        assert!(comment.properties[0].is_identifier());
+        assert_eq!(
+            comment.properties[0].ref_document_type.as_deref(),
+            Some("post")
+        );
+        assert_eq!(comment.indices.len(), 1);
+        assert_eq!(comment.indices[0].name, "byPost");
+        assert_eq!(comment.indices[0].properties[0].field(), "post");
+        assert_eq!(comment.properties[1].format.as_deref(), Some("email"));
+        assert_eq!(comment.properties[2].data_type, DataType::Integer);
+        assert_eq!(comment.properties[2].minimum, Some(0.into()));
+    }
+
+    #[test]
+    fn test_links_references_to_listed_entities() {
+        let document_types =
+            OfflineGenerator::document_types("user: name\nfollow: follower:userRef, targetId")
+                .unwrap();
+        let follow = &document_types[1];
+
+        assert_eq!(
+            follow.properties[0].ref_document_type.as_deref(),
+            Some("user")
+        );
+        // `target` is not a listed entity, so it stays a plain identifier
+        assert_eq!(follow.properties[1].ref_document_type, None);
+        let indices: Vec<(&str, &str)> = follow
+            .indices
+            .iter()
+            .map(|index| (index.name.as_str(), index.properties[0].field()))
+            .collect();
+        assert_eq!(
+            indices,
+            vec![("byFollower", "follower"), ("byTargetId", "targetId")]
+        );
+    }
+
+    #[test]
+    fn test_reports_malformed_descriptions() {
+        assert!(OfflineGenerator::document_types("").is_err());
+        assert!(OfflineGenerator::document_types("a blog with posts").is_err());
+        assert!(OfflineGenerator::document_types("post: title:colour").is_err());
+        assert!(OfflineGenerator::document_types("post: title, title").is_err());
+        // Only a camel-case `Id` or `Ref` suffix makes a hint a reference
+        for hint in ["paid", "valid", "grid", "pref", "ownerref"] {
+            let description = format!("order: status:{}", hint);
+            assert!(
+                OfflineGenerator::document_types(&description).is_err(),
+                "{}",
+                hint
+            );
+        }
+    }
+
+    #[test]
+    fn test_references_survive_review() {
+        // The form already has a `user` type, so the generated contract is reviewed
+        let current = OfflineGenerator::document_types("user: name").unwrap();
+        let proposed =
+            OfflineGenerator::document_types("user: name\nfollow: follower:userRef").unwrap();
+        let changes = ContractDiffService::diff(&current, &proposed);
+        let accepted = vec![true; changes.len()];
+        let applied = ContractDiffService::apply(&current, &proposed, &changes, &accepted);
+
+        let follow = applied.iter().find(|doc| doc.name == "follow").unwrap();
+        assert_eq!(
+            follow.properties[0].ref_document_type.as_deref(),
+            Some("user")
+        );
+        // `follower` names no document type, so the edge comes from the declared reference
+        let edges = DiagramService::edges(&applied);
+        assert_eq!(edges.len(), 1);
+        assert_eq!((edges[0].to.as_str(), edges[0].inferred), ("user", false));
+    }
+}
@@ -1,16 +1,89 @@
 use anyhow::{anyhow, Result};
 use serde_json::json;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use wasm_bindgen::prelude::*;
-use wasm_bindgen_futures::JsFuture;
-use web_sys::{Request, RequestInit, RequestMode, Response};
+use web_sys::AbortController;
+
+use crate::services::{AiTransport, FetchTransport, JsonExtractor, RecordingTransport, ReplyShape};
+use crate::types::{ChatMessage, ConversationTurn, PromptPart, PromptTemplate, ValidationError};
+use crate::utils::{
+    OPENAI_HISTORY_TURNS, OPENAI_MAX_TOKENS, OPENAI_MODEL, OPENAI_TEMPERATURE, OPENAI_TIMEOUT_SECS,
+};
+
+/// One parsed line of a streamed chat completion
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    /// A piece of the model's reply
+    Delta(String),
+    /// The end-of-stream marker
+    Done,
+    /// An error reported inside the stream
+    Error(String),
+    /// The model stopped at the token limit, so the reply is incomplete
+    Truncated,
+    /// Blank lines, comments and events without text
+    Ignore,
+}
+
+/// Handle for cancelling a streaming AI request
+#[derive(Clone)]
+pub struct AiRequestHandle {
+    controller: AbortController,
+    cancelled: Rc<Cell<bool>>,
+    timed_out: Rc<Cell<bool>>,
+    /// Recording of the latest response, replayable as a test fixture
+    recording: Rc<RefCell<String>>,
+}
+
+impl AiRequestHandle {
+    /// Creates a handle for a new request
+    pub fn new() -> Result<Self> {
+        let controller = AbortController::new()
+            .map_err(|e| anyhow!("Failed to create abort controller: {:?}", e))?;
+        Ok(Self {
+            controller,
+            cancelled: Rc::new(Cell::new(false)),
+            timed_out: Rc::new(Cell::new(false)),
+            recording: Rc::new(RefCell::new(String::new())),
+        })
+    }
+
+    /// Aborts the request
+    pub fn cancel(&self) {
+        self.cancelled.set(true);
+        self.controller.abort();
+    }
+
+    /// Returns the recorded response, empty if none was received
+    pub fn recording(&self) -> String {
+        self.recording.borrow().clone()
+    }
+}
+
+/// Error for a model response that did not contain a usable contract
+#[derive(Debug)]
+pub struct AiResponseError {
+    pub message: String,
+    /// Response text as returned by the API
+    pub raw_response: String,
+}
+
+impl std::fmt::Display for AiResponseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for AiResponseError {}
 
 /// Service for interacting with OpenAI API
 pub struct OpenAiService;
 
 impl OpenAiService {
-    /// Context prepended to the first user-input prompt when creating a new contract
-    const FIRST_PROMPT_PRE: &'static str = r#"
-I'm going to ask you to generate a Dash Platform data contract after giving you some context and rules. 
+    /// System message giving the model background, rules and output format for every turn
+    const SYSTEM_PROMPT: &'static str = r#"
+You generate and edit Dash Platform data contracts. Here is some context and the rules they must follow.
 
 *Background info*: 
 Dash Platform is a blockchain for decentralized applications that are backed by data contracts. 
@@ -29,138 +102,347 @@ The following requirements must be met in Dash Platform data contracts:
  - Indexes may only have "asc" sort order.
  - All "string" properties that are used in indexes must specify "maxLength", which must be no more than 63.
  - All "array" properties that are used in indexes must specify "maxItems", and it must be less than or equal to 255.
- - All "array" properties must specify `"byteArray": true`.
+ - All "array" properties must specify either `"byteArray": true`, for binary data such as hashes and identifiers, or an "items" schema, for lists of strings, numbers or objects.
  - All "object" properties must define at least 1 property within themselves.
  - All properties must define a "position" field, which is a number starting at 0, incrementing for each property.
 
-*App description*: 
-Now I will give you a user prompt that describes the application that you will generate a data contract for.
+*Output*:
+The user will either describe an application to generate a data contract for, or ask for changes to the data contract from your previous reply.
 
-When creating the data contract, please:
+When creating or changing the data contract, please:
  - Include descriptions for every document type and property. Be creative, extensive, and utilize multiple document types if possible.
  - Include both "description" and "comment" fields for every document type (at the same level as "type", "properties", etc.).
  - Include indexes for any properties that it makes sense for a useful app to index. More is better. 
  - Do not explain anything or return anything else other than a properly formatted data contract JSON schema. 
- - Double check that all requirements and requests above are met. Again, all "array" properties must specify `"byteArray": true`.
-
-App description: 
-
+ - Always return the complete data contract, not only the changed parts.
+ - Double check that all requirements and requests above are met. Again, all "array" properties must specify `"byteArray": true` or "items".
 "#;
 
-    /// Context prepended to user-input prompts after the first prompt
-    const SECOND_PROMPT_PRE: &'static str = r#"
-I'm going to ask you to make some changes to a Dash Platform data contract after giving you some context and rules. 
-
-*Requirements*:
-The following requirements must be met in Dash Platform data contracts: 
- - Indexes may only have "asc" sort order. 
- - All "array" properties must specify "byteArray": true. 
- - All "string" properties that are used in indexes must specify "maxLength", which must be no more than 63. 
- - All "array" properties that are used in indexes must specify "maxItems", and it must be less than or equal to 255. 
- - All "object" properties must define at least 1 property within themselves. 
+    /// Default user message describing a new contract
+    const NEW_CONTRACT_PROMPT: &'static str = "App description:\n\n{{request}}";
 
+    /// Default user message for prompts that change an existing contract
+    const CHANGE_CONTRACT_PROMPT: &'static str = r#"
 *Changes to be made*: 
 Make the following change(s) to this Dash Platform data contract JSON schema, along with any other changes that are necessary to make it valid according to the rules above. 
 Note that the highest-level keys in the data contract are called "document types".
 Do not explain anything or return anything else other than a properly formatted JSON schema:
 
-"#;
+Existing schema:
+{{contract}}
 
-    /// Calls OpenAI API to generate or modify data contracts
-    pub async fn generate_contract(prompt: &str, existing_schema: Option<&str>) -> Result<String> {
-        let full_prompt = if let Some(schema) = existing_schema {
-            format!(
-                "{}\n\nExisting schema:\n{}\n\nUser request:\n{}",
-                Self::SECOND_PROMPT_PRE,
-                schema,
-                prompt
-            )
-        } else {
-            format!("{}{}", Self::FIRST_PROMPT_PRE, prompt)
-        };
+User request:
+{{request}}"#;
 
-        Self::call_api(&full_prompt).await
-    }
+    /// Default follow-up message asking the model to fix validation errors in its last reply
+    const REPAIR_PROMPT: &'static str = r#"The data contract you returned failed Dash Platform validation with these errors:
 
-    /// Makes the actual API call to OpenAI
-    async fn call_api(prompt: &str) -> Result<String> {
-        let params = json!({
-            "model": "gpt-4o",
-            "messages": [{"role": "user", "content": prompt}],
-            "max_tokens": 4096,
-            "temperature": 0.2
-        });
+{{errors}}
 
-        let mut opts = RequestInit::new();
-        let headers =
-            web_sys::Headers::new().map_err(|e| anyhow!("Failed to create headers: {:?}", e))?;
+Fix these errors while keeping the rest of the contract unchanged. Do not explain anything or return anything else other than the complete, corrected JSON schema."#;
 
-        headers
-            .append("Content-Type", "application/json")
-            .map_err(|e| anyhow!("Failed to set content type: {:?}", e))?;
+    /// Stands in for the contract when it is the one from the previous reply
+    const PREVIOUS_CONTRACT: &'static str = "(the data contract from your previous reply)";
 
-        // Note: For local testing, uncomment and add your API key:
-        // headers.append("Authorization", &format!("Bearer {}", "YOUR_API_KEY_HERE"))
-        //     .map_err(|e| anyhow!("Failed to set authorization: {:?}", e))?;
+    /// Default user message for requests that edit one part of a contract
+    const SCOPED_PROMPT: &'static str = r#"
+*Targeted change*: 
+Only one part of a Dash Platform data contract is shown below. Make the following change(s) to that part, keeping it valid according to the rules above. 
+Return the same JSON structure with the same document type and property names, and nothing else. Do not return the rest of the data contract or add other document types.
 
-        opts.method("POST");
-        opts.headers(&headers);
-        opts.body(Some(&JsValue::from_str(&params.to_string())));
-        opts.mode(RequestMode::Cors);
+Part to edit ({{part}}):
+{{contract}}
 
-        // Use the Lambda endpoint for production, OpenAI directly for local testing
-        let url = "https://22vazdmku2qz3prrn57elhdj2i0wyejr.lambda-url.us-west-2.on.aws/";
-        // For local testing, use: "https://api.openai.com/v1/chat/completions"
+User request:
+{{request}}"#;
 
-        let request = Request::new_with_str_and_init(url, &opts)
-            .map_err(|e| anyhow!("Failed to create request: {:?}", e))?;
+    /// Default message for requests that only describe existing fields
+    const DESCRIPTIONS_PROMPT: &'static str = r#"
+You write documentation for Dash Platform data contracts. Data contracts define document types, and each document type has properties.
 
-        let window = web_sys::window().ok_or_else(|| anyhow!("Failed to obtain window object"))?;
+Below is a JSON object whose keys are document types (such as "note") and properties (such as "note.title" or "note.author.name"), each with its definition for context.
+Write a concise description for every key: one sentence, at most 200 characters, saying what the document type or property is for.
 
-        let response = JsFuture::from(window.fetch_with_request(&request))
-            .await
-            .map_err(|e| {
-                anyhow!(
-                    "Fetch request failed: {:?}",
-                    e.as_string().unwrap_or_default()
-                )
-            })?;
+Return only a JSON object mapping each key, exactly as given, to its description text. Do not explain anything or return anything else.
 
-        let response: Response = response.dyn_into().map_err(|e| {
-            anyhow!(
-                "Failed to convert response: {:?}",
-                e.as_string().unwrap_or_default()
-            )
-        })?;
+{{fields}}"#;
 
-        let text = JsFuture::from(
-            response
-                .text()
-                .map_err(|_| anyhow!("Failed to read response text"))?,
+    /// Returns the prompt templates that ship with the app
+    pub fn built_in_templates() -> Vec<PromptTemplate> {
+        vec![PromptTemplate {
+            id: "default".to_string(),
+            name: "Default".to_string(),
+            version: 1,
+            built_in: true,
+            system: Self::SYSTEM_PROMPT.to_string(),
+            new_contract: Self::NEW_CONTRACT_PROMPT.to_string(),
+            change_contract: Self::CHANGE_CONTRACT_PROMPT.to_string(),
+            repair: Self::REPAIR_PROMPT.to_string(),
+            scoped: Self::SCOPED_PROMPT.to_string(),
+            descriptions: Self::DESCRIPTIONS_PROMPT.to_string(),
+        }]
+    }
+
+    /// Builds the user message for a prompt
+    ///
+    /// `existing_schema` is included when the contract is not the one from the
+    /// previous turn, such as after editing the form or importing a contract.
+    pub fn user_request(
+        template: &PromptTemplate,
+        prompt: &str,
+        existing_schema: Option<&str>,
+        has_history: bool,
+    ) -> String {
+        match existing_schema {
+            Some(schema) => template.render(
+                PromptPart::ChangeContract,
+                &[("contract", schema), ("request", prompt)],
+            ),
+            None if has_history => template.render(
+                PromptPart::ChangeContract,
+                &[("contract", Self::PREVIOUS_CONTRACT), ("request", prompt)],
+            ),
+            None => template.render(PromptPart::NewContract, &[("request", prompt)]),
+        }
+    }
+
+    /// Builds the user message for an edit limited to one document type or property
+    pub fn scoped_request(
+        template: &PromptTemplate,
+        instruction: &str,
+        label: &str,
+        subtree_schema: &str,
+    ) -> String {
+        template.render(
+            PromptPart::Scoped,
+            &[
+                ("part", label),
+                ("contract", subtree_schema),
+                ("request", instruction),
+            ],
         )
-        .await
-        .map_err(|e| {
-            anyhow!(
-                "Failed to get response text: {:?}",
-                e.as_string().unwrap_or_default()
+    }
+
+    /// Builds the follow-up message asking the model to fix validation errors in its last reply
+    pub fn repair_request(template: &PromptTemplate, errors: &[ValidationError]) -> String {
+        let error_list = errors
+            .iter()
+            .map(|error| {
+                if error.path.is_empty() {
+                    format!(" - {}", error.message)
+                } else {
+                    format!(" - {}: {}", error.path, error.message)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        template.render(PromptPart::Repair, &[("errors", &error_list)])
+    }
+
+    /// Builds the messages asking for descriptions of the fields in `fields_json`
+    pub fn description_messages(template: &PromptTemplate, fields_json: &str) -> Vec<ChatMessage> {
+        vec![ChatMessage::user(template.render(
+            PromptPart::Descriptions,
+            &[("fields", fields_json)],
+        ))]
+    }
+
+    /// Builds the messages for a new request, replaying earlier turns as context
+    ///
+    /// Only the last `OPENAI_HISTORY_TURNS` turns are replayed with their contracts.
+    /// Older turns are listed by prompt alone, so a long branch does not outgrow the context window.
+    pub fn conversation_messages(
+        template: &PromptTemplate,
+        history: &[&ConversationTurn],
+        request: &str,
+    ) -> Vec<ChatMessage> {
+        let mut messages = vec![ChatMessage::system(template.system.as_str())];
+        let (older, recent) = history.split_at(history.len().saturating_sub(OPENAI_HISTORY_TURNS));
+        if !older.is_empty() {
+            let prompts: Vec<String> = older
+                .iter()
+                .map(|turn| format!(" - {}", turn.prompt))
+                .collect();
+            messages.push(ChatMessage::user(format!(
+                "Earlier requests in this conversation, already applied:\n{}",
+                prompts.join("\n")
+            )));
+        }
+        for turn in recent {
+            messages.push(ChatMessage::user(turn.request.as_str()));
+            messages.push(ChatMessage::assistant(turn.schema.as_str()));
+        }
+        messages.push(ChatMessage::user(request));
+        messages
+    }
+
+    /// Calls OpenAI API with a streamed response, reporting each piece of text as it arrives
+    ///
+    /// The request is aborted by `handle.cancel()` or after `OPENAI_TIMEOUT_SECS`.
+    /// `on_delta` only sees partial output; the contract is returned once the stream ends.
+    pub async fn generate_contract_streaming(
+        messages: &[ChatMessage],
+        handle: &AiRequestHandle,
+        on_delta: impl Fn(&str),
+    ) -> Result<String> {
+        let content =
+            Self::complete_streaming(messages, ReplyShape::Contract, handle, on_delta).await?;
+        // The reply should be the contract alone, but fall back to searching the text
+        Self::extract_from_content(&content, JsonExtractor::extract_contract)
+    }
+
+    /// Requests descriptions for the fields listed by `description_messages`
+    ///
+    /// Returns pairs of field key and suggested description, streamed like `generate_contract_streaming`.
+    pub async fn suggest_descriptions_streaming(
+        messages: &[ChatMessage],
+        handle: &AiRequestHandle,
+        on_delta: impl Fn(&str),
+    ) -> Result<Vec<(String, String)>> {
+        let content =
+            Self::complete_streaming(messages, ReplyShape::Descriptions, handle, on_delta).await?;
+        Self::extract_from_content(&content, JsonExtractor::extract_descriptions)
+    }
+
+    /// Generates a contract through any transport, such as recorded fixtures in tests
+    pub async fn generate_contract_with(
+        transport: &impl AiTransport,
+        messages: &[ChatMessage],
+        on_delta: impl Fn(&str),
+    ) -> Result<String> {
+        let content =
+            Self::complete_with(transport, messages, ReplyShape::Contract, true, on_delta).await?;
+        Self::extract_from_content(&content, JsonExtractor::extract_contract)
+    }
+
+    /// Streams a chat completion from the API and returns the full message content
+    async fn complete_streaming(
+        messages: &[ChatMessage],
+        shape: ReplyShape,
+        handle: &AiRequestHandle,
+        on_delta: impl Fn(&str),
+    ) -> Result<String> {
+        let window = web_sys::window().ok_or_else(|| anyhow!("Failed to obtain window object"))?;
+
+        let timeout_handle = handle.clone();
+        let on_timeout: Closure<dyn FnMut()> = Closure::once(move || {
+            timeout_handle.timed_out.set(true);
+            timeout_handle.controller.abort();
+        });
+        let timeout = window
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                on_timeout.as_ref().unchecked_ref(),
+                (OPENAI_TIMEOUT_SECS * 1000) as i32,
             )
-        })?;
+            .map_err(|e| anyhow!("Failed to set timeout: {:?}", e))?;
+
+        let transport = RecordingTransport::new(
+            FetchTransport::new(Some(handle.controller.signal())),
+            handle.recording.clone(),
+        );
+        let result = Self::complete_with(&transport, messages, shape, true, on_delta).await;
+        window.clear_timeout_with_handle(timeout);
+
+        result.map_err(|e| {
+            if handle.timed_out.get() {
+                anyhow!("Request timed out after {} seconds", OPENAI_TIMEOUT_SECS)
+            } else if handle.cancelled.get() {
+                anyhow!("Request cancelled")
+            } else {
+                e
+            }
+        })
+    }
 
-        let text = text
-            .as_string()
-            .ok_or_else(|| anyhow!("Failed to convert response to string"))?;
+    /// Sends a chat completion request and returns the full message content
+    ///
+    /// Endpoints that ignore `stream` answer with a complete JSON response, which is handled too.
+    async fn complete_with(
+        transport: &impl AiTransport,
+        messages: &[ChatMessage],
+        shape: ReplyShape,
+        stream: bool,
+        on_delta: impl Fn(&str),
+    ) -> Result<String> {
+        let body = Self::request_body(messages, shape, stream).to_string();
+        let mut reader = StreamReader::default();
+        let response = transport
+            .send(&body, &mut |chunk| reader.push(chunk, &on_delta))
+            .await?;
 
         if !response.ok() {
-            let status = response.status();
-            let error_message = Self::extract_error_message(&text);
-            return Err(anyhow!("HTTP {} error from API: {}", status, error_message));
+            let error_message = Self::extract_error_message(&response.body);
+            return Err(anyhow!(
+                "HTTP {} error from API: {}",
+                response.status,
+                error_message
+            ));
+        }
+
+        if response.event_stream {
+            reader.finish()
+        } else {
+            Self::extract_message_content(&response.body)
+        }
+    }
+
+    /// Parses one line of a server-sent event stream from the chat completions API
+    pub fn parse_stream_line(line: &str) -> StreamEvent {
+        let Some(data) = line.trim().strip_prefix("data:") else {
+            return StreamEvent::Ignore;
+        };
+        let data = data.trim();
+        if data == "[DONE]" {
+            return StreamEvent::Done;
+        }
+
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(data) else {
+            return StreamEvent::Ignore;
+        };
+        if let Some(message) = json
+            .get("error")
+            .and_then(|e| e.get("message"))
+            .and_then(|m| m.as_str())
+        {
+            return StreamEvent::Error(message.to_string());
+        }
+
+        let choice = json.get("choices").and_then(|choices| choices.get(0));
+        if let Some(content) = choice
+            .and_then(|choice| choice.get("delta"))
+            .and_then(|delta| delta.get("content"))
+            .and_then(|content| content.as_str())
+            .filter(|content| !content.is_empty())
+        {
+            return StreamEvent::Delta(content.to_string());
         }
 
-        Self::extract_json_schema(&text)
+        if Self::hit_token_limit(choice) {
+            StreamEvent::Truncated
+        } else {
+            StreamEvent::Ignore
+        }
+    }
+
+    /// Builds the chat completions request body, asking for a reply of the given shape
+    pub fn request_body(
+        messages: &[ChatMessage],
+        shape: ReplyShape,
+        stream: bool,
+    ) -> serde_json::Value {
+        json!({
+            "model": OPENAI_MODEL,
+            "messages": messages,
+            "max_tokens": OPENAI_MAX_TOKENS,
+            "temperature": OPENAI_TEMPERATURE,
+            "stream": stream,
+            "response_format": shape.response_format()
+        })
     }
 
     /// Extracts error message from API response
-    fn extract_error_message(text: &str) -> String {
+    pub fn extract_error_message(text: &str) -> String {
         if let Ok(json) = serde_json::from_str::<serde_json::Value>(text) {
             json.get("error")
                 .and_then(|e| e.get("message"))
@@ -172,33 +454,121 @@ Do not explain anything or return anything else other than a properly formatted
         }
     }
 
-    /// Extracts JSON schema from OpenAI response
-    fn extract_json_schema(response_text: &str) -> Result<String> {
-        let json: serde_json::Value = serde_json::from_str(response_text)
-            .map_err(|e| anyhow!("Failed to parse API response: {}", e))?;
+    /// Extracts the message content from a complete chat completions response
+    pub fn extract_message_content(response_text: &str) -> Result<String> {
+        let json: serde_json::Value = serde_json::from_str(response_text).map_err(|e| {
+            anyhow!(AiResponseError {
+                message: format!("Failed to parse API response: {}", e),
+                raw_response: response_text.to_string(),
+            })
+        })?;
 
-        let content = json
-            .get("choices")
-            .and_then(|choices| choices.get(0))
+        let choice = json.get("choices").and_then(|choices| choices.get(0));
+        let content = choice
             .and_then(|choice| choice.get("message"))
             .and_then(|message| message.get("content"))
             .and_then(|content| content.as_str())
-            .ok_or_else(|| anyhow!("Invalid response format from API"))?;
+            .ok_or_else(|| {
+                anyhow!(AiResponseError {
+                    message: "Invalid response format from API".to_string(),
+                    raw_response: response_text.to_string(),
+                })
+            })?;
+
+        if Self::hit_token_limit(choice) {
+            return Err(Self::truncated_error(content));
+        }
+        Ok(content.to_string())
+    }
 
-        // Extract JSON from the response
-        let start = content
-            .find('{')
-            .ok_or_else(|| anyhow!("No JSON found in API response"))?;
-        let end = content
-            .rfind('}')
-            .ok_or_else(|| anyhow!("No valid JSON found in API response"))?;
+    /// Returns true if the model stopped because it ran out of tokens
+    fn hit_token_limit(choice: Option<&serde_json::Value>) -> bool {
+        choice
+            .and_then(|choice| choice.get("finish_reason"))
+            .and_then(|reason| reason.as_str())
+            == Some("length")
+    }
 
-        let schema_json = &content[start..=end];
+    fn truncated_error(content: &str) -> anyhow::Error {
+        anyhow!(AiResponseError {
+            message: "The response was cut off at the token limit".to_string(),
+            raw_response: content.to_string(),
+        })
+    }
 
-        // Validate that it's proper JSON
-        serde_json::from_str::<serde_json::Value>(schema_json)
-            .map_err(|e| anyhow!("Extracted text is not valid JSON: {}", e))?;
+    /// Runs an extractor over message content, keeping the content for display on failure
+    fn extract_from_content<T>(
+        content: &str,
+        extract: impl Fn(&str) -> std::result::Result<T, String>,
+    ) -> Result<T> {
+        extract(content).map_err(|message| {
+            anyhow!(AiResponseError {
+                message,
+                raw_response: content.to_string(),
+            })
+        })
+    }
+}
 
-        Ok(schema_json.to_string())
+/// Collects the reply text from the chunks of an event stream
+#[derive(Default)]
+struct StreamReader {
+    buffer: Vec<u8>,
+    content: String,
+    truncated: bool,
+    error: Option<String>,
+}
+
+impl StreamReader {
+    /// Adds a chunk of the body, returning false once the stream has ended
+    fn push(&mut self, chunk: &[u8], on_delta: &impl Fn(&str)) -> bool {
+        self.buffer.extend_from_slice(chunk);
+
+        // Events are newline-delimited; a newline byte never splits a UTF-8 character
+        while let Some(newline) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=newline).collect();
+            if !self.handle_line(&String::from_utf8_lossy(&line), on_delta) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Handles one event line, returning false once the stream has ended
+    fn handle_line(&mut self, line: &str, on_delta: &impl Fn(&str)) -> bool {
+        match OpenAiService::parse_stream_line(line) {
+            StreamEvent::Delta(text) => {
+                self.content.push_str(&text);
+                on_delta(&text);
+                true
+            }
+            StreamEvent::Truncated => {
+                self.truncated = true;
+                true
+            }
+            StreamEvent::Done => false,
+            StreamEvent::Error(message) => {
+                self.error = Some(message);
+                false
+            }
+            StreamEvent::Ignore => true,
+        }
+    }
+
+    /// Returns the collected reply, or the error reported by the stream
+    fn finish(mut self) -> Result<String> {
+        // The last event may not end with a newline
+        if self.error.is_none() && !self.buffer.is_empty() {
+            let line = String::from_utf8_lossy(&std::mem::take(&mut self.buffer)).into_owned();
+            self.handle_line(&line, &|_| {});
+        }
+
+        if let Some(message) = self.error {
+            return Err(anyhow!("Error from API: {}", message));
+        }
+        if self.truncated {
+            return Err(OpenAiService::truncated_error(&self.content));
+        }
+        Ok(self.content)
     }
 }
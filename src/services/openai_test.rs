@@ -0,0 +1,249 @@
+#[cfg(test)]
+mod tests {
+    use super::super::ai_transport::FixtureTransport;
+    use super::super::json_parser::JsonParser;
+    use super::super::openai::{AiResponseError, OpenAiService, StreamEvent};
+    use super::super::reply_schema::ReplyShape;
+    use crate::types::{ChatMessage, ChatRole, ConversationTurn, DataType, ValidationError};
+    use crate::utils::OPENAI_HISTORY_TURNS;
+    use std::cell::RefCell;
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    /// Runs a future that never waits on anything, as fixture transports do not
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("fixture transport should not wait"),
+        }
+    }
+
+    fn fixture(recording: &str) -> FixtureTransport {
+        FixtureTransport::from_recording(recording).unwrap()
+    }
+
+    fn messages() -> Vec<ChatMessage> {
+        let template = &OpenAiService::built_in_templates()[0];
+        let request = OpenAiService::user_request(template, "A note with a message", None, false);
+        OpenAiService::conversation_messages(template, &[], &request)
+    }
+
+    /// Replays a fixture and returns the response error message and raw response
+    fn replay_error(recording: &str) -> (String, Option<String>) {
+        let error = block_on(OpenAiService::generate_contract_with(
+            &fixture(recording),
+            &messages(),
+            |_| {},
+        ))
+        .unwrap_err();
+        let raw = error
+            .downcast_ref::<AiResponseError>()
+            .map(|e| e.raw_response.clone());
+        (error.to_string(), raw)
+    }
+
+    #[test]
+    fn test_stream_fixture_fills_form() {
+        // Small chunks split events and multi-byte characters across reads
+        for chunk_size in [usize::MAX, 7, 1] {
+            let transport =
+                fixture(include_str!("fixtures/openai/stream_ok.http")).with_chunk_size(chunk_size);
+            let preview = RefCell::new(String::new());
+            let schema = block_on(OpenAiService::generate_contract_with(
+                &transport,
+                &messages(),
+                |delta| preview.borrow_mut().push_str(delta),
+            ))
+            .unwrap();
+
+            assert_eq!(preview.into_inner(), schema);
+            let document_types = JsonParser::parse_contract(&schema).unwrap();
+            assert_eq!(document_types.len(), 1);
+            assert_eq!(document_types[0].name, "note");
+            let message = &document_types[0].properties[0];
+            assert_eq!(message.name, "message");
+            assert_eq!(message.data_type, DataType::String);
+            assert_eq!(message.max_length, Some(280));
+            assert!(message.required);
+            assert_eq!(message.description.as_deref(), Some("Note text — ünïcode"));
+        }
+    }
+
+    #[test]
+    fn test_json_fixture_with_prose() {
+        let schema = block_on(OpenAiService::generate_contract_with(
+            &fixture(include_str!("fixtures/openai/json_ok.http")),
+            &messages(),
+            |_| {},
+        ))
+        .unwrap();
+        let document_types = JsonParser::parse_contract(&schema).unwrap();
+        assert_eq!(document_types[0].properties[0].name, "message");
+    }
+
+    #[test]
+    fn test_error_fixtures() {
+        let (message, raw) = replay_error(include_str!("fixtures/openai/rate_limit.http"));
+        assert_eq!(
+            message,
+            "HTTP 429 error from API: Rate limit reached for requests"
+        );
+        assert_eq!(raw, None);
+
+        let (message, raw) = replay_error(include_str!("fixtures/openai/malformed.http"));
+        assert!(message.contains("JSON"), "{}", message);
+        assert_eq!(
+            raw.as_deref(),
+            Some("I could not produce a contract for that request.")
+        );
+
+        let (message, raw) = replay_error(include_str!("fixtures/openai/truncated.http"));
+        assert!(message.contains("token limit"), "{}", message);
+        assert!(raw.unwrap().starts_with("{\"note\""));
+    }
+
+    #[test]
+    fn test_request_assembly() {
+        let transport = fixture(include_str!("fixtures/openai/stream_ok.http"));
+        block_on(OpenAiService::generate_contract_with(
+            &transport,
+            &messages(),
+            |_| {},
+        ))
+        .unwrap();
+
+        let requests = transport.requests.borrow();
+        assert_eq!(requests.len(), 1);
+        let body: serde_json::Value = serde_json::from_str(&requests[0]).unwrap();
+        assert_eq!(body["stream"], true);
+        // The reply is constrained to the shape of a contract
+        let format = &body["response_format"];
+        assert_eq!(format["type"], "json_schema");
+        assert_eq!(format["json_schema"]["name"], "data_contract");
+        let schema = &format["json_schema"]["schema"];
+        assert_eq!(
+            schema["additionalProperties"]["$ref"],
+            "#/$defs/documentType"
+        );
+        assert_eq!(
+            schema["$defs"]["property"]["properties"]["type"]["enum"],
+            serde_json::json!(["string", "integer", "number", "boolean", "array", "object"])
+        );
+        let descriptions = ReplyShape::Descriptions.response_format();
+        assert_eq!(descriptions["json_schema"]["name"], "descriptions");
+        assert_eq!(
+            descriptions["json_schema"]["schema"]["additionalProperties"]["type"],
+            "string"
+        );
+        let sent = body["messages"].as_array().unwrap();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0]["role"], "system");
+        let request = sent[1]["content"].as_str().unwrap();
+        assert!(request.contains("A note with a message"));
+        assert!(!request.contains("{{"));
+
+        // A changed contract is sent along with the request
+        let template = &OpenAiService::built_in_templates()[0];
+        let request =
+            OpenAiService::user_request(template, "Add a title", Some("{\"note\": {}}"), true);
+        assert!(request.contains("{\"note\": {}}") && request.contains("Add a title"));
+        assert_eq!(
+            OpenAiService::conversation_messages(template, &[], &request)[1].role,
+            ChatRole::User
+        );
+    }
+
+    #[test]
+    fn test_conversation_replay_is_limited() {
+        let template = &OpenAiService::built_in_templates()[0];
+        let turns: Vec<ConversationTurn> = (0..OPENAI_HISTORY_TURNS + 2)
+            .map(|id| ConversationTurn {
+                id,
+                parent: id.checked_sub(1),
+                prompt: format!("prompt {}", id),
+                request: format!("request {} with the contract", id),
+                schema: format!("{{\"turn{}\": {{}}}}", id),
+            })
+            .collect();
+        let history: Vec<&ConversationTurn> = turns.iter().collect();
+
+        let messages = OpenAiService::conversation_messages(template, &history, "next");
+        // System prompt, summary of older turns, recent turns in full, then the new request
+        assert_eq!(messages.len(), 2 + 2 * OPENAI_HISTORY_TURNS + 1);
+        assert_eq!(
+            messages[1].content,
+            "Earlier requests in this conversation, already applied:\n - prompt 0\n - prompt 1"
+        );
+        assert_eq!(messages[2].content, "request 2 with the contract");
+        assert_eq!(messages[3].role, ChatRole::Assistant);
+        assert_eq!(messages.last().unwrap().content, "next");
+        let sent: String = messages.iter().map(|m| m.content.as_str()).collect();
+        assert!(!sent.contains("request 1") && !sent.contains("turn1"));
+
+        // Short histories are replayed whole
+        let messages = OpenAiService::conversation_messages(template, &history[..2], "next");
+        assert_eq!(messages.len(), 1 + 2 * 2 + 1);
+        assert_eq!(messages[1].content, "request 0 with the contract");
+    }
+
+    #[test]
+    fn test_repair_request_lists_every_error() {
+        let template = &OpenAiService::built_in_templates()[0];
+        let errors = [
+            ValidationError::schema_error(
+                String::new(),
+                "Data contract must have at least one document type".to_string(),
+            ),
+            ValidationError::property_error(
+                "note.message".to_string(),
+                "maxLength is required for indexed strings".to_string(),
+            ),
+            ValidationError::index_error("note.byAuthor".to_string(), "Unknown field".to_string()),
+        ];
+
+        let request = OpenAiService::repair_request(template, &errors);
+        assert!(request.contains(" - Data contract must have at least one document type\n"));
+        assert!(request.contains(" - note.message: maxLength is required for indexed strings\n"));
+        assert!(request.contains(" - note.byAuthor: Unknown field"));
+        assert!(!request.contains("{{"));
+    }
+
+    #[test]
+    fn test_parse_stream_line() {
+        assert_eq!(
+            OpenAiService::parse_stream_line(
+                r#"data: {"choices":[{"index":0,"delta":{"content":"{\"note\""}}]}"#
+            ),
+            StreamEvent::Delta("{\"note\"".to_string())
+        );
+        assert_eq!(
+            OpenAiService::parse_stream_line("data: [DONE]"),
+            StreamEvent::Done
+        );
+        assert_eq!(
+            OpenAiService::parse_stream_line(r#"data: {"error":{"message":"Rate limited"}}"#),
+            StreamEvent::Error("Rate limited".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_stream_line_ignores_non_content() {
+        for line in [
+            "",
+            ": keep-alive",
+            "event: message",
+            r#"data: {"choices":[{"index":0,"delta":{"role":"assistant"}}]}"#,
+            r#"data: {"choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+        ] {
+            assert_eq!(
+                OpenAiService::parse_stream_line(line),
+                StreamEvent::Ignore,
+                "{:?}",
+                line
+            );
+        }
+    }
+}
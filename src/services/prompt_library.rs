@@ -0,0 +1,156 @@
+use crate::services::OpenAiService;
+use crate::types::{PromptPart, PromptTemplate};
+use crate::utils::{load_local, save_local};
+use serde::{Deserialize, Serialize};
+
+/// Local storage key of the saved prompt library
+const STORAGE_KEY: &str = "data-contract-creator.prompt-library";
+
+/// Built-in and user-defined prompt templates, with earlier versions of edited ones
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptLibrary {
+    pub templates: Vec<PromptTemplate>,
+    /// Superseded versions of user-defined templates, oldest first
+    pub history: Vec<PromptTemplate>,
+    /// Id of the template used for new requests
+    pub selected: String,
+}
+
+impl Default for PromptLibrary {
+    fn default() -> Self {
+        Self::from_stored(None)
+    }
+}
+
+impl PromptLibrary {
+    /// Loads the library saved in local storage
+    pub fn load() -> Self {
+        let stored = load_local(STORAGE_KEY).and_then(|json| serde_json::from_str(&json).ok());
+        Self::from_stored(stored)
+    }
+
+    /// Saves the library to local storage
+    pub fn store(&self) -> Result<(), String> {
+        let json = serde_json::to_string(self)
+            .map_err(|e| format!("Failed to serialize prompt templates: {}", e))?;
+        save_local(STORAGE_KEY, &json)
+    }
+
+    /// Combines stored user-defined templates with the built-ins of this build
+    ///
+    /// Built-ins are never taken from storage, so they follow updates to the app.
+    pub fn from_stored(stored: Option<PromptLibrary>) -> Self {
+        let mut templates = OpenAiService::built_in_templates();
+        let mut history = Vec::new();
+        let mut selected = templates[0].id.clone();
+
+        if let Some(stored) = stored {
+            templates.extend(stored.templates.into_iter().filter(|t| !t.built_in));
+            history = stored.history;
+            // Templates saved before these parts existed get the built-in texts for them
+            let defaults = templates[0].clone();
+            for template in templates.iter_mut().chain(history.iter_mut()) {
+                for part in [PromptPart::Scoped, PromptPart::Descriptions] {
+                    if template.part(part).is_empty() {
+                        template.set_part(part, defaults.part(part).to_string());
+                    }
+                }
+            }
+            if templates.iter().any(|t| t.id == stored.selected) {
+                selected = stored.selected;
+            }
+        }
+
+        Self {
+            templates,
+            history,
+            selected,
+        }
+    }
+
+    /// Returns the template used for new requests
+    pub fn selected(&self) -> &PromptTemplate {
+        self.find(&self.selected).unwrap_or(&self.templates[0])
+    }
+
+    /// Finds a current template by id
+    pub fn find(&self, id: &str) -> Option<&PromptTemplate> {
+        self.templates.iter().find(|t| t.id == id)
+    }
+
+    /// Selects the template used for new requests
+    pub fn select(&mut self, id: &str) {
+        if self.find(id).is_some() {
+            self.selected = id.to_string();
+        }
+    }
+
+    /// Saves an edited template and selects it, returning its id
+    ///
+    /// Editing a built-in creates a new template from it. Saving a user-defined
+    /// template keeps the previous text as an earlier version.
+    pub fn save(&mut self, mut draft: PromptTemplate) -> String {
+        let next_id = self.next_id();
+
+        match self
+            .templates
+            .iter_mut()
+            .find(|t| t.id == draft.id && !t.built_in)
+        {
+            // Saving unchanged text does not create a new version
+            Some(existing) if existing.same_text(&draft) => {}
+            Some(existing) => {
+                draft.version = existing.version + 1;
+                self.history.push(std::mem::replace(existing, draft.clone()));
+            }
+            None => {
+                draft.id = next_id;
+                draft.version = 1;
+                draft.built_in = false;
+                self.templates.push(draft.clone());
+            }
+        }
+
+        self.selected = draft.id.clone();
+        draft.id
+    }
+
+    /// Deletes a user-defined template with its earlier versions
+    pub fn delete(&mut self, id: &str) {
+        self.templates.retain(|t| t.id != id || t.built_in);
+        self.history.retain(|t| t.id != id);
+        if self.find(&self.selected).is_none() {
+            self.selected = self.templates[0].id.clone();
+        }
+    }
+
+    /// Lists earlier versions of a template, oldest first
+    pub fn versions(&self, id: &str) -> Vec<&PromptTemplate> {
+        self.history.iter().filter(|t| t.id == id).collect()
+    }
+
+    /// Saves an earlier version of a template as its newest version
+    pub fn restore(&mut self, id: &str, version: u32) {
+        let Some(earlier) = self
+            .history
+            .iter()
+            .find(|t| t.id == id && t.version == version)
+            .cloned()
+        else {
+            return;
+        };
+        self.save(earlier);
+    }
+
+    /// Returns an id not used by any current or earlier template
+    fn next_id(&self) -> String {
+        let highest = self
+            .templates
+            .iter()
+            .chain(&self.history)
+            .filter_map(|t| t.id.strip_prefix("custom-")?.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0);
+        format!("custom-{}", highest + 1)
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use super::super::openai::OpenAiService;
+    use super::super::prompt_library::PromptLibrary;
+    use crate::types::{PromptPart, PromptTemplate};
+
+    #[test]
+    fn test_editing_built_in_creates_versioned_copy() {
+        let mut library = PromptLibrary::default();
+        let mut draft = library.selected().clone();
+        draft.name = "Strict".to_string();
+        draft.system = "v1".to_string();
+
+        let id = library.save(draft);
+        assert_eq!(id, "custom-1");
+        assert_eq!(library.selected, id);
+        assert!(library.templates[0].built_in);
+        assert_ne!(library.templates[0].system, "v1");
+
+        let mut draft = library.selected().clone();
+        draft.system = "v2".to_string();
+        library.save(draft.clone());
+        library.save(draft);
+        assert_eq!(library.selected().version, 2);
+        assert_eq!(library.versions(&id).len(), 1);
+
+        library.restore(&id, 1);
+        assert_eq!(library.selected().system, "v1");
+        assert_eq!(library.selected().version, 3);
+    }
+
+    #[test]
+    fn test_stored_built_ins_are_replaced() {
+        let mut stored = PromptLibrary::default();
+        stored.templates[0].system = "stale".to_string();
+        let mut custom = stored.templates[0].clone();
+        custom.name = "Mine".to_string();
+        let id = stored.save(custom);
+
+        let library = PromptLibrary::from_stored(Some(stored));
+        assert_ne!(library.templates[0].system, "stale");
+        assert_eq!(library.templates.len(), 2);
+        assert_eq!(library.selected, id);
+    }
+
+    #[test]
+    fn test_render_fills_known_variables_once() {
+        let template = PromptTemplate {
+            id: "t".to_string(),
+            name: "T".to_string(),
+            version: 1,
+            built_in: false,
+            system: String::new(),
+            new_contract: String::new(),
+            change_contract: "Schema: {{ contract }}\nRequest: {{request}} {{reqest}}".to_string(),
+            repair: String::new(),
+            scoped: String::new(),
+            descriptions: String::new(),
+        };
+
+        let rendered = template.render(
+            PromptPart::ChangeContract,
+            &[("contract", "{\"note\": \"{{request}}\"}"), ("request", "add tags")],
+        );
+        assert_eq!(
+            rendered,
+            "Schema: {\"note\": \"{{request}}\"}\nRequest: add tags {{reqest}}"
+        );
+        assert_eq!(
+            template.unknown_variables(PromptPart::ChangeContract),
+            vec!["reqest"]
+        );
+    }
+
+    #[test]
+    fn test_scoped_and_description_prompts_are_templates() {
+        let mut template = PromptLibrary::default().selected().clone();
+        template.scoped = "Edit {{part}}: {{request}}\n{{contract}}".to_string();
+        template.descriptions = "Describe {{fields}}".to_string();
+
+        assert_eq!(
+            OpenAiService::scoped_request(&template, "add tags", "post", "{\"post\": {}}"),
+            "Edit post: add tags\n{\"post\": {}}"
+        );
+        let messages = OpenAiService::description_messages(&template, "{\"post\": {}}");
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].content, "Describe {\"post\": {}}");
+
+        // The built-in texts use only the variables they offer
+        let built_in = PromptLibrary::default().selected().clone();
+        for part in [PromptPart::Scoped, PromptPart::Descriptions] {
+            assert!(built_in.unknown_variables(part).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_templates_saved_before_new_parts_get_defaults() {
+        let mut library = PromptLibrary::default();
+        let mut draft = library.selected().clone();
+        draft.name = "Old".to_string();
+        let id = library.save(draft);
+
+        // Stored as an earlier build did, without the scoped and description parts
+        let mut json: serde_json::Value = serde_json::to_value(&library).unwrap();
+        let stored = json["templates"][1].as_object_mut().unwrap();
+        stored.remove("scoped");
+        stored.remove("descriptions");
+        let stored: PromptLibrary = serde_json::from_value(json).unwrap();
+
+        let library = PromptLibrary::from_stored(Some(stored));
+        let template = library.find(&id).unwrap();
+        assert_eq!(template.scoped, library.templates[0].scoped);
+        assert_eq!(template.descriptions, library.templates[0].descriptions);
+    }
+}
@@ -0,0 +1,125 @@
+use serde_json::{json, Value};
+
+/// Shape of the JSON the model is asked to reply with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplyShape {
+    /// A data contract, or the part of one sent for a scoped edit
+    Contract,
+    /// Descriptions keyed by document type and property, as `description_messages` asks
+    Descriptions,
+}
+
+impl ReplyShape {
+    /// Returns the `response_format` of a chat completions request for this shape
+    ///
+    /// The contract schema follows the Dash Platform document meta-schema for the keywords
+    /// the form supports. Strict mode cannot describe objects keyed by document type or
+    /// property name, so the schema guides the model rather than being enforced, and
+    /// replies still go through `JsonExtractor` and DPP validation.
+    pub fn response_format(self) -> Value {
+        let (name, schema) = match self {
+            ReplyShape::Contract => ("data_contract", Self::contract_schema()),
+            ReplyShape::Descriptions => ("descriptions", Self::descriptions_schema()),
+        };
+        json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": name,
+                "strict": false,
+                "schema": schema
+            }
+        })
+    }
+
+    /// Document types by name, each an object schema with positioned properties
+    fn contract_schema() -> Value {
+        json!({
+            "type": "object",
+            "minProperties": 1,
+            "additionalProperties": {"$ref": "#/$defs/documentType"},
+            "$defs": {
+                "documentType": {
+                    "type": "object",
+                    "properties": {
+                        "type": {"const": "object"},
+                        "properties": {
+                            "type": "object",
+                            "minProperties": 1,
+                            "additionalProperties": {"$ref": "#/$defs/property"}
+                        },
+                        "indices": {"type": "array", "items": {"$ref": "#/$defs/index"}},
+                        "required": {"type": "array", "items": {"type": "string"}},
+                        "transient": {"type": "array", "items": {"type": "string"}},
+                        "additionalProperties": {"const": false},
+                        "description": {"type": "string"},
+                        "keywords": {"type": "array", "items": {"type": "string"}},
+                        "$comment": {"type": "string"}
+                    },
+                    "required": ["type", "properties", "additionalProperties"],
+                    "additionalProperties": false
+                },
+                "property": {
+                    "type": "object",
+                    "properties": {
+                        "type": {
+                            "enum": ["string", "integer", "number", "boolean", "array", "object"]
+                        },
+                        "position": {"type": "integer", "minimum": 0},
+                        "description": {"type": "string"},
+                        "$comment": {"type": "string"},
+                        "enum": {"type": "array"},
+                        "minLength": {"type": "integer", "minimum": 0},
+                        "maxLength": {"type": "integer", "minimum": 0},
+                        "pattern": {"type": "string"},
+                        "format": {"type": "string"},
+                        "minimum": {"type": "number"},
+                        "maximum": {"type": "number"},
+                        "byteArray": {"const": true},
+                        "items": {"$ref": "#/$defs/property"},
+                        "minItems": {"type": "integer", "minimum": 0},
+                        "maxItems": {"type": "integer", "minimum": 0, "maximum": 255},
+                        "contentMediaType": {"type": "string"},
+                        "properties": {
+                            "type": "object",
+                            "additionalProperties": {"$ref": "#/$defs/property"}
+                        },
+                        "required": {"type": "array", "items": {"type": "string"}},
+                        "minProperties": {"type": "integer", "minimum": 0},
+                        "maxProperties": {"type": "integer", "minimum": 0},
+                        "additionalProperties": {"const": false}
+                    },
+                    "required": ["type"],
+                    "additionalProperties": false
+                },
+                "index": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string", "minLength": 1, "maxLength": 32},
+                        "properties": {
+                            "type": "array",
+                            "minItems": 1,
+                            "items": {
+                                "type": "object",
+                                "minProperties": 1,
+                                "maxProperties": 1,
+                                "additionalProperties": {"enum": ["asc"]}
+                            }
+                        },
+                        "unique": {"type": "boolean"}
+                    },
+                    "required": ["name", "properties"],
+                    "additionalProperties": false
+                }
+            }
+        })
+    }
+
+    /// Description text keyed by document type or property path
+    fn descriptions_schema() -> Value {
+        json!({
+            "type": "object",
+            "minProperties": 1,
+            "additionalProperties": {"type": "string", "maxLength": 200}
+        })
+    }
+}
@@ -0,0 +1,297 @@
+use crate::types::{DataType, DocumentType, Property};
+use crate::utils::{to_pascal_case, to_snake_case};
+
+/// Largest fixed-size byte array emitted as `[u8; N]`, since serde only
+/// derives array support up to 32 elements
+const MAX_FIXED_BYTE_ARRAY: u32 = 32;
+
+/// Rust keywords that must be written as raw identifiers
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+    "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try", "typeof",
+    "unsized", "virtual", "yield",
+];
+
+/// A struct collected while walking the contract, emitted in order
+struct RustStruct {
+    name: String,
+    description: Option<String>,
+    fields: Vec<RustField>,
+}
+
+struct RustField {
+    json_name: String,
+    rust_name: String,
+    rust_type: String,
+    required: bool,
+    description: Option<String>,
+}
+
+/// Structs collected while walking the contract, with the names they have taken
+#[derive(Default)]
+struct Collector {
+    structs: Vec<RustStruct>,
+    /// Struct names in use, including those reserved for document types
+    struct_names: Vec<String>,
+    uses_identifier: bool,
+}
+
+/// Service for generating Rust types with serde derives from document types
+pub struct RustGenerator;
+
+impl RustGenerator {
+    /// Generates a Rust module with one struct per document type
+    pub fn generate(document_types: &[DocumentType]) -> String {
+        let document_types: Vec<&DocumentType> = document_types
+            .iter()
+            .filter(|doc_type| !doc_type.name.is_empty())
+            .collect();
+
+        // Document types name their structs first, so nested structs give way to them
+        let mut collector = Collector::default();
+        let struct_names: Vec<String> = document_types
+            .iter()
+            .map(|doc_type| {
+                let name = to_pascal_case(&doc_type.name);
+                let name = if name.is_empty() {
+                    "Document".to_string()
+                } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+                    format!("_{}", name)
+                } else {
+                    name
+                };
+                Self::unique_struct_name(&mut collector, name)
+            })
+            .collect();
+
+        for (doc_type, struct_name) in document_types.into_iter().zip(struct_names) {
+            Self::collect_document_type(doc_type, struct_name, &mut collector);
+        }
+
+        let mut output = String::new();
+        output.push_str("//! Types generated from a Dash Platform data contract\n\n");
+        if collector.uses_identifier {
+            output.push_str("use dpp::prelude::Identifier;\n");
+        }
+        output.push_str("use serde::{Deserialize, Serialize};\n");
+
+        for rust_struct in &collector.structs {
+            output.push('\n');
+            Self::write_struct(&mut output, rust_struct);
+        }
+
+        output
+    }
+
+    /// Collects the struct for a document type followed by its nested object structs
+    fn collect_document_type(
+        doc_type: &DocumentType,
+        struct_name: String,
+        collector: &mut Collector,
+    ) {
+        let position = collector.structs.len();
+        let mut fields = Self::collect_fields(&struct_name, &doc_type.properties, collector);
+
+        if doc_type.created_at_required {
+            Self::push_field(
+                &mut fields,
+                Self::system_field("$createdAt", "Creation time in milliseconds"),
+            );
+        }
+        if doc_type.updated_at_required {
+            Self::push_field(
+                &mut fields,
+                Self::system_field("$updatedAt", "Last update time in milliseconds"),
+            );
+        }
+
+        collector.structs.insert(
+            position,
+            RustStruct {
+                name: struct_name,
+                description: Some(doc_type.description.clone()).filter(|d| !d.is_empty()),
+                fields,
+            },
+        );
+    }
+
+    /// Converts properties to fields, collecting nested object structs along the way
+    fn collect_fields(
+        parent_name: &str,
+        properties: &[Property],
+        collector: &mut Collector,
+    ) -> Vec<RustField> {
+        let mut fields = Vec::new();
+        for prop in properties.iter().filter(|prop| !prop.name.is_empty()) {
+            let field = RustField {
+                json_name: prop.name.clone(),
+                rust_name: Self::rust_ident(&prop.name),
+                rust_type: Self::rust_type(parent_name, prop, collector),
+                required: prop.required,
+                description: prop.description.clone().filter(|d| !d.is_empty()),
+            };
+            Self::push_field(&mut fields, field);
+        }
+        fields
+    }
+
+    /// Adds a field, numbering its name if another field of the struct already has it
+    fn push_field(fields: &mut Vec<RustField>, mut field: RustField) {
+        let base = field.rust_name.trim_start_matches("r#").to_string();
+        let mut suffix = 2;
+        while fields.iter().any(|f| f.rust_name == field.rust_name) {
+            field.rust_name = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        fields.push(field);
+    }
+
+    /// Takes a struct name, numbering it if another struct already has it
+    fn unique_struct_name(collector: &mut Collector, name: String) -> String {
+        let mut unique = name.clone();
+        let mut suffix = 2;
+        while collector.struct_names.contains(&unique) {
+            unique = format!("{}{}", name, suffix);
+            suffix += 1;
+        }
+        collector.struct_names.push(unique.clone());
+        unique
+    }
+
+    /// Returns the Rust type for a property, emitting a nested struct for objects
+    fn rust_type(parent_name: &str, prop: &Property, collector: &mut Collector) -> String {
+        match prop.data_type {
+            DataType::String => "String".to_string(),
+            DataType::Integer => {
+                if prop
+                    .minimum
+                    .as_ref()
+                    .is_some_and(|min| min.as_u64().is_some())
+                {
+                    "u64".to_string()
+                } else {
+                    "i64".to_string()
+                }
+            }
+            DataType::Number => "f64".to_string(),
+            DataType::Boolean => "bool".to_string(),
+            DataType::Array => {
+                if let Some(ref items) = prop.items {
+                    // Named after the array, so object items get a struct of their own
+                    let item = Property {
+                        name: format!("{} item", prop.name),
+                        ..(**items).clone()
+                    };
+                    return format!("Vec<{}>", Self::rust_type(parent_name, &item, collector));
+                }
+                if prop.is_identifier() {
+                    collector.uses_identifier = true;
+                    return "Identifier".to_string();
+                }
+                match (prop.min_items, prop.max_items) {
+                    (Some(min), Some(max)) if min == max && max <= MAX_FIXED_BYTE_ARRAY => {
+                        format!("[u8; {}]", max)
+                    }
+                    _ => "Vec<u8>".to_string(),
+                }
+            }
+            DataType::Object => {
+                let struct_name = Self::unique_struct_name(
+                    collector,
+                    format!("{}{}", parent_name, to_pascal_case(&prop.name)),
+                );
+                let nested = prop.properties.as_deref().map(Vec::as_slice).unwrap_or(&[]);
+                let position = collector.structs.len();
+                let fields = Self::collect_fields(&struct_name, nested, collector);
+                collector.structs.insert(
+                    position,
+                    RustStruct {
+                        name: struct_name.clone(),
+                        description: prop.description.clone().filter(|d| !d.is_empty()),
+                        fields,
+                    },
+                );
+                struct_name
+            }
+        }
+    }
+
+    /// Creates a required field for a system timestamp property
+    fn system_field(json_name: &str, description: &str) -> RustField {
+        RustField {
+            json_name: json_name.to_string(),
+            rust_name: Self::rust_ident(json_name),
+            rust_type: "u64".to_string(),
+            required: true,
+            description: Some(description.to_string()),
+        }
+    }
+
+    /// Converts a JSON name to a valid snake_case Rust identifier
+    fn rust_ident(name: &str) -> String {
+        let ident = to_snake_case(name);
+        if ident.is_empty() {
+            "field".to_string()
+        } else if ident.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("_{}", ident)
+        } else if ["self", "super", "crate"].contains(&ident.as_str()) {
+            // These keywords cannot be raw identifiers
+            format!("{}_", ident)
+        } else if RUST_KEYWORDS.contains(&ident.as_str()) {
+            format!("r#{}", ident)
+        } else {
+            ident
+        }
+    }
+
+    /// Writes a struct definition with doc comments and serde attributes
+    fn write_struct(output: &mut String, rust_struct: &RustStruct) {
+        if let Some(ref description) = rust_struct.description {
+            Self::write_doc_comment(output, description, "");
+        }
+        output.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+        output.push_str(&format!("pub struct {} {{\n", rust_struct.name));
+
+        for field in &rust_struct.fields {
+            if let Some(ref description) = field.description {
+                Self::write_doc_comment(output, description, "    ");
+            }
+
+            let mut serde_args = Vec::new();
+            if field.rust_name.trim_start_matches("r#") != field.json_name {
+                serde_args.push(format!("rename = \"{}\"", field.json_name));
+            }
+            if !field.required {
+                serde_args.push("default".to_string());
+                serde_args.push("skip_serializing_if = \"Option::is_none\"".to_string());
+            }
+            if !serde_args.is_empty() {
+                output.push_str(&format!("    #[serde({})]\n", serde_args.join(", ")));
+            }
+
+            let field_type = if field.required {
+                field.rust_type.clone()
+            } else {
+                format!("Option<{}>", field.rust_type)
+            };
+            output.push_str(&format!("    pub {}: {},\n", field.rust_name, field_type));
+        }
+
+        output.push_str("}\n");
+    }
+
+    /// Writes a description as `///` doc comment lines
+    fn write_doc_comment(output: &mut String, description: &str, indent: &str) {
+        for line in description.lines() {
+            output.push_str(indent);
+            output.push_str("///");
+            if !line.trim().is_empty() {
+                output.push(' ');
+                output.push_str(line.trim_end());
+            }
+            output.push('\n');
+        }
+    }
+}
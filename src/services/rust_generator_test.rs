@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod tests {
+    use super::super::json_parser::JsonParser;
+    use super::super::rust_generator::RustGenerator;
+
+    fn generate(json: &str) -> String {
+        RustGenerator::generate(&JsonParser::parse_contract(json).unwrap())
+    }
+
+    #[test]
+    fn test_field_types() {
+        let output = generate(
+            r#"{"post": {"type": "object", "properties": {
+                "title": {"type": "string", "position": 0, "description": "Post title"},
+                "likes": {"type": "integer", "position": 1, "minimum": 0},
+                "score": {"type": "integer", "position": 2, "minimum": -10},
+                "ratio": {"type": "number", "position": 3},
+                "draft": {"type": "boolean", "position": 4},
+                "owner": {"type": "array", "position": 5, "byteArray": true,
+                    "minItems": 32, "maxItems": 32,
+                    "contentMediaType": "application/x.dash.dpp.identifier"},
+                "hash": {"type": "array", "position": 6, "byteArray": true, "minItems": 20, "maxItems": 20},
+                "blob": {"type": "array", "position": 7, "byteArray": true}
+            }, "required": ["title", "likes", "score", "ratio", "draft", "owner", "hash", "blob"],
+            "additionalProperties": false}}"#,
+        );
+
+        assert!(output.contains("use dpp::prelude::Identifier;\n"));
+        assert!(output.contains("pub struct Post {\n"));
+        assert!(output.contains("    /// Post title\n    pub title: String,\n"));
+        assert!(output.contains("    pub likes: u64,\n"));
+        assert!(output.contains("    pub score: i64,\n"));
+        assert!(output.contains("    pub ratio: f64,\n"));
+        assert!(output.contains("    pub draft: bool,\n"));
+        assert!(output.contains("    pub owner: Identifier,\n"));
+        assert!(output.contains("    pub hash: [u8; 20],\n"));
+        assert!(output.contains("    pub blob: Vec<u8>,\n"));
+    }
+
+    #[test]
+    fn test_optional_fields() {
+        let output = generate(
+            r#"{"post": {"type": "object", "properties": {
+                "title": {"type": "string", "position": 0},
+                "subTitle": {"type": "string", "position": 1}
+            }, "required": ["title", "$createdAt"], "additionalProperties": false}}"#,
+        );
+
+        assert!(!output.contains("Identifier"));
+        assert!(output.contains("    pub title: String,\n"));
+        assert!(output.contains(concat!(
+            "    #[serde(rename = \"subTitle\", default, skip_serializing_if = \"Option::is_none\")]\n",
+            "    pub sub_title: Option<String>,\n"
+        )));
+        assert!(
+            output.contains("    #[serde(rename = \"$createdAt\")]\n    pub created_at: u64,\n")
+        );
+    }
+
+    #[test]
+    fn test_nested_structs_and_typed_arrays() {
+        let output = generate(
+            r#"{"post": {"type": "object", "properties": {
+                "author": {"type": "object", "position": 0,
+                    "properties": {"name": {"type": "string", "position": 0}},
+                    "required": ["name"], "additionalProperties": false},
+                "tags": {"type": "array", "position": 1, "items": {"type": "string"}},
+                "links": {"type": "array", "position": 2, "items": {"type": "object",
+                    "properties": {"url": {"type": "string", "position": 0}},
+                    "required": ["url"], "additionalProperties": false}}
+            }, "required": ["author", "tags", "links"], "additionalProperties": false}}"#,
+        );
+
+        assert!(output.contains("    pub author: PostAuthor,\n"));
+        assert!(output.contains("pub struct PostAuthor {\n    pub name: String,\n}\n"));
+        assert!(output.contains("    pub tags: Vec<String>,\n"));
+        assert!(output.contains("    pub links: Vec<PostLinksItem>,\n"));
+        assert!(output.contains("pub struct PostLinksItem {\n    pub url: String,\n}\n"));
+        // Nested structs follow the document type's struct
+        assert!(
+            output.find("pub struct Post {").unwrap()
+                < output.find("pub struct PostAuthor").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_identifier_import_only_for_identifiers() {
+        let output = generate(
+            r#"{"userIdentifier": {"type": "object", "properties": {
+                "label": {"type": "string", "position": 0}
+            }, "required": ["label"], "additionalProperties": false},
+            "post": {"type": "object", "properties": {
+                "by": {"type": "object", "position": 0,
+                    "properties": {"name": {"type": "string", "position": 0}},
+                    "additionalProperties": false}
+            }, "required": ["by"], "additionalProperties": false}}"#,
+        );
+
+        assert!(output.contains("pub struct UserIdentifier {\n"));
+        assert!(!output.contains("use dpp::prelude::Identifier;"));
+    }
+
+    #[test]
+    fn test_field_names_deduplicated() {
+        let output = generate(
+            r#"{"post": {"type": "object", "properties": {
+                "createdAt": {"type": "string", "position": 0},
+                "ownerId": {"type": "string", "position": 1},
+                "owner_id": {"type": "string", "position": 2},
+                "type": {"type": "string", "position": 3},
+                "Type": {"type": "string", "position": 4}
+            }, "required": ["createdAt", "ownerId", "owner_id", "type", "Type", "$createdAt"],
+            "additionalProperties": false}}"#,
+        );
+
+        assert!(
+            output.contains("    #[serde(rename = \"createdAt\")]\n    pub created_at: String,\n")
+        );
+        assert!(output.contains("    #[serde(rename = \"ownerId\")]\n    pub owner_id: String,\n"));
+        assert!(
+            output.contains("    #[serde(rename = \"owner_id\")]\n    pub owner_id_2: String,\n")
+        );
+        assert!(output.contains("    pub r#type: String,\n"));
+        assert!(output.contains("    #[serde(rename = \"Type\")]\n    pub type_2: String,\n"));
+        assert!(
+            output.contains("    #[serde(rename = \"$createdAt\")]\n    pub created_at_2: u64,\n")
+        );
+    }
+
+    #[test]
+    fn test_struct_names_deduplicated() {
+        let output = generate(
+            r#"{"a": {"type": "object", "properties": {
+                "bC": {"type": "object", "position": 0,
+                    "properties": {"x": {"type": "string", "position": 0}},
+                    "additionalProperties": false}
+            }, "additionalProperties": false},
+            "aB": {"type": "object", "properties": {
+                "c": {"type": "object", "position": 0,
+                    "properties": {"y": {"type": "string", "position": 0}},
+                    "additionalProperties": false}
+            }, "additionalProperties": false},
+            "a_b_c": {"type": "object", "properties": {
+                "z": {"type": "string", "position": 0}
+            }, "additionalProperties": false}}"#,
+        );
+
+        // Document types keep their names, and nested structs are numbered around them
+        assert!(output.contains("pub struct ABC {\n    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub z: "));
+        assert!(output.contains("    pub b_c: Option<ABC2>,\n"));
+        assert!(output.contains("    pub c: Option<ABC3>,\n"));
+        assert!(output.contains("pub struct ABC2 {\n"));
+        assert!(output.contains("pub struct ABC3 {\n"));
+    }
+
+    #[test]
+    fn test_struct_name_starting_with_digit() {
+        let output = generate(
+            r#"{"1stPost": {"type": "object", "properties": {
+                "title": {"type": "string", "position": 0}
+            }, "additionalProperties": false}}"#,
+        );
+
+        assert!(output.contains("pub struct _1stPost {\n"));
+    }
+}
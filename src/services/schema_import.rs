@@ -0,0 +1,684 @@
+use crate::services::{JsonParser, ParsedContract, SourceLocations};
+use crate::types::Severity;
+use crate::utils::{to_camel_case, MAX_INDEXED_ARRAY_ITEMS, MAX_INDEXED_STRING_LENGTH};
+use serde_json::{json, Map, Value};
+
+/// Validation keywords that Dash contracts cannot express, dropped with a warning
+const UNSUPPORTED_KEYWORDS: &[&str] = &[
+    "not",
+    "if",
+    "then",
+    "else",
+    "patternProperties",
+    "propertyNames",
+    "dependencies",
+    "dependentRequired",
+    "dependentSchemas",
+    "unevaluatedProperties",
+    "unevaluatedItems",
+    "uniqueItems",
+    "multipleOf",
+    "contains",
+    "minContains",
+    "maxContains",
+    "prefixItems",
+    "additionalItems",
+    "discriminator",
+];
+
+/// Kind of schema document a contract was adapted from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchemaSource {
+    JsonSchema,
+    OpenApi,
+}
+
+impl SchemaSource {
+    /// Returns a short description for the import message
+    pub fn label(&self) -> &'static str {
+        match self {
+            SchemaSource::JsonSchema => "JSON Schema",
+            SchemaSource::OpenApi => "OpenAPI component schemas",
+        }
+    }
+}
+
+/// A Dash contract adapted from JSON Schema or OpenAPI
+#[derive(Debug, Clone)]
+pub struct AdaptedSchema {
+    pub source: SchemaSource,
+    /// Document types, with a warning for every construct that was converted or dropped
+    pub parsed: ParsedContract,
+    /// The adapted contract as JSON, which the diagnostics refer to
+    pub text: String,
+}
+
+/// Service for importing plain JSON Schema and OpenAPI schemas as Dash contracts
+///
+/// Local `$ref`s are inlined, positions follow declaration order, and constructs
+/// Dash contracts cannot express are converted or dropped with a warning.
+pub struct SchemaImport;
+
+impl SchemaImport {
+    /// Returns the kind of schema document, or `None` for anything that looks like a contract
+    ///
+    /// A contract may name a document type `openapi` or `definitions`, so those keys
+    /// only count when their value is not a document type.
+    pub fn detect(value: &Value) -> Option<SchemaSource> {
+        let obj = value.as_object()?;
+        // The OpenAPI version is a string, such as "3.0.3"
+        if ["openapi", "swagger"]
+            .iter()
+            .any(|key| obj.get(*key).is_some_and(Value::is_string))
+        {
+            return Some(SchemaSource::OpenApi);
+        }
+        let object_schema = obj.get("type") == Some(&json!("object"))
+            && obj.get("properties").is_some_and(Value::is_object);
+        let definitions = ["$defs", "definitions"].iter().any(|key| {
+            obj.get(*key)
+                .is_some_and(|defs| !Self::is_document_type(defs))
+        });
+        (object_schema || obj.contains_key("$schema") || definitions)
+            .then_some(SchemaSource::JsonSchema)
+    }
+
+    /// Returns true for an object shaped like a document type rather than a map of schemas
+    fn is_document_type(value: &Value) -> bool {
+        value.get("properties").is_some_and(Value::is_object)
+    }
+
+    /// Adapts a JSON Schema or OpenAPI document, or returns `None` if it is neither
+    pub fn adapt(value: &Value) -> Option<AdaptedSchema> {
+        let source = Self::detect(value)?;
+        let mut adapter = Adapter {
+            root: value,
+            refs: Vec::new(),
+            notes: Vec::new(),
+        };
+
+        let mut contract = Map::new();
+        for (name, pointer) in Self::document_schemas(value, source) {
+            if let Some(document_type) = adapter.document_type(&name, &pointer) {
+                contract.insert(name, document_type);
+            }
+        }
+        if contract.is_empty() {
+            adapter.note(
+                Severity::Error,
+                "",
+                "No object schemas with properties were found to import as document types"
+                    .to_string(),
+            );
+        }
+
+        let text = serde_json::to_string_pretty(&Value::Object(contract)).unwrap_or_default();
+        let mut parsed = JsonParser::parse_with_diagnostics(&text);
+        let locations = SourceLocations::scan(&text);
+        parsed.diagnostics.extend(
+            adapter
+                .notes
+                .into_iter()
+                .map(|(severity, pointer, message)| {
+                    locations.diagnostic(&text, severity, &pointer, message)
+                }),
+        );
+        parsed
+            .diagnostics
+            .sort_by_key(|diagnostic| diagnostic.start);
+
+        Some(AdaptedSchema {
+            source,
+            parsed,
+            text,
+        })
+    }
+
+    /// Lists the schemas to import as document types, with their JSON pointers
+    fn document_schemas(value: &Value, source: SchemaSource) -> Vec<(String, String)> {
+        let named = |pointer: &str| -> Vec<(String, String)> {
+            value
+                .pointer(pointer)
+                .and_then(Value::as_object)
+                .map(|schemas| {
+                    schemas
+                        .keys()
+                        .map(|name| (name.clone(), SourceLocations::child(pointer, name)))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        match source {
+            SchemaSource::OpenApi if value.get("swagger").is_some() => named("/definitions"),
+            SchemaSource::OpenApi => named("/components/schemas"),
+            SchemaSource::JsonSchema if value.get("properties").is_some() => {
+                let title = value.get("title").and_then(Value::as_str).unwrap_or("");
+                let name = match to_camel_case(title) {
+                    name if name.is_empty() => "document".to_string(),
+                    name => name,
+                };
+                vec![(name, String::new())]
+            }
+            SchemaSource::JsonSchema => {
+                let mut schemas = named("/$defs");
+                schemas.extend(named("/definitions"));
+                schemas
+            }
+        }
+    }
+}
+
+/// Converts schemas into contract JSON, noting every change by its pointer in the contract
+struct Adapter<'a> {
+    root: &'a Value,
+    /// `$ref`s being inlined, to stop at recursive references
+    refs: Vec<String>,
+    notes: Vec<(Severity, String, String)>,
+}
+
+impl Adapter<'_> {
+    fn note(&mut self, severity: Severity, pointer: &str, message: String) {
+        self.notes.push((severity, pointer.to_string(), message));
+    }
+
+    fn warn(&mut self, pointer: &str, message: String) {
+        self.note(Severity::Warning, pointer, message);
+    }
+
+    /// Converts the schema at `pointer` into a document type
+    fn document_type(&mut self, name: &str, pointer: &str) -> Option<Value> {
+        let target = SourceLocations::child("", name);
+        self.refs = vec![format!("#{}", pointer)];
+        let schema = self.resolve(self.root.pointer(pointer)?, &target)?;
+        let (schema, _) = self.simplify(schema, &target)?;
+
+        if !schema.get("properties").is_some_and(Value::is_object) {
+            self.warn(
+                "",
+                format!(
+                    "Schema '{}' has no properties, so it is only used where referenced",
+                    name
+                ),
+            );
+            return None;
+        }
+
+        let mut document_type = Map::new();
+        document_type.insert("type".to_string(), json!("object"));
+        self.object_body(&schema, &target, &mut document_type);
+
+        // Dash indices written into the schema are kept, with indexed values bounded
+        if let Some(Value::Array(indices)) = schema.get("indices") {
+            document_type.insert("indices".to_string(), Value::Array(indices.clone()));
+            let fields = indices
+                .iter()
+                .filter_map(|index| index.get("properties")?.as_array())
+                .flatten()
+                .filter_map(Value::as_object)
+                .flat_map(|field| field.keys());
+            for field in fields {
+                self.bound_indexed(&mut document_type, &target, field);
+            }
+        }
+
+        Self::copy(&schema, &mut document_type, &["description", "$comment"]);
+        Some(Value::Object(document_type))
+    }
+
+    /// Limits an indexed string or byte array to the sizes Dash can index
+    fn bound_indexed(&mut self, document_type: &mut Map<String, Value>, target: &str, field: &str) {
+        let Some(Value::Object(property)) = document_type
+            .get_mut("properties")
+            .and_then(|properties| properties.get_mut(field))
+        else {
+            return;
+        };
+
+        let (key, limit) = match property.get("type").and_then(Value::as_str) {
+            Some("string") => ("maxLength", MAX_INDEXED_STRING_LENGTH),
+            Some("array") => ("maxItems", MAX_INDEXED_ARRAY_ITEMS),
+            _ => return,
+        };
+        if property
+            .get(key)
+            .and_then(Value::as_u64)
+            .is_some_and(|max| max <= u64::from(limit))
+        {
+            return;
+        }
+
+        property.insert(key.to_string(), json!(limit));
+        let pointer = SourceLocations::child(&SourceLocations::child(target, "properties"), field);
+        self.warn(
+            &SourceLocations::child(&pointer, key),
+            format!(
+                "'{}' is indexed, so its {} was limited to {}",
+                field, key, limit
+            ),
+        );
+    }
+
+    /// Converts the properties and required list of an object schema
+    fn object_body(
+        &mut self,
+        schema: &Map<String, Value>,
+        target: &str,
+        out: &mut Map<String, Value>,
+    ) {
+        let required_names: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|names| names.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let properties_target = SourceLocations::child(target, "properties");
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        if let Some(Value::Object(schemas)) = schema.get("properties") {
+            for (name, property_schema) in schemas {
+                let pointer = SourceLocations::child(&properties_target, name);
+                let Some((converted, nullable)) = self.property(property_schema, &pointer) else {
+                    continue;
+                };
+
+                if required_names.contains(&name.as_str()) {
+                    if nullable {
+                        self.warn(
+                            &pointer,
+                            format!(
+                                "'{}' is required but may be null, which Dash cannot express; imported as optional",
+                                name
+                            ),
+                        );
+                    } else {
+                        required.push(json!(name));
+                    }
+                }
+
+                let mut property = Map::new();
+                property.insert("position".to_string(), json!(properties.len()));
+                property.extend(converted);
+                properties.insert(name.clone(), Value::Object(property));
+            }
+        }
+        // System fields such as $createdAt have no property of their own
+        required.extend(
+            required_names
+                .iter()
+                .filter(|name| name.starts_with('$'))
+                .map(|name| json!(name)),
+        );
+
+        out.insert("properties".to_string(), Value::Object(properties));
+        if !required.is_empty() {
+            out.insert("required".to_string(), Value::Array(required));
+        }
+        if !matches!(
+            schema.get("additionalProperties"),
+            None | Some(Value::Bool(false))
+        ) {
+            self.warn(
+                target,
+                "Dash contracts do not allow additional properties, so additionalProperties is false"
+                    .to_string(),
+            );
+        }
+        out.insert("additionalProperties".to_string(), json!(false));
+    }
+
+    /// Converts a property schema, returning it with whether it may be null
+    fn property(&mut self, schema: &Value, target: &str) -> Option<(Map<String, Value>, bool)> {
+        let depth = self.refs.len();
+        let converted = self.resolve(schema, target).and_then(|schema| {
+            let (schema, nullable) = self.simplify(schema, target)?;
+            Some((self.convert(&schema, target), nullable))
+        });
+        self.refs.truncate(depth);
+        converted
+    }
+
+    /// Inlines `$ref`s, letting keywords beside a reference override the referenced schema
+    fn resolve(&mut self, schema: &Value, target: &str) -> Option<Map<String, Value>> {
+        let Some(mut schema) = schema.as_object().cloned() else {
+            self.warn(
+                target,
+                "Schema is not an object, so it was dropped".to_string(),
+            );
+            return None;
+        };
+
+        while let Some(Value::String(reference)) = schema.remove("$ref") {
+            let Some(pointer) = reference.strip_prefix('#') else {
+                self.warn(
+                    target,
+                    format!(
+                        "External $ref '{}' cannot be resolved, so it was dropped",
+                        reference
+                    ),
+                );
+                return None;
+            };
+            if self.refs.contains(&reference) {
+                self.warn(
+                    target,
+                    format!(
+                        "Recursive $ref '{}' cannot be expressed in Dash, so it was dropped",
+                        reference
+                    ),
+                );
+                return None;
+            }
+            let Some(Value::Object(referenced)) = self.root.pointer(pointer) else {
+                self.warn(
+                    target,
+                    format!("$ref '{}' was not found, so it was dropped", reference),
+                );
+                return None;
+            };
+
+            let mut resolved = referenced.clone();
+            resolved.extend(schema);
+            schema = resolved;
+            self.refs.push(reference);
+        }
+
+        Some(schema)
+    }
+
+    /// Merges `allOf`, picks one `oneOf`/`anyOf` alternative and removes null types
+    fn simplify(
+        &mut self,
+        mut schema: Map<String, Value>,
+        target: &str,
+    ) -> Option<(Map<String, Value>, bool)> {
+        let mut nullable = schema.remove("nullable") == Some(json!(true));
+
+        if let Some(Value::Array(parts)) = schema.remove("allOf") {
+            for part in &parts {
+                let part = self.resolve(part, target)?;
+                let (part, _) = self.simplify(part, target)?;
+                Self::merge(&mut schema, part);
+            }
+        }
+
+        for key in ["oneOf", "anyOf"] {
+            let Some(Value::Array(alternatives)) = schema.remove(key) else {
+                continue;
+            };
+            let (nulls, others): (Vec<&Value>, Vec<&Value>) = alternatives
+                .iter()
+                .partition(|alternative| alternative.get("type") == Some(&json!("null")));
+            nullable |= !nulls.is_empty();
+            let Some(first) = others.first() else {
+                continue;
+            };
+            if others.len() > 1 {
+                self.warn(
+                    target,
+                    format!(
+                        "{} is not supported by Dash contracts; only the first of {} alternatives was imported",
+                        key,
+                        others.len()
+                    ),
+                );
+            }
+            let alternative = self.resolve(first, target)?;
+            let (alternative, alternative_nullable) = self.simplify(alternative, target)?;
+            nullable |= alternative_nullable;
+            Self::merge(&mut schema, alternative);
+        }
+
+        if let Some(Value::Array(types)) = schema.get("type").cloned() {
+            let named: Vec<&str> = types
+                .iter()
+                .filter_map(Value::as_str)
+                .filter(|name| *name != "null")
+                .collect();
+            nullable |= named.len() < types.len();
+            if named.len() > 1 {
+                self.warn(
+                    target,
+                    format!(
+                        "Properties can only have one type; imported as '{}'",
+                        named[0]
+                    ),
+                );
+            }
+            match named.first() {
+                Some(name) => schema.insert("type".to_string(), json!(name)),
+                None => schema.remove("type"),
+            };
+        }
+
+        if schema.get("type") == Some(&json!("null")) {
+            self.warn(
+                target,
+                "A property that can only be null cannot be stored, so it was dropped".to_string(),
+            );
+            return None;
+        }
+
+        Some((schema, nullable))
+    }
+
+    /// Adds the keywords of `part` to `schema`, combining properties and required lists
+    fn merge(schema: &mut Map<String, Value>, part: Map<String, Value>) {
+        for (key, value) in part {
+            match (schema.get_mut(&key), value) {
+                (Some(Value::Object(existing)), Value::Object(added)) if key == "properties" => {
+                    for (name, property) in added {
+                        existing.entry(name).or_insert(property);
+                    }
+                }
+                (Some(Value::Array(existing)), Value::Array(added)) if key == "required" => {
+                    for name in added {
+                        if !existing.contains(&name) {
+                            existing.push(name);
+                        }
+                    }
+                }
+                (Some(_), _) => {}
+                (None, value) => {
+                    schema.insert(key, value);
+                }
+            }
+        }
+    }
+
+    /// Converts a resolved, simplified schema into a contract property
+    fn convert(&mut self, schema: &Map<String, Value>, target: &str) -> Map<String, Value> {
+        let mut out = Map::new();
+        let data_type = match schema.get("type").and_then(Value::as_str) {
+            Some(name) => name.to_string(),
+            None if schema.contains_key("properties") => "object".to_string(),
+            None if schema.contains_key("items") => "array".to_string(),
+            None => match schema.get("enum").or(schema.get("const")) {
+                Some(Value::Array(values)) => Self::type_of(values.first()),
+                Some(value) if !value.is_array() => Self::type_of(Some(value)),
+                _ => {
+                    self.warn(target, "No type given, imported as a string".to_string());
+                    "string".to_string()
+                }
+            },
+        };
+
+        match data_type.as_str() {
+            "string" => match schema.get("format").and_then(Value::as_str) {
+                Some(format @ ("byte" | "binary")) => {
+                    self.warn(
+                        target,
+                        format!("String format '{}' imported as a byte array", format),
+                    );
+                    Self::byte_array(&mut out);
+                }
+                _ => {
+                    out.insert("type".to_string(), json!("string"));
+                    Self::copy(
+                        schema,
+                        &mut out,
+                        &["minLength", "maxLength", "pattern", "format"],
+                    );
+                }
+            },
+            "integer" | "number" => {
+                out.insert("type".to_string(), json!(data_type));
+                Self::copy(schema, &mut out, &["minimum", "maximum"]);
+                self.exclusive_bounds(schema, &mut out, target, data_type == "integer");
+            }
+            "boolean" => {
+                out.insert("type".to_string(), json!("boolean"));
+            }
+            "array" => {
+                self.array(schema, &mut out, target);
+            }
+            "object" => {
+                if schema.get("properties").is_some_and(Value::is_object) {
+                    out.insert("type".to_string(), json!("object"));
+                    self.object_body(schema, target, &mut out);
+                    Self::copy(schema, &mut out, &["minProperties", "maxProperties"]);
+                } else {
+                    self.warn(
+                        target,
+                        "An object without properties cannot be described in Dash; imported as a byte array to hold it serialized"
+                            .to_string(),
+                    );
+                    Self::byte_array(&mut out);
+                }
+            }
+            other => {
+                self.warn(
+                    target,
+                    format!("Unknown type '{}', imported as a string", other),
+                );
+                out.insert("type".to_string(), json!("string"));
+            }
+        }
+
+        if out.get("type") != Some(&json!("array")) && out.get("type") != Some(&json!("object")) {
+            if let Some(value) = schema.get("const") {
+                out.insert("enum".to_string(), json!([value]));
+            }
+            Self::copy(schema, &mut out, &["enum"]);
+        }
+        Self::copy(schema, &mut out, &["description", "$comment"]);
+
+        for keyword in UNSUPPORTED_KEYWORDS {
+            if schema.contains_key(*keyword) {
+                self.warn(
+                    target,
+                    format!(
+                        "'{}' is not supported by Dash contracts, so it was dropped",
+                        keyword
+                    ),
+                );
+            }
+        }
+
+        out
+    }
+
+    /// Converts an array schema into typed items, or a byte array for arrays of bytes
+    fn array(&mut self, schema: &Map<String, Value>, out: &mut Map<String, Value>, target: &str) {
+        let items = schema.get("items").filter(|items| items.is_object());
+        let bytes = schema.get("byteArray") == Some(&json!(true))
+            || items.is_some_and(|items| {
+                items.get("type") == Some(&json!("integer"))
+                    && items.get("minimum").and_then(Value::as_i64) == Some(0)
+                    && items.get("maximum").and_then(Value::as_i64) == Some(255)
+            });
+
+        out.insert("type".to_string(), json!("array"));
+        let converted = match items.filter(|_| !bytes) {
+            Some(items) => self.property(items, &SourceLocations::child(target, "items")),
+            None => None,
+        };
+        match converted {
+            Some((items, _)) => {
+                out.insert("items".to_string(), Value::Object(items));
+                Self::copy(schema, out, &["minItems", "maxItems"]);
+            }
+            None => {
+                if !bytes {
+                    self.warn(
+                        target,
+                        "Arrays without a single items schema are imported as byte arrays"
+                            .to_string(),
+                    );
+                }
+                Self::byte_array(out);
+                Self::copy(schema, out, &["minItems", "maxItems", "contentMediaType"]);
+            }
+        }
+    }
+
+    /// Converts `exclusiveMinimum` and `exclusiveMaximum` to inclusive bounds
+    fn exclusive_bounds(
+        &mut self,
+        schema: &Map<String, Value>,
+        out: &mut Map<String, Value>,
+        target: &str,
+        integer: bool,
+    ) {
+        for (key, bound, step) in [
+            ("exclusiveMinimum", "minimum", 1),
+            ("exclusiveMaximum", "maximum", -1),
+        ] {
+            // Draft 4 and OpenAPI 3.0 mark the bound exclusive; later drafts give the value
+            let value = match schema.get(key) {
+                Some(Value::Number(value)) => value.clone(),
+                Some(Value::Bool(true)) => match schema.get(bound) {
+                    Some(Value::Number(value)) => value.clone(),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+
+            let pointer = SourceLocations::child(target, bound);
+            // Bounds at the ends of the i64 range have no inclusive integer neighbour
+            match value
+                .as_i64()
+                .filter(|_| integer)
+                .and_then(|value| value.checked_add(step))
+            {
+                Some(inclusive) => {
+                    out.insert(bound.to_string(), json!(inclusive));
+                    self.warn(
+                        &pointer,
+                        format!("{} converted to {} {}", key, bound, inclusive),
+                    );
+                }
+                None => {
+                    out.insert(bound.to_string(), Value::Number(value));
+                    self.warn(
+                        &pointer,
+                        format!("{} imported as an inclusive {}", key, bound),
+                    );
+                }
+            }
+        }
+    }
+
+    fn byte_array(out: &mut Map<String, Value>) {
+        out.insert("type".to_string(), json!("array"));
+        out.insert("byteArray".to_string(), json!(true));
+    }
+
+    fn type_of(value: Option<&Value>) -> String {
+        match value {
+            Some(Value::Number(number)) if number.is_f64() => "number",
+            Some(Value::Number(_)) => "integer",
+            Some(Value::Bool(_)) => "boolean",
+            _ => "string",
+        }
+        .to_string()
+    }
+
+    fn copy(schema: &Map<String, Value>, out: &mut Map<String, Value>, keys: &[&str]) {
+        for key in keys {
+            if let Some(value) = schema.get(*key) {
+                out.insert(key.to_string(), value.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod tests {
+    use super::super::json_generator::JsonGenerator;
+    use super::super::schema_import::{AdaptedSchema, SchemaImport, SchemaSource};
+    use super::super::validation::ValidationService;
+    use crate::types::{DataType, Severity};
+    use serde_json::json;
+
+    /// Checks that the adapted contract is ready for validation, as the form would send it
+    fn assert_validates(adapted: &AdaptedSchema) {
+        let json = JsonGenerator::generate_contract(&adapted.parsed.document_types);
+        let errors = ValidationService::validate_schema(&json.to_string()).unwrap();
+        assert!(errors.is_empty(), "validation errors: {:?}", errors);
+    }
+
+    #[test]
+    fn test_openapi_components_are_adapted() {
+        let openapi = json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Shop", "version": "1"},
+            "components": {"schemas": {
+                "Money": {"type": "integer", "minimum": 0},
+                "Tag": {
+                    "type": "object",
+                    "properties": {"label": {"type": "string", "maxLength": 20}},
+                    "required": ["label"]
+                },
+                "Product": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "price": {"$ref": "#/components/schemas/Money", "description": "In duffs"},
+                        "tag": {"$ref": "#/components/schemas/Tag"},
+                        "note": {"type": "string", "nullable": true},
+                        "photo": {"type": "string", "format": "byte"}
+                    },
+                    "required": ["name", "price", "note"],
+                    "indices": [{"name": "byName", "properties": [{"name": "asc"}]}]
+                }
+            }}
+        });
+
+        let adapted = SchemaImport::adapt(&openapi).unwrap();
+        assert_eq!(adapted.source, SchemaSource::OpenApi);
+        assert!(!adapted.parsed.has_errors());
+        assert_validates(&adapted);
+
+        let names: Vec<&str> = adapted
+            .parsed
+            .document_types
+            .iter()
+            .map(|document_type| document_type.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Tag", "Product"]);
+
+        let product = &adapted.parsed.document_types[1];
+        let positions: Vec<(&str, u64)> = product
+            .properties
+            .iter()
+            .map(|property| (property.name.as_str(), property.position))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![
+                ("name", 0),
+                ("price", 1),
+                ("tag", 2),
+                ("note", 3),
+                ("photo", 4)
+            ]
+        );
+        assert_eq!(product.required, vec!["name", "price"]);
+
+        let price = &product.properties[1];
+        assert_eq!(price.data_type, DataType::Integer);
+        assert_eq!(price.description.as_deref(), Some("In duffs"));
+        let tag = product.properties[2].properties.as_ref().unwrap();
+        assert!(tag[0].required);
+        assert_eq!(product.properties[4].byte_array, Some(true));
+        // The indexed string had no maxLength
+        assert_eq!(product.properties[0].max_length, Some(63));
+
+        let warned: Vec<&str> = adapted
+            .parsed
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Warning)
+            .map(|diagnostic| diagnostic.pointer.as_str())
+            .collect();
+        assert_eq!(
+            warned,
+            vec![
+                "",
+                "/Product/properties/name/maxLength",
+                "/Product/properties/note",
+                "/Product/properties/photo",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_schema_constructs_are_converted() {
+        let schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Blog post",
+            "type": "object",
+            "properties": {
+                "author": {"$ref": "#/$defs/person"},
+                "score": {"type": "integer", "exclusiveMinimum": 0},
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "body": {"oneOf": [{"type": "string"}, {"type": "integer"}]},
+                "extra": {"type": "object"}
+            },
+            "additionalProperties": true,
+            "$defs": {
+                "person": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": ["string", "null"]},
+                        "friend": {"$ref": "#/$defs/person"}
+                    }
+                }
+            }
+        });
+
+        let adapted = SchemaImport::adapt(&schema).unwrap();
+        assert_eq!(adapted.source, SchemaSource::JsonSchema);
+        assert_validates(&adapted);
+        let document_type = &adapted.parsed.document_types[0];
+        assert_eq!(document_type.name, "blogPost");
+
+        // The recursive reference is dropped
+        let author = document_type.properties[0].properties.as_ref().unwrap();
+        assert_eq!(author.len(), 1);
+        assert_eq!(author[0].data_type, DataType::String);
+        assert_eq!(
+            document_type.properties[1]
+                .minimum
+                .as_ref()
+                .unwrap()
+                .as_i64(),
+            Some(1)
+        );
+        let tags = document_type.properties[2].items.as_ref().unwrap();
+        assert_eq!(tags.data_type, DataType::String);
+        assert_eq!(document_type.properties[3].data_type, DataType::String);
+        assert_eq!(document_type.properties[4].byte_array, Some(true));
+
+        let messages: Vec<&str> = adapted
+            .parsed
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.message.as_str())
+            .collect();
+        assert_eq!(messages.len(), 5);
+        assert!(messages[0].starts_with("Dash contracts do not allow additional properties"));
+        assert!(messages[1].starts_with("Recursive $ref '#/$defs/person'"));
+    }
+
+    #[test]
+    fn test_contracts_are_not_schemas() {
+        let contract = json!({"note": {"type": "object", "properties": {}}});
+        assert!(SchemaImport::detect(&contract).is_none());
+
+        // Document types may be named like schema keywords
+        let document_type = json!({"type": "object", "properties": {
+            "title": {"type": "string", "position": 0}
+        }, "additionalProperties": false});
+        for name in ["openapi", "swagger", "definitions", "$defs"] {
+            let contract = json!({ name: document_type.clone() });
+            assert!(SchemaImport::detect(&contract).is_none(), "{}", name);
+        }
+        let schema = json!({"definitions": {"note": document_type}});
+        assert_eq!(
+            SchemaImport::detect(&schema),
+            Some(SchemaSource::JsonSchema)
+        );
+
+        let empty = json!({"openapi": "3.1.0", "components": {"schemas": {}}});
+        let adapted = SchemaImport::adapt(&empty).unwrap();
+        assert!(adapted.parsed.has_errors());
+    }
+
+    #[test]
+    fn test_exclusive_bounds_at_integer_limits() {
+        let schema = json!({"type": "object", "properties": {
+            "low": {"type": "integer", "exclusiveMinimum": i64::MAX},
+            "high": {"type": "integer", "exclusiveMaximum": i64::MIN},
+            "near": {"type": "integer", "exclusiveMaximum": i64::MIN + 1}
+        }});
+
+        let adapted = SchemaImport::adapt(&schema).unwrap();
+        let properties = &adapted.parsed.document_types[0].properties;
+        // Without an inclusive neighbour the bound is kept as written
+        assert_eq!(
+            properties[0].minimum.as_ref().unwrap().as_i64(),
+            Some(i64::MAX)
+        );
+        assert_eq!(
+            properties[1].maximum.as_ref().unwrap().as_i64(),
+            Some(i64::MIN)
+        );
+        assert_eq!(
+            properties[2].maximum.as_ref().unwrap().as_i64(),
+            Some(i64::MIN)
+        );
+
+        let messages: Vec<&str> = adapted
+            .parsed
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.message.as_str())
+            .collect();
+        assert!(messages.contains(&"exclusiveMinimum imported as an inclusive minimum"));
+        assert!(messages.contains(&"exclusiveMaximum imported as an inclusive maximum"));
+        assert!(messages
+            .contains(&format!("exclusiveMaximum converted to maximum {}", i64::MIN).as_str()));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::types::DocumentType;
+
+/// Part of a contract that a targeted AI edit may change
+///
+/// Scopes name their target rather than point at form positions, so an edit made
+/// while a request is in flight cannot redirect the reply to another element.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EditScope {
+    /// A whole document type, by name
+    DocumentType(String),
+    /// A top-level property, by document type name and property name
+    Property(String, String),
+}
+
+impl EditScope {
+    /// Suggested instructions for this kind of scope
+    pub fn presets(&self) -> &'static [&'static str] {
+        match self {
+            EditScope::DocumentType(_) => &[
+                "Add sensible constraints",
+                "Write descriptions",
+                "Suggest indices for these queries: ",
+            ],
+            EditScope::Property(_, _) => &["Add sensible constraints", "Write descriptions"],
+        }
+    }
+
+    /// Returns the name of the document type the scope is in
+    pub fn document_type(&self) -> &str {
+        match self {
+            EditScope::DocumentType(doc_name) | EditScope::Property(doc_name, _) => doc_name,
+        }
+    }
+}
+
+/// Service for cutting one subtree out of a contract and patching an edited version back in
+pub struct ScopedEditService;
+
+impl ScopedEditService {
+    /// Returns a readable name for the scope, such as `note.title`, or `None` if it no longer exists
+    pub fn label(document_types: &[DocumentType], scope: &EditScope) -> Option<String> {
+        let doc_index = Self::document_index(document_types, scope).ok()?;
+        match scope {
+            EditScope::DocumentType(doc_name) => Some(doc_name.clone()),
+            EditScope::Property(doc_name, prop_name) => {
+                Self::property_index(&document_types[doc_index], scope).ok()?;
+                Some(format!("{}.{}", doc_name, prop_name))
+            }
+        }
+    }
+
+    /// Returns a contract holding only the scoped subtree
+    ///
+    /// A property is wrapped in a bare document type of the same name so the model
+    /// still sees a valid contract, without sibling properties, indices or settings.
+    pub fn subtree(
+        document_types: &[DocumentType],
+        scope: &EditScope,
+    ) -> Option<Vec<DocumentType>> {
+        let doc = &document_types[Self::document_index(document_types, scope).ok()?];
+        match scope {
+            EditScope::DocumentType(_) => Some(vec![doc.clone()]),
+            EditScope::Property(_, _) => {
+                let mut wrapper = DocumentType::new(doc.name.clone());
+                wrapper
+                    .add_property(doc.properties[Self::property_index(doc, scope).ok()?].clone());
+                wrapper.update_required_properties();
+                Some(vec![wrapper])
+            }
+        }
+    }
+
+    /// Returns a copy of `document_types` with the scoped subtree replaced by its edited version
+    ///
+    /// Names and positions of the scoped subtree are kept, and anything the model
+    /// returned outside of it is ignored. The patch is rejected if the scoped
+    /// document type or property has been renamed or removed since.
+    pub fn patch(
+        document_types: &[DocumentType],
+        scope: &EditScope,
+        edited: &[DocumentType],
+    ) -> Result<Vec<DocumentType>, String> {
+        let doc_index = Self::document_index(document_types, scope)?;
+        let current = &document_types[doc_index];
+        let edited_doc =
+            Self::find_by_name(edited, &current.name, |doc| &doc.name).ok_or_else(|| {
+                format!(
+                    "The response does not contain document type {}",
+                    current.name
+                )
+            })?;
+
+        let mut result = document_types.to_vec();
+        match scope {
+            EditScope::DocumentType(_) => {
+                let mut doc = edited_doc.clone();
+                doc.name = current.name.clone();
+                result[doc_index] = doc;
+            }
+            EditScope::Property(_, _) => {
+                let prop_index = Self::property_index(current, scope)?;
+                let current_prop = &current.properties[prop_index];
+                let edited_prop =
+                    Self::find_by_name(&edited_doc.properties, &current_prop.name, |p| &p.name)
+                        .ok_or_else(|| {
+                            format!(
+                                "The response does not contain property {}",
+                                current_prop.name
+                            )
+                        })?;
+
+                let mut prop = edited_prop.clone();
+                prop.name = current_prop.name.clone();
+                prop.position = current_prop.position;
+                result[doc_index].properties[prop_index] = prop;
+                result[doc_index].update_required_properties();
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Finds the scoped document type in the form
+    fn document_index(document_types: &[DocumentType], scope: &EditScope) -> Result<usize, String> {
+        let doc_name = scope.document_type();
+        document_types
+            .iter()
+            .position(|doc| doc.name == doc_name)
+            .ok_or_else(|| format!("Document type {} was renamed or removed", doc_name))
+    }
+
+    /// Finds the scoped property in its document type
+    fn property_index(doc: &DocumentType, scope: &EditScope) -> Result<usize, String> {
+        let EditScope::Property(_, prop_name) = scope else {
+            return Err("The edit is not limited to a property".to_string());
+        };
+        doc.properties
+            .iter()
+            .position(|prop| &prop.name == prop_name)
+            .ok_or_else(|| format!("Property {}.{} was renamed or removed", doc.name, prop_name))
+    }
+
+    /// Finds the item with the given name in a reply, or the only item if the model renamed it
+    fn find_by_name<'a, T>(
+        items: &'a [T],
+        name: &str,
+        name_of: impl Fn(&T) -> &String,
+    ) -> Option<&'a T> {
+        items
+            .iter()
+            .find(|item| name_of(item) == name)
+            .or(if items.len() == 1 {
+                items.first()
+            } else {
+                None
+            })
+    }
+}
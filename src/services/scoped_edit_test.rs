@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use super::super::scoped_edit::{EditScope, ScopedEditService};
+    use crate::types::{DataType, DocumentType, Index, Property};
+
+    fn contract() -> Vec<DocumentType> {
+        let mut note = DocumentType::new("note".to_string());
+        let mut title = Property::new("title".to_string(), DataType::String);
+        title.required = true;
+        let mut body = Property::new("body".to_string(), DataType::String);
+        body.position = 1;
+        note.properties = vec![title, body];
+        note.update_required_properties();
+        let mut index = Index::new("byTitle".to_string());
+        index.add_property("title".to_string());
+        note.indices.push(index);
+        vec![note, DocumentType::new("folder".to_string())]
+    }
+
+    fn title_scope() -> EditScope {
+        EditScope::Property("note".to_string(), "title".to_string())
+    }
+
+    #[test]
+    fn test_property_subtree_excludes_siblings() {
+        let subtree = ScopedEditService::subtree(&contract(), &title_scope()).unwrap();
+
+        assert_eq!(subtree.len(), 1);
+        assert_eq!(subtree[0].name, "note");
+        assert_eq!(subtree[0].properties.len(), 1);
+        assert_eq!(subtree[0].properties[0].name, "title");
+        assert_eq!(subtree[0].required, vec!["title"]);
+        assert!(subtree[0].indices.is_empty());
+    }
+
+    #[test]
+    fn test_patch_property_keeps_name_position_and_rest() {
+        let current = contract();
+        // The model renamed the property, moved it and added an unrelated one
+        let mut edited = DocumentType::new("note".to_string());
+        let mut renamed = Property::new("heading".to_string(), DataType::String);
+        renamed.position = 5;
+        renamed.max_length = Some(63);
+        edited.properties = vec![renamed];
+        edited.indices = vec![Index::new("extra".to_string())];
+
+        let result = ScopedEditService::patch(&current, &title_scope(), &[edited]).unwrap();
+
+        let title = &result[0].properties[0];
+        assert_eq!(title.name, "title");
+        assert_eq!(title.position, 0);
+        assert_eq!(title.max_length, Some(63));
+        assert!(result[0].required.is_empty());
+        assert_eq!(result[0].properties[1].name, "body");
+        assert_eq!(result[0].indices.len(), 1);
+        assert_eq!(result[1].name, "folder");
+    }
+
+    #[test]
+    fn test_patch_document_type_requires_matching_name() {
+        let current = contract();
+        let mut edited = current[0].clone();
+        edited.description = "A note".to_string();
+        let other = DocumentType::new("other".to_string());
+
+        let result = ScopedEditService::patch(
+            &current,
+            &EditScope::DocumentType("note".to_string()),
+            &[other.clone(), edited],
+        )
+        .unwrap();
+        assert_eq!(result[0].description, "A note");
+        assert_eq!(result.len(), 2);
+
+        let missing = ScopedEditService::patch(
+            &current,
+            &EditScope::DocumentType("note".to_string()),
+            &[other.clone(), other],
+        );
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_patch_rejected_after_form_changes() {
+        let mut current = contract();
+        let mut edited = current[0].clone();
+        edited.properties[0].max_length = Some(63);
+
+        // Reordering keeps the edit on the scoped property
+        current[0].properties.swap(0, 1);
+        let result = ScopedEditService::patch(&current, &title_scope(), &[edited.clone()]).unwrap();
+        assert_eq!(result[0].properties[1].max_length, Some(63));
+        assert_eq!(result[0].properties[0].max_length, None);
+
+        // Renaming the target rejects the reply instead of patching a neighbour
+        current[0].properties[1].name = "heading".to_string();
+        let error = ScopedEditService::patch(&current, &title_scope(), &[edited.clone()]);
+        assert_eq!(
+            error.unwrap_err(),
+            "Property note.title was renamed or removed"
+        );
+        assert_eq!(ScopedEditService::label(&current, &title_scope()), None);
+
+        current.remove(0);
+        let error = ScopedEditService::patch(
+            &current,
+            &EditScope::DocumentType("note".to_string()),
+            &[edited],
+        );
+        assert_eq!(
+            error.unwrap_err(),
+            "Document type note was renamed or removed"
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use crate::types::{Diagnostic, Severity};
+use std::collections::HashMap;
+
+/// Byte ranges of the values in a JSON text, keyed by JSON pointer
+///
+/// `serde_json::Value` does not keep positions, so the text is scanned once more
+/// to point diagnostics at the right place.
+#[derive(Debug, Clone, Default)]
+pub struct SourceLocations {
+    spans: HashMap<String, (usize, usize)>,
+}
+
+impl SourceLocations {
+    /// Records the span of every value in a text that `serde_json` accepts
+    pub fn scan(text: &str) -> Self {
+        let mut scanner = Scanner {
+            text,
+            bytes: text.as_bytes(),
+            pos: 0,
+            spans: HashMap::new(),
+        };
+        scanner.value(String::new());
+        Self {
+            spans: scanner.spans,
+        }
+    }
+
+    /// Returns the span of the value at `pointer`, or of its nearest enclosing value
+    pub fn find(&self, pointer: &str) -> (usize, usize) {
+        let mut pointer = pointer;
+        loop {
+            if let Some(span) = self.spans.get(pointer) {
+                return *span;
+            }
+            match pointer.rfind('/') {
+                Some(slash) => pointer = &pointer[..slash],
+                None => return (0, 0),
+            }
+        }
+    }
+
+    /// Creates a diagnostic located at the value at `pointer` in the scanned text
+    pub fn diagnostic(
+        &self,
+        text: &str,
+        severity: Severity,
+        pointer: &str,
+        message: String,
+    ) -> Diagnostic {
+        let (start, end) = self.find(pointer);
+        let (line, column) = Self::line_column(text, start);
+        Diagnostic {
+            severity,
+            message,
+            pointer: pointer.to_string(),
+            line,
+            column,
+            start,
+            end,
+        }
+    }
+
+    /// Appends an object key or array index to a JSON pointer
+    pub fn child(pointer: &str, token: &str) -> String {
+        format!(
+            "{}/{}",
+            pointer,
+            token.replace('~', "~0").replace('/', "~1")
+        )
+    }
+
+    /// Returns the 1-based line and column of a byte offset, counting characters
+    pub fn line_column(text: &str, offset: usize) -> (usize, usize) {
+        let before = &text[..Self::floor_boundary(text, offset)];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        (line, before[line_start..].chars().count() + 1)
+    }
+
+    /// Returns the byte offset of a 1-based line and column, as reported by `serde_json`
+    pub fn offset(text: &str, line: usize, column: usize) -> usize {
+        let line_start: usize = text
+            .split_inclusive('\n')
+            .take(line.saturating_sub(1))
+            .map(str::len)
+            .sum();
+        Self::floor_boundary(text, line_start + column.saturating_sub(1))
+    }
+
+    /// Moves an offset back to the nearest character boundary within the text
+    pub fn floor_boundary(text: &str, offset: usize) -> usize {
+        let mut offset = offset.min(text.len());
+        while !text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+}
+
+struct Scanner<'a> {
+    text: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    spans: HashMap<String, (usize, usize)>,
+}
+
+impl Scanner<'_> {
+    fn value(&mut self, pointer: String) {
+        self.skip_whitespace();
+        let start = self.pos;
+
+        match self.bytes.get(self.pos) {
+            Some(b'{') => {
+                self.pos += 1;
+                loop {
+                    self.skip_whitespace();
+                    if self.bytes.get(self.pos) != Some(&b'"') {
+                        // Empty object, or text serde_json would have rejected
+                        self.pos += usize::from(self.pos < self.bytes.len());
+                        break;
+                    }
+                    let key_start = self.pos;
+                    self.string();
+                    let key: String =
+                        serde_json::from_str(&self.text[key_start..self.pos]).unwrap_or_default();
+                    self.skip_whitespace();
+                    if self.bytes.get(self.pos) != Some(&b':') {
+                        break;
+                    }
+                    self.pos += 1;
+                    self.value(SourceLocations::child(&pointer, &key));
+                    if !self.separator(b'}') {
+                        break;
+                    }
+                }
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                self.skip_whitespace();
+                if self.bytes.get(self.pos) == Some(&b']') {
+                    self.pos += 1;
+                } else {
+                    let mut index = 0;
+                    loop {
+                        self.value(SourceLocations::child(&pointer, &index.to_string()));
+                        index += 1;
+                        if !self.separator(b']') {
+                            break;
+                        }
+                    }
+                }
+            }
+            Some(b'"') => self.string(),
+            Some(_) => {
+                while self.pos < self.bytes.len()
+                    && !matches!(
+                        self.bytes[self.pos],
+                        b',' | b'}' | b']' | b' ' | b'\t' | b'\r' | b'\n'
+                    )
+                {
+                    self.pos += 1;
+                }
+            }
+            None => return,
+        }
+
+        self.spans.insert(pointer, (start, self.pos));
+    }
+
+    /// Consumes a comma, returning true, or the closing bracket, returning false
+    fn separator(&mut self, close: u8) -> bool {
+        self.skip_whitespace();
+        match self.bytes.get(self.pos) {
+            Some(b',') => {
+                self.pos += 1;
+                true
+            }
+            Some(byte) if *byte == close => {
+                self.pos += 1;
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn string(&mut self) {
+        self.pos += 1;
+        while let Some(byte) = self.bytes.get(self.pos) {
+            match byte {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return;
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|byte| matches!(byte, b' ' | b'\t' | b'\r' | b'\n'))
+        {
+            self.pos += 1;
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use crate::types::{DataType, DocumentType, Index, Property};
+use crate::utils::{to_pascal_case, IDENTIFIER_MEDIA_TYPE};
+use serde_json::Value;
+
+/// Header shared by every generated module: SDK types and the contract alias
+const TYPESCRIPT_PRELUDE: &str = r#"// Types and helpers generated from a Dash Platform data contract
+import Dash from 'dash';
+
+type Client = InstanceType<typeof Dash.Client>;
+type Identity = NonNullable<Awaited<ReturnType<Client['platform']['identities']['get']>>>;
+type PlatformDocument = Awaited<ReturnType<Client['platform']['documents']['create']>>;
+type WhereClause = [string, '==', unknown];
+
+/** Name the contract is registered under in the client's `apps` option */
+export const CONTRACT_APP_NAME = 'contract';
+
+/** Paging options accepted by the query helpers */
+export interface QueryOptions {
+  limit?: number;
+  startAt?: Uint8Array;
+  startAfter?: Uint8Array;
+}
+"#;
+
+/// Service for generating TypeScript definitions and Dash JS SDK helpers
+pub struct TypeScriptGenerator;
+
+impl TypeScriptGenerator {
+    /// Generates a TypeScript module with interfaces and helpers for each document type
+    pub fn generate(document_types: &[DocumentType]) -> String {
+        let mut output = String::from(TYPESCRIPT_PRELUDE);
+
+        for doc_type in document_types.iter().filter(|d| !d.name.is_empty()) {
+            let interface_name = to_pascal_case(&doc_type.name);
+
+            output.push('\n');
+            Self::write_interface(
+                &mut output,
+                &interface_name,
+                Some(doc_type.description.as_str()),
+                &doc_type.properties,
+            );
+            output.push('\n');
+            Self::write_create_helper(&mut output, doc_type, &interface_name);
+
+            for index in doc_type
+                .indices
+                .iter()
+                .filter(|index| !index.name.is_empty() && !index.properties.is_empty())
+            {
+                output.push('\n');
+                Self::write_query_helper(&mut output, doc_type, &interface_name, index);
+            }
+        }
+
+        output
+    }
+
+    /// Writes an interface, followed by interfaces for any nested objects
+    fn write_interface(
+        output: &mut String,
+        name: &str,
+        description: Option<&str>,
+        properties: &[Property],
+    ) {
+        let mut nested = Vec::new();
+
+        Self::write_doc_comment(output, description.unwrap_or_default(), "");
+        output.push_str(&format!("export interface {} {{\n", name));
+
+        for prop in properties.iter().filter(|p| !p.name.is_empty()) {
+            Self::write_doc_comment(
+                output,
+                prop.description.as_deref().unwrap_or_default(),
+                "  ",
+            );
+
+            let ts_type = match prop.items.as_deref() {
+                _ if prop.data_type == DataType::Object => {
+                    let nested_name = format!("{}{}", name, to_pascal_case(&prop.name));
+                    nested.push((nested_name.clone(), prop));
+                    nested_name
+                }
+                Some(items)
+                    if prop.data_type == DataType::Array && items.data_type == DataType::Object =>
+                {
+                    let nested_name = format!("{}{}Item", name, to_pascal_case(&prop.name));
+                    nested.push((nested_name.clone(), items));
+                    format!("Array<{}>", nested_name)
+                }
+                _ => Self::ts_type(prop),
+            };
+
+            let optional = if prop.required { "" } else { "?" };
+            output.push_str(&format!(
+                "  {}{}: {};\n",
+                Self::property_key(&prop.name),
+                optional,
+                ts_type
+            ));
+        }
+
+        output.push_str("}\n");
+
+        for (nested_name, prop) in nested {
+            let nested_props = prop.properties.as_deref().map(Vec::as_slice).unwrap_or(&[]);
+            output.push('\n');
+            Self::write_interface(
+                output,
+                &nested_name,
+                prop.description.as_deref(),
+                nested_props,
+            );
+        }
+    }
+
+    /// Returns the TypeScript type for a non-object property
+    fn ts_type(prop: &Property) -> String {
+        if let Some(ref enum_values) = prop.enum_values {
+            if !enum_values.is_empty() {
+                return enum_values
+                    .iter()
+                    .map(Self::literal)
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+            }
+        }
+
+        match prop.data_type {
+            DataType::String => "string".to_string(),
+            DataType::Integer | DataType::Number => "number".to_string(),
+            DataType::Boolean => "boolean".to_string(),
+            DataType::Array => match prop.items.as_deref() {
+                Some(items) => format!("Array<{}>", Self::ts_type(items)),
+                None => "Uint8Array".to_string(),
+            },
+            DataType::Object => "Record<string, unknown>".to_string(),
+        }
+    }
+
+    /// Returns the TypeScript type used to query a field of an index
+    fn query_field_type(doc_type: &DocumentType, field: &str) -> String {
+        match field {
+            "$ownerId" | "$id" => "Uint8Array | string".to_string(),
+            "$createdAt" | "$updatedAt" => "number".to_string(),
+            _ => doc_type
+                .properties
+                .iter()
+                .find(|p| p.name == field)
+                .map(|p| {
+                    let ts_type = Self::ts_type(p);
+                    // Identifiers may also be given as base58 strings
+                    if p.content_media_type.as_deref() == Some(IDENTIFIER_MEDIA_TYPE) {
+                        format!("{} | string", ts_type)
+                    } else {
+                        ts_type
+                    }
+                })
+                .unwrap_or_else(|| "unknown".to_string()),
+        }
+    }
+
+    /// Writes a helper that creates and broadcasts a document
+    fn write_create_helper(output: &mut String, doc_type: &DocumentType, interface_name: &str) {
+        output.push_str(&format!(
+            "/** Creates and broadcasts a `{name}` document owned by `identity` */\n\
+             export async function create{iface}(\n  \
+             client: Client,\n  \
+             identity: Identity,\n  \
+             data: {iface},\n\
+             ): Promise<PlatformDocument> {{\n  \
+             const document = await client.platform.documents.create(\n    \
+             `${{CONTRACT_APP_NAME}}.{name}`,\n    \
+             identity,\n    \
+             data,\n  \
+             );\n  \
+             await client.platform.documents.broadcast({{ create: [document] }}, identity);\n  \
+             return document;\n\
+             }}\n",
+            name = doc_type.name,
+            iface = interface_name,
+        ));
+    }
+
+    /// Writes a query type and helper restricted to the fields of one index
+    fn write_query_helper(
+        output: &mut String,
+        doc_type: &DocumentType,
+        interface_name: &str,
+        index: &Index,
+    ) {
+        let suffix = format!("By{}", to_pascal_case(&index.name));
+        let query_name = format!("{}{}Query", interface_name, suffix);
+        let fields: Vec<&str> = index
+            .properties
+            .iter()
+            .map(|p| p.field())
+            .filter(|f| !f.is_empty())
+            .collect();
+
+        output.push_str(&format!(
+            "/** Fields of the `{}` index; supply them as a prefix in index order */\n",
+            index.name
+        ));
+        output.push_str(&format!("export interface {} {{\n", query_name));
+        for field in &fields {
+            output.push_str(&format!(
+                "  {}?: {};\n",
+                Self::property_key(field),
+                Self::query_field_type(doc_type, field)
+            ));
+        }
+        output.push_str("}\n\n");
+
+        let unique = if index.unique { " (unique)" } else { "" };
+        output.push_str(&format!(
+            "/** Queries `{}` documents using the `{}` index{} */\n",
+            doc_type.name, index.name, unique
+        ));
+        output.push_str(&format!(
+            "export async function query{}{}(\n  \
+             client: Client,\n  \
+             query: {},\n  \
+             options: QueryOptions = {{}},\n\
+             ): Promise<PlatformDocument[]> {{\n  \
+             const where: WhereClause[] = [];\n",
+            interface_name, suffix, query_name
+        ));
+        for field in &fields {
+            output.push_str(&format!(
+                "  if (query{accessor} !== undefined) where.push([{field}, '==', query{accessor}]);\n",
+                accessor = Self::property_accessor(field),
+                field = Self::string_literal(field)
+            ));
+        }
+        let order_by = fields
+            .iter()
+            .map(|field| format!("[{}, 'asc']", Self::string_literal(field)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        output.push_str(&format!(
+            "  return client.platform.documents.get(`${{CONTRACT_APP_NAME}}.{}`, {{\n    \
+             where,\n    \
+             orderBy: [{}],\n    \
+             ...options,\n  \
+             }});\n\
+             }}\n",
+            doc_type.name, order_by
+        ));
+    }
+
+    /// Formats an enum value as a TypeScript literal type
+    fn literal(value: &Value) -> String {
+        match value {
+            Value::String(s) => Self::string_literal(s),
+            other => other.to_string(),
+        }
+    }
+
+    /// Quotes text as a single-quoted string literal
+    fn string_literal(text: &str) -> String {
+        let escaped = text
+            .replace('\\', "\\\\")
+            .replace('\'', "\\'")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r");
+        format!("'{}'", escaped)
+    }
+
+    /// Returns a property key, quoted when it is not a valid identifier
+    fn property_key(name: &str) -> String {
+        if Self::is_identifier(name) {
+            name.to_string()
+        } else {
+            Self::string_literal(name)
+        }
+    }
+
+    /// Returns the member access expression for a property
+    fn property_accessor(name: &str) -> String {
+        if Self::is_identifier(name) {
+            format!(".{}", name)
+        } else {
+            format!("[{}]", Self::string_literal(name))
+        }
+    }
+
+    fn is_identifier(name: &str) -> bool {
+        let mut chars = name.chars();
+        matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+    }
+
+    /// Writes a description as a JSDoc comment
+    fn write_doc_comment(output: &mut String, description: &str, indent: &str) {
+        // A literal `*/` would end the comment early
+        let lines: Vec<String> = description
+            .lines()
+            .map(|l| l.trim_end().replace("*/", "*\\/"))
+            .filter(|l| !l.is_empty())
+            .collect();
+
+        match lines.as_slice() {
+            [] => {}
+            [line] => output.push_str(&format!("{}/** {} */\n", indent, line)),
+            _ => {
+                output.push_str(&format!("{}/**\n", indent));
+                for line in lines {
+                    output.push_str(&format!("{} * {}\n", indent, line));
+                }
+                output.push_str(&format!("{} */\n", indent));
+            }
+        }
+    }
+}
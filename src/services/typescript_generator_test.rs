@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use super::super::json_parser::JsonParser;
+    use super::super::typescript_generator::TypeScriptGenerator;
+
+    const CONTRACT: &str = r#"{"post": {"type": "object", "properties": {
+        "title": {"type": "string", "position": 0, "maxLength": 63},
+        "status": {"type": "string", "position": 1, "enum": ["draft", "it's live"]},
+        "rating": {"type": "integer", "position": 2, "enum": [1, 2, 3]},
+        "hash": {"type": "array", "position": 3, "byteArray": true, "maxItems": 32},
+        "tags": {"type": "array", "position": 4, "items": {"type": "string"}},
+        "author": {"type": "object", "position": 5,
+            "properties": {"name": {"type": "string", "position": 0}},
+            "required": ["name"], "additionalProperties": false},
+        "links": {"type": "array", "position": 6, "items": {"type": "object",
+            "properties": {"url": {"type": "string", "position": 0}},
+            "additionalProperties": false}},
+        "owner": {"type": "array", "position": 7, "byteArray": true, "minItems": 32, "maxItems": 32,
+            "contentMediaType": "application/x.dash.dpp.identifier"}
+    }, "indices": [{"name": "ownerTitle", "properties": [{"owner": "asc"}, {"title": "asc"}]}],
+    "required": ["title"], "additionalProperties": false}}"#;
+
+    fn generate() -> String {
+        TypeScriptGenerator::generate(&JsonParser::parse_contract(CONTRACT).unwrap())
+    }
+
+    #[test]
+    fn test_interface_fields() {
+        let output = generate();
+
+        assert!(output.contains("export interface Post {\n"));
+        assert!(output.contains("  title: string;\n"));
+        assert!(output.contains("  status?: 'draft' | 'it\\'s live';\n"));
+        assert!(output.contains("  rating?: 1 | 2 | 3;\n"));
+        assert!(output.contains("  hash?: Uint8Array;\n"));
+        assert!(output.contains("  tags?: Array<string>;\n"));
+        assert!(output.contains("  author?: PostAuthor;\n"));
+        assert!(output.contains("  links?: Array<PostLinksItem>;\n"));
+        assert!(output.contains("export interface PostAuthor {\n  name: string;\n}\n"));
+        assert!(output.contains("export interface PostLinksItem {\n  url?: string;\n}\n"));
+        assert!(output.contains("export async function createPost(\n"));
+    }
+
+    #[test]
+    fn test_query_restricted_to_index_fields() {
+        let output = generate();
+        let query = output
+            .split("export interface PostByOwnerTitleQuery {\n")
+            .nth(1)
+            .and_then(|rest| rest.split("}\n").next())
+            .unwrap();
+
+        // Identifiers may be queried by their base58 string
+        assert_eq!(query, "  owner?: Uint8Array | string;\n  title?: string;\n");
+        assert!(output.contains("export async function queryPostByOwnerTitle(\n"));
+        assert!(output.contains("orderBy: [['owner', 'asc'], ['title', 'asc']],"));
+    }
+
+    #[test]
+    fn test_quoted_names_escaped() {
+        let output = TypeScriptGenerator::generate(
+            &JsonParser::parse_contract(
+                r#"{"note": {"type": "object", "properties": {
+                    "it's": {"type": "string", "position": 0, "maxLength": 63},
+                    "back\\slash": {"type": "string", "position": 1, "maxLength": 63}
+                }, "indices": [{"name": "quoted", "properties": [{"it's": "asc"}, {"back\\slash": "asc"}]}],
+                "additionalProperties": false}}"#,
+            )
+            .unwrap(),
+        );
+
+        assert!(output.contains("  'it\\'s'?: string;\n"));
+        assert!(output.contains("  'back\\\\slash'?: string;\n"));
+        assert!(output.contains(
+            "  if (query['it\\'s'] !== undefined) where.push(['it\\'s', '==', query['it\\'s']]);\n"
+        ));
+        assert!(output.contains("orderBy: [['it\\'s', 'asc'], ['back\\\\slash', 'asc']],"));
+    }
+}
@@ -86,7 +86,7 @@ impl ValidationService {
                         if json_error.error_summary().contains("\"items\" is a required property") {
                             Some(ValidationError::schema_error(
                                 json_error.instance_path().to_string(),
-                                "Array properties must specify \"byteArray\": true. In the dynamic form, just change the property from an array to a string and back to an array again, and resubmit.".to_string(),
+                                "Array properties must either be byte arrays or specify items. In the dynamic form, choose what the array holds in its optional fields, and resubmit.".to_string(),
                             ))
                         } else {
                             Some(ValidationError::schema_error(
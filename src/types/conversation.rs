@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+/// Author of a chat message
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRole {
+    System,
+    User,
+    Assistant,
+}
+
+/// A single message sent to the chat completions API
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    pub content: String,
+}
+
+impl ChatMessage {
+    /// Creates a system message
+    pub fn system(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::System,
+            content: content.into(),
+        }
+    }
+
+    /// Creates a user message
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::User,
+            content: content.into(),
+        }
+    }
+
+    /// Creates an assistant message
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::Assistant,
+            content: content.into(),
+        }
+    }
+}
+
+/// One completed AI request and the contract it produced
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversationTurn {
+    pub id: usize,
+    /// Turn this one continued from, or `None` for the first turn of a branch
+    pub parent: Option<usize>,
+    /// Prompt as typed by the user
+    pub prompt: String,
+    /// User message content actually sent, including any context
+    pub request: String,
+    /// Contract JSON produced by this turn
+    pub schema: String,
+}
+
+/// Tree of AI turns; new turns continue from the head, so earlier turns can be branched from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Conversation {
+    pub turns: Vec<ConversationTurn>,
+    pub head: Option<usize>,
+}
+
+impl Conversation {
+    /// Returns true if no turns have been made
+    pub fn is_empty(&self) -> bool {
+        self.turns.is_empty()
+    }
+
+    /// Returns a turn by id
+    pub fn turn(&self, id: usize) -> Option<&ConversationTurn> {
+        self.turns.iter().find(|turn| turn.id == id)
+    }
+
+    /// Returns the turns leading to and including `id`, oldest first
+    pub fn path_to(&self, id: usize) -> Vec<&ConversationTurn> {
+        let mut path = Vec::new();
+        let mut current = self.turn(id);
+        while let Some(turn) = current {
+            path.push(turn);
+            current = turn.parent.and_then(|parent| self.turn(parent));
+        }
+        path.reverse();
+        path
+    }
+
+    /// Returns the turns leading to the head, oldest first
+    pub fn active_path(&self) -> Vec<&ConversationTurn> {
+        self.head.map(|head| self.path_to(head)).unwrap_or_default()
+    }
+
+    /// Returns the contract JSON produced by the head turn
+    pub fn head_schema(&self) -> Option<&str> {
+        self.head
+            .and_then(|head| self.turn(head))
+            .map(|turn| turn.schema.as_str())
+    }
+
+    /// Returns the number of turns between a turn and the root
+    pub fn depth(&self, id: usize) -> usize {
+        self.path_to(id).len().saturating_sub(1)
+    }
+
+    /// Appends a turn continuing from the head and makes it the new head
+    pub fn push_turn(&mut self, prompt: String, request: String, schema: String) -> usize {
+        let id = self.turns.len();
+        self.turns.push(ConversationTurn {
+            id,
+            parent: self.head,
+            prompt,
+            request,
+            schema,
+        });
+        self.head = Some(id);
+        id
+    }
+
+    /// Moves the head to an earlier turn so the next turn branches from it
+    pub fn branch_from(&mut self, id: usize) {
+        if self.turn(id).is_some() {
+            self.head = Some(id);
+        }
+    }
+
+    /// Returns turns in depth-first order, children after their parent
+    pub fn tree_order(&self) -> Vec<&ConversationTurn> {
+        let mut ordered = Vec::new();
+        self.collect_children(None, &mut ordered);
+        ordered
+    }
+
+    fn collect_children<'a>(
+        &'a self,
+        parent: Option<usize>,
+        ordered: &mut Vec<&'a ConversationTurn>,
+    ) {
+        for turn in self.turns.iter().filter(|turn| turn.parent == parent) {
+            ordered.push(turn);
+            self.collect_children(Some(turn.id), ordered);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use super::super::conversation::{Conversation, ConversationTurn};
+
+    fn push(conversation: &mut Conversation, prompt: &str) -> usize {
+        conversation.push_turn(
+            prompt.to_string(),
+            format!("request: {}", prompt),
+            format!("{{\"{}\": {{}}}}", prompt),
+        )
+    }
+
+    fn prompts(turns: &[&ConversationTurn]) -> Vec<String> {
+        turns.iter().map(|turn| turn.prompt.clone()).collect()
+    }
+
+    #[test]
+    fn test_turns_continue_from_head() {
+        let mut conversation = Conversation::default();
+        assert!(conversation.is_empty());
+        assert_eq!(conversation.head_schema(), None);
+        assert!(conversation.active_path().is_empty());
+
+        let first = push(&mut conversation, "post");
+        let second = push(&mut conversation, "like");
+
+        assert_eq!(conversation.head, Some(second));
+        assert_eq!(conversation.turn(second).unwrap().parent, Some(first));
+        assert_eq!(conversation.head_schema(), Some("{\"like\": {}}"));
+        assert_eq!(prompts(&conversation.active_path()), ["post", "like"]);
+        assert_eq!(conversation.depth(second), 1);
+    }
+
+    #[test]
+    fn test_branch_from_earlier_turn() {
+        let mut conversation = Conversation::default();
+        let first = push(&mut conversation, "post");
+        push(&mut conversation, "like");
+        let third = push(&mut conversation, "comment");
+
+        conversation.branch_from(first);
+        assert_eq!(conversation.head_schema(), Some("{\"post\": {}}"));
+        let branch = push(&mut conversation, "tag");
+        let after_branch = push(&mut conversation, "vote");
+
+        assert_eq!(conversation.turn(branch).unwrap().parent, Some(first));
+        assert_eq!(
+            prompts(&conversation.active_path()),
+            ["post", "tag", "vote"]
+        );
+        // The abandoned branch is kept and still reachable
+        assert_eq!(
+            prompts(&conversation.path_to(third)),
+            ["post", "like", "comment"]
+        );
+        assert_eq!(conversation.depth(after_branch), 2);
+        assert_eq!(
+            prompts(&conversation.tree_order()),
+            ["post", "like", "comment", "tag", "vote"]
+        );
+
+        // Unknown turns leave the head alone
+        conversation.branch_from(99);
+        assert_eq!(conversation.head, Some(after_branch));
+    }
+}
@@ -0,0 +1,40 @@
+/// How serious a problem found while importing a contract is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    /// The contract cannot be imported
+    Error,
+    /// The contract is imported, but something was dropped or guessed
+    Warning,
+}
+
+/// A problem in pasted contract text, with where it was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// JSON pointer to the value, such as `/note/indices/0`
+    pub pointer: String,
+    /// 1-based line and column, counted in characters
+    pub line: usize,
+    pub column: usize,
+    /// Byte range of the value in the text
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Diagnostic {
+    /// Returns true if the problem prevents importing
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// Returns a formatted message with the location for display
+    pub fn display_message(&self) -> String {
+        let location = format!("Line {}, column {}", self.line, self.column);
+        if self.pointer.is_empty() {
+            format!("{}: {}", location, self.message)
+        } else {
+            format!("{} ({}): {}", location, self.pointer, self.message)
+        }
+    }
+}
@@ -1,10 +1,23 @@
 /// Data types and structures for the Data Contract Creator
+pub mod conversation;
+pub mod diagnostic;
 pub mod document_type;
 pub mod index;
+pub mod output_format;
 pub mod property;
+pub mod prompt_template;
 pub mod validation;
 
+#[cfg(test)]
+mod conversation_test;
+#[cfg(test)]
+mod property_test;
+
+pub use conversation::{ChatMessage, ChatRole, Conversation, ConversationTurn};
+pub use diagnostic::{Diagnostic, Severity};
 pub use document_type::DocumentType;
 pub use index::{Index, IndexProperties};
-pub use property::{DataType, Property};
+pub use output_format::{KeyOrder, OutputFormat, PropertyOrder, DEFAULT_INDENT, MAX_INDENT};
+pub use property::{DataType, PathStep, Property};
+pub use prompt_template::{PromptPart, PromptTemplate};
 pub use validation::ValidationError;
@@ -0,0 +1,133 @@
+use crate::utils::{load_local, save_local};
+use serde::{Deserialize, Serialize};
+
+/// Local storage key of the saved output format
+const STORAGE_KEY: &str = "data-contract-creator.output-format";
+
+/// Indentation used unless the user picks another
+pub const DEFAULT_INDENT: usize = 2;
+
+/// Largest indentation offered for generated JSON
+pub const MAX_INDENT: usize = 8;
+
+/// Order of object keys in generated JSON, other than property names
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum KeyOrder {
+    /// Document types sorted by name, as DPP keeps them, and keywords as written by the form
+    Dpp,
+    /// Every key sorted by its characters
+    Alphabetical,
+    /// Document types in form order and keywords in the order of the canonical form
+    #[default]
+    AsAuthored,
+}
+
+impl KeyOrder {
+    /// Returns all orders in display order
+    pub fn all() -> Vec<KeyOrder> {
+        vec![KeyOrder::Dpp, KeyOrder::Alphabetical, KeyOrder::AsAuthored]
+    }
+
+    /// Returns the value used by the format selector
+    pub fn id(&self) -> &'static str {
+        match self {
+            KeyOrder::Dpp => "dpp",
+            KeyOrder::Alphabetical => "alphabetical",
+            KeyOrder::AsAuthored => "authored",
+        }
+    }
+
+    /// Returns the order with the given selector value
+    pub fn from_id(id: &str) -> Option<KeyOrder> {
+        Self::all().into_iter().find(|order| order.id() == id)
+    }
+
+    /// Returns the label shown in the format selector
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeyOrder::Dpp => "DPP canonical",
+            KeyOrder::Alphabetical => "Alphabetical",
+            KeyOrder::AsAuthored => "As authored",
+        }
+    }
+}
+
+/// Order of the entries of `properties` objects in generated JSON
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum PropertyOrder {
+    #[default]
+    Position,
+    Name,
+}
+
+impl PropertyOrder {
+    /// Returns all orders in display order
+    pub fn all() -> Vec<PropertyOrder> {
+        vec![PropertyOrder::Position, PropertyOrder::Name]
+    }
+
+    /// Returns the value used by the format selector
+    pub fn id(&self) -> &'static str {
+        match self {
+            PropertyOrder::Position => "position",
+            PropertyOrder::Name => "name",
+        }
+    }
+
+    /// Returns the order with the given selector value
+    pub fn from_id(id: &str) -> Option<PropertyOrder> {
+        Self::all().into_iter().find(|order| order.id() == id)
+    }
+
+    /// Returns the label shown in the format selector
+    pub fn label(&self) -> &'static str {
+        match self {
+            PropertyOrder::Position => "By position",
+            PropertyOrder::Name => "By name",
+        }
+    }
+}
+
+/// How generated contract JSON is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputFormat {
+    pub key_order: KeyOrder,
+    pub property_order: PropertyOrder,
+    /// Spaces per level, or 0 for JSON on a single line
+    pub indent: usize,
+    /// Whether `additionalProperties: false` and `unique: false` are written when not set
+    pub explicit_defaults: bool,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        Self {
+            key_order: KeyOrder::default(),
+            property_order: PropertyOrder::default(),
+            indent: DEFAULT_INDENT,
+            explicit_defaults: false,
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Loads the format saved in local storage
+    pub fn load() -> Self {
+        load_local(STORAGE_KEY)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Saves the format to local storage
+    pub fn store(&self) -> Result<(), String> {
+        let json = serde_json::to_string(self)
+            .map_err(|e| format!("Failed to serialize output format: {}", e))?;
+        save_local(STORAGE_KEY, &json)
+    }
+
+    /// Returns the same format on a single line
+    pub fn compact(&self) -> Self {
+        Self { indent: 0, ..*self }
+    }
+}
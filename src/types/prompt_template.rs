@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+/// One of the prompts a template provides
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptPart {
+    System,
+    NewContract,
+    ChangeContract,
+    Repair,
+    Scoped,
+    Descriptions,
+}
+
+impl PromptPart {
+    /// Returns all parts in display order
+    pub fn all() -> Vec<PromptPart> {
+        vec![
+            PromptPart::System,
+            PromptPart::NewContract,
+            PromptPart::ChangeContract,
+            PromptPart::Repair,
+            PromptPart::Scoped,
+            PromptPart::Descriptions,
+        ]
+    }
+
+    /// Returns the label shown in the template editor
+    pub fn label(&self) -> &'static str {
+        match self {
+            PromptPart::System => "System prompt",
+            PromptPart::NewContract => "New contract",
+            PromptPart::ChangeContract => "Change contract",
+            PromptPart::Repair => "Repair validation errors",
+            PromptPart::Scoped => "Edit one part",
+            PromptPart::Descriptions => "Suggest descriptions",
+        }
+    }
+
+    /// Returns the variables this part may use
+    pub fn variables(&self) -> &'static [&'static str] {
+        match self {
+            PromptPart::System => &[],
+            PromptPart::NewContract => &["request"],
+            PromptPart::ChangeContract => &["contract", "request"],
+            PromptPart::Repair => &["errors"],
+            PromptPart::Scoped => &["part", "contract", "request"],
+            PromptPart::Descriptions => &["fields"],
+        }
+    }
+}
+
+/// Editable texts for the prompts sent by the AI section
+///
+/// Texts may contain `{{variable}}` placeholders, filled in by `render`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub id: String,
+    pub name: String,
+    /// Incremented each time the template is saved
+    pub version: u32,
+    /// Built-in templates are defaults that are never edited in place
+    pub built_in: bool,
+    pub system: String,
+    pub new_contract: String,
+    pub change_contract: String,
+    pub repair: String,
+    /// Empty in templates saved before this part existed, until filled from the built-in
+    #[serde(default)]
+    pub scoped: String,
+    #[serde(default)]
+    pub descriptions: String,
+}
+
+impl PromptTemplate {
+    /// Returns the text of one part
+    pub fn part(&self, part: PromptPart) -> &str {
+        match part {
+            PromptPart::System => &self.system,
+            PromptPart::NewContract => &self.new_contract,
+            PromptPart::ChangeContract => &self.change_contract,
+            PromptPart::Repair => &self.repair,
+            PromptPart::Scoped => &self.scoped,
+            PromptPart::Descriptions => &self.descriptions,
+        }
+    }
+
+    /// Replaces the text of one part
+    pub fn set_part(&mut self, part: PromptPart, text: String) {
+        match part {
+            PromptPart::System => self.system = text,
+            PromptPart::NewContract => self.new_contract = text,
+            PromptPart::ChangeContract => self.change_contract = text,
+            PromptPart::Repair => self.repair = text,
+            PromptPart::Scoped => self.scoped = text,
+            PromptPart::Descriptions => self.descriptions = text,
+        }
+    }
+
+    /// Returns true if the name and every part match, regardless of version
+    pub fn same_text(&self, other: &PromptTemplate) -> bool {
+        self.name == other.name
+            && PromptPart::all()
+                .into_iter()
+                .all(|part| self.part(part) == other.part(part))
+    }
+
+    /// Renders one part, replacing `{{name}}` placeholders with the given values
+    ///
+    /// Values are inserted as-is, so placeholders inside them are left alone.
+    pub fn render(&self, part: PromptPart, variables: &[(&str, &str)]) -> String {
+        let mut rendered = String::new();
+        let mut rest = self.part(part);
+
+        while let Some(start) = rest.find("{{") {
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("}}") else {
+                break;
+            };
+            rendered.push_str(&rest[..start]);
+            let name = after[..end].trim();
+            match variables.iter().find(|(variable, _)| *variable == name) {
+                Some((_, value)) => rendered.push_str(value),
+                None => rendered.push_str(&rest[start..start + end + 4]),
+            }
+            rest = &after[end + 2..];
+        }
+
+        rendered.push_str(rest);
+        rendered
+    }
+
+    /// Lists placeholders that a part does not support, such as typos
+    pub fn unknown_variables(&self, part: PromptPart) -> Vec<String> {
+        let mut unknown = Vec::new();
+        let mut rest = self.part(part);
+
+        while let Some(start) = rest.find("{{") {
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("}}") else {
+                break;
+            };
+            let name = after[..end].trim();
+            if !part.variables().contains(&name) && !unknown.iter().any(|u| u == name) {
+                unknown.push(name.to_string());
+            }
+            rest = &after[end + 2..];
+        }
+
+        unknown
+    }
+}
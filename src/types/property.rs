@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+
+use crate::utils::IDENTIFIER_MEDIA_TYPE;
 
 /// Property data types supported by Dash Platform
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -38,12 +41,23 @@ impl DataType {
     }
 }
 
+/// Step in a path from a property down to one nested inside it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PathStep {
+    /// An inner property of an object, by index
+    Property(usize),
+    /// The items schema of an array
+    Items,
+}
+
 /// Property in a document type with validation parameters specific to each data type
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Property {
     pub name: String,
     pub data_type: DataType,
     pub required: bool,
+    /// Listed in the document type's `transient` list, which DPP does not store
+    pub transient: bool,
     pub position: u64,
     pub description: Option<String>,
     pub comment: Option<String>,
@@ -54,12 +68,16 @@ pub struct Property {
     pub pattern: Option<String>,
     pub format: Option<String>,
 
-    // Number/Integer-specific validation
-    pub minimum: Option<i32>,
-    pub maximum: Option<i32>,
+    // Number/Integer-specific validation, kept exactly as written in the contract
+    pub minimum: Option<Number>,
+    pub maximum: Option<Number>,
 
-    // Array-specific validation
+    // String/Number/Integer allowed values
+    pub enum_values: Option<Vec<Value>>,
+
+    // Array-specific validation. Arrays hold bytes unless they have an items schema.
     pub byte_array: Option<bool>,
+    pub items: Option<Box<Property>>,
     pub min_items: Option<u32>,
     pub max_items: Option<u32>,
     pub content_media_type: Option<String>,
@@ -68,8 +86,16 @@ pub struct Property {
     pub properties: Option<Box<Vec<Property>>>,
     pub min_properties: Option<u32>,
     pub max_properties: Option<u32>,
-    pub rec_required: Option<Vec<String>>,
     pub additional_properties: Option<bool>,
+
+    // Document type an identifier property points to, for diagrams only.
+    // Not part of the contract schema.
+    pub ref_document_type: Option<String>,
+
+    // Allowed values as typed, kept while they do not parse for the data type.
+    // Not part of the contract schema.
+    #[serde(skip)]
+    pub enum_input: Option<String>,
 }
 
 impl Property {
@@ -84,19 +110,21 @@ impl Property {
 
     /// Clears validation parameters that don't apply to the current data type
     pub fn clear_invalid_parameters(&mut self) {
+        self.enum_input = None;
         match self.data_type {
             DataType::String => {
                 self.minimum = None;
                 self.maximum = None;
                 self.byte_array = None;
+                self.items = None;
                 self.min_items = None;
                 self.max_items = None;
                 self.content_media_type = None;
                 self.properties = None;
                 self.min_properties = None;
                 self.max_properties = None;
-                self.rec_required = None;
                 self.additional_properties = None;
+                self.ref_document_type = None;
             }
             DataType::Integer | DataType::Number => {
                 self.min_length = None;
@@ -104,61 +132,177 @@ impl Property {
                 self.pattern = None;
                 self.format = None;
                 self.byte_array = None;
+                self.items = None;
                 self.min_items = None;
                 self.max_items = None;
                 self.content_media_type = None;
                 self.properties = None;
                 self.min_properties = None;
                 self.max_properties = None;
-                self.rec_required = None;
                 self.additional_properties = None;
+                self.ref_document_type = None;
             }
             DataType::Array => {
                 self.min_length = None;
                 self.max_length = None;
                 self.pattern = None;
                 self.format = None;
+                self.enum_values = None;
                 self.minimum = None;
                 self.maximum = None;
                 self.properties = None;
                 self.min_properties = None;
                 self.max_properties = None;
-                self.rec_required = None;
                 self.additional_properties = None;
-                self.byte_array = Some(true); // Arrays must be byte arrays in Dash Platform
+                if self.items.is_none() {
+                    self.byte_array = Some(true); // Arrays without items are byte arrays
+                }
             }
             DataType::Object => {
                 self.min_length = None;
                 self.max_length = None;
                 self.pattern = None;
                 self.format = None;
+                self.enum_values = None;
                 self.minimum = None;
                 self.maximum = None;
                 self.byte_array = None;
+                self.items = None;
                 self.min_items = None;
                 self.max_items = None;
                 self.content_media_type = None;
+                self.ref_document_type = None;
             }
             DataType::Boolean => {
                 self.min_length = None;
                 self.max_length = None;
                 self.pattern = None;
                 self.format = None;
+                self.enum_values = None;
                 self.minimum = None;
                 self.maximum = None;
                 self.byte_array = None;
+                self.items = None;
                 self.min_items = None;
                 self.max_items = None;
                 self.content_media_type = None;
                 self.properties = None;
                 self.min_properties = None;
                 self.max_properties = None;
-                self.rec_required = None;
                 self.additional_properties = None;
+                self.ref_document_type = None;
+            }
+        }
+    }
+
+    /// Sets the allowed values from a comma-separated list, typed to match the data type
+    ///
+    /// If a value does not parse, the allowed values are left as they were and the
+    /// text is kept in `enum_input` so it can be corrected.
+    pub fn set_enum_values_from_str(&mut self, values: &str) -> Result<(), String> {
+        match self.parse_enum_values(values) {
+            Ok(parsed) => {
+                self.enum_values = Some(parsed).filter(|parsed| !parsed.is_empty());
+                self.enum_input = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.enum_input = Some(values.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns why the allowed values as typed could not be set, if they could not
+    pub fn enum_input_error(&self) -> Option<String> {
+        self.enum_input
+            .as_deref()
+            .and_then(|values| self.parse_enum_values(values).err())
+    }
+
+    fn parse_enum_values(&self, values: &str) -> Result<Vec<Value>, String> {
+        values
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| match self.data_type {
+                DataType::Integer => v
+                    .parse::<i64>()
+                    .map(Value::from)
+                    .map_err(|_| format!("'{}' is not an integer", v)),
+                DataType::Number => v
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(Number::from_f64)
+                    .map(Value::Number)
+                    .ok_or_else(|| format!("'{}' is not a number", v)),
+                _ => Ok(Value::String(v.to_string())),
+            })
+            .collect()
+    }
+
+    /// Returns the allowed values as a comma-separated list for display,
+    /// or the text as typed while it does not parse
+    pub fn enum_values_display(&self) -> String {
+        if let Some(ref values) = self.enum_input {
+            return values.clone();
+        }
+        self.enum_values
+            .iter()
+            .flatten()
+            .map(|v| match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Returns true if the property is an array of bytes rather than of typed items
+    pub fn is_byte_array(&self) -> bool {
+        self.data_type == DataType::Array && self.items.is_none()
+    }
+
+    /// Makes an array hold bytes (`None`) or items of the given type
+    pub fn set_items(&mut self, data_type: Option<DataType>) {
+        match data_type {
+            None => {
+                self.items = None;
+                self.byte_array = Some(true);
+            }
+            Some(data_type) => {
+                let items = self.items.get_or_insert_with(Default::default);
+                items.data_type = data_type;
+                items.clear_invalid_parameters();
+                self.byte_array = None;
+                // A media type only describes byte arrays
+                self.content_media_type = None;
             }
         }
     }
 
+    /// Follows a path of inner properties and array items down from this property
+    pub fn nested(&self, path: &[PathStep]) -> Option<&Property> {
+        path.iter().try_fold(self, |current, step| match *step {
+            PathStep::Items => current.items.as_deref(),
+            PathStep::Property(index) => current.properties.as_ref()?.get(index),
+        })
+    }
+
+    /// Mutable version of `nested`
+    pub fn nested_mut(&mut self, path: &[PathStep]) -> Option<&mut Property> {
+        path.iter().try_fold(self, |current, step| match *step {
+            PathStep::Items => current.items.as_deref_mut(),
+            PathStep::Property(index) => current.properties.as_mut()?.get_mut(index),
+        })
+    }
+
+    /// Returns true if the property is a byte array with the identifier media type
+    pub fn is_identifier(&self) -> bool {
+        self.data_type == DataType::Array
+            && self.content_media_type.as_deref() == Some(IDENTIFIER_MEDIA_TYPE)
+    }
+
     /// Validates that the property configuration is valid for Dash Platform
     pub fn validate(&self) -> Result<(), String> {
         if self.name.is_empty() {
@@ -176,7 +320,10 @@ impl Property {
                 }
             }
             DataType::Integer | DataType::Number => {
-                if let (Some(min), Some(max)) = (self.minimum, self.maximum) {
+                if let (Some(min), Some(max)) = (
+                    self.minimum.as_ref().and_then(Number::as_f64),
+                    self.maximum.as_ref().and_then(Number::as_f64),
+                ) {
                     if min > max {
                         return Err("Minimum cannot be greater than maximum".to_string());
                     }
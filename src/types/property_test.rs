@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use super::super::property::{DataType, Property};
+    use serde_json::json;
+
+    #[test]
+    fn test_enum_values_typed_to_data_type() {
+        let mut property = Property::new("rating".to_string(), DataType::Integer);
+        assert!(property.set_enum_values_from_str("1, 2,, 3").is_ok());
+        assert_eq!(
+            property.enum_values,
+            Some(vec![json!(1), json!(2), json!(3)])
+        );
+        assert_eq!(property.enum_values_display(), "1, 2, 3");
+
+        assert!(property.set_enum_values_from_str(" ").is_ok());
+        assert_eq!(property.enum_values, None);
+    }
+
+    #[test]
+    fn test_invalid_enum_values_kept_as_typed() {
+        let mut property = Property::new("rating".to_string(), DataType::Number);
+        property.set_enum_values_from_str("0.5, 1").unwrap();
+
+        let error = property.set_enum_values_from_str("0.5, 1, x").unwrap_err();
+        assert_eq!(error, "'x' is not a number");
+        // The previous values stay, and the text stays for correcting
+        assert_eq!(property.enum_values, Some(vec![json!(0.5), json!(1.0)]));
+        assert_eq!(property.enum_values_display(), "0.5, 1, x");
+        assert_eq!(
+            property.enum_input_error().as_deref(),
+            Some("'x' is not a number")
+        );
+
+        property.set_enum_values_from_str("0.5, 2").unwrap();
+        assert_eq!(property.enum_input_error(), None);
+        assert_eq!(property.enum_values_display(), "0.5, 2.0");
+    }
+}
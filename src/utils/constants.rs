@@ -33,3 +33,18 @@ pub const OPENAI_MAX_TOKENS: u32 = 4096;
 
 /// Temperature for OpenAI requests (lower = more deterministic)
 pub const OPENAI_TEMPERATURE: f32 = 0.2;
+
+/// Seconds before a streaming OpenAI request is aborted
+pub const OPENAI_TIMEOUT_SECS: u32 = 180;
+
+/// Latest conversation turns replayed in full; earlier ones are sent as their prompts only
+pub const OPENAI_HISTORY_TURNS: usize = 3;
+
+/// Default number of rounds the AI may take to fix validation errors in its output
+pub const DEFAULT_AI_REPAIR_ROUNDS: u32 = 3;
+
+/// Upper bound on AI repair rounds selectable in the UI
+pub const MAX_AI_REPAIR_ROUNDS: u32 = 5;
+
+/// Content media type marking a 32-byte array as a platform identifier
+pub const IDENTIFIER_MEDIA_TYPE: &str = "application/x.dash.dpp.identifier";
@@ -0,0 +1,29 @@
+use wasm_bindgen::JsCast;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+/// Offers text content to the user as a file download
+pub fn download_text(filename: &str, mime_type: &str, contents: &str) -> Result<(), String> {
+    let parts = js_sys::Array::of1(&contents.into());
+    let mut options = BlobPropertyBag::new();
+    options.type_(mime_type);
+
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options)
+        .map_err(|e| format!("Failed to create file: {:?}", e))?;
+    let url = Url::create_object_url_with_blob(&blob)
+        .map_err(|e| format!("Failed to create download link: {:?}", e))?;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("Failed to obtain document object")?;
+    let anchor: HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(|e| format!("Failed to create download link: {:?}", e))?
+        .dyn_into()
+        .map_err(|_| "Failed to create download link".to_string())?;
+
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+
+    Url::revoke_object_url(&url).map_err(|e| format!("Failed to release download link: {:?}", e))
+}
@@ -1,4 +1,15 @@
 /// Utility functions and helpers
 pub mod constants;
+pub mod download;
+pub mod naming;
+pub mod storage;
+pub mod text_diff;
+
+#[cfg(test)]
+mod text_diff_test;
 
 pub use constants::*;
+pub use download::download_text;
+pub use naming::{to_camel_case, to_pascal_case, to_snake_case};
+pub use storage::{load_local, save_local};
+pub use text_diff::{diff_lines, with_context, DiffLine};
@@ -0,0 +1,18 @@
+/// Reads a value saved in the browser's local storage
+pub fn load_local(key: &str) -> Option<String> {
+    web_sys::window()?
+        .local_storage()
+        .ok()??
+        .get_item(key)
+        .ok()?
+}
+
+/// Saves a value to the browser's local storage
+pub fn save_local(key: &str, value: &str) -> Result<(), String> {
+    let storage = web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or("Local storage is not available")?;
+    storage
+        .set_item(key, value)
+        .map_err(|e| format!("Failed to save to local storage: {:?}", e))
+}
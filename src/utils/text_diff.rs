@@ -0,0 +1,135 @@
+/// A line in a diff between two texts
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+impl DiffLine {
+    /// Returns true for added or removed lines
+    pub fn is_change(&self) -> bool {
+        !matches!(self, DiffLine::Same(_))
+    }
+
+    /// Returns the line with a `+`, `-` or space prefix
+    pub fn to_prefixed(&self) -> String {
+        match self {
+            DiffLine::Same(line) => format!("  {}", line),
+            DiffLine::Added(line) => format!("+ {}", line),
+            DiffLine::Removed(line) => format!("- {}", line),
+        }
+    }
+}
+
+/// Largest table the line diff builds; beyond it, the changed region is shown
+/// as removed and then added rather than aligned line by line
+const MAX_DIFF_CELLS: usize = 1 << 20;
+
+/// Diffs two texts line by line using their longest common subsequence
+///
+/// Lines shared at the start and end are matched first, so the table only
+/// covers the region in between.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut diff: Vec<DiffLine> = old[..prefix]
+        .iter()
+        .map(|line| DiffLine::Same(line.to_string()))
+        .collect();
+    diff_middle(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+        &mut diff,
+    );
+    diff.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| DiffLine::Same(line.to_string())),
+    );
+
+    diff
+}
+
+/// Diffs the lines between the common prefix and suffix
+fn diff_middle(old: &[&str], new: &[&str], diff: &mut Vec<DiffLine>) {
+    let width = new.len() + 1;
+    let cells = (old.len() + 1).saturating_mul(width);
+
+    // lcs[i * width + j] is the common subsequence length of old[i..] and new[j..]
+    let mut lcs = Vec::new();
+    if cells <= MAX_DIFF_CELLS {
+        lcs = vec![0u32; cells];
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                lcs[i * width + j] = if old[i] == new[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while !lcs.is_empty() && i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push(DiffLine::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+            diff.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    diff.extend(
+        old[i..]
+            .iter()
+            .map(|line| DiffLine::Removed(line.to_string())),
+    );
+    diff.extend(
+        new[j..]
+            .iter()
+            .map(|line| DiffLine::Added(line.to_string())),
+    );
+}
+
+/// Keeps changed lines and up to `context` unchanged lines around them
+pub fn with_context(diff: &[DiffLine], context: usize) -> Vec<Option<&DiffLine>> {
+    let changes: Vec<usize> = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.is_change())
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut output = Vec::new();
+    let mut skipped = false;
+    for (i, line) in diff.iter().enumerate() {
+        let near_change = changes
+            .iter()
+            .any(|&c| i + context >= c && i <= c + context);
+        if near_change {
+            output.push(Some(line));
+            skipped = false;
+        } else if !skipped {
+            // `None` marks a run of omitted unchanged lines
+            output.push(None);
+            skipped = true;
+        }
+    }
+
+    output
+}
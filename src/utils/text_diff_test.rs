@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use super::super::text_diff::{diff_lines, with_context, DiffLine};
+
+    fn same(line: &str) -> DiffLine {
+        DiffLine::Same(line.to_string())
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("a\nb\nc\nd", "a\nc\nx\nd\ne");
+
+        assert_eq!(
+            diff,
+            vec![
+                same("a"),
+                DiffLine::Removed("b".to_string()),
+                same("c"),
+                DiffLine::Added("x".to_string()),
+                same("d"),
+                DiffLine::Added("e".to_string()),
+            ]
+        );
+        assert_eq!(
+            diff.iter().map(DiffLine::to_prefixed).collect::<Vec<_>>(),
+            ["  a", "- b", "  c", "+ x", "  d", "+ e"]
+        );
+
+        assert!(diff_lines("a\nb", "a\nb")
+            .iter()
+            .all(|line| !line.is_change()));
+        assert_eq!(
+            diff_lines("a\nb", ""),
+            vec![
+                DiffLine::Removed("a".to_string()),
+                DiffLine::Removed("b".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_large_texts() {
+        let old: Vec<String> = (0..5000).map(|i| format!("line {}", i)).collect();
+        let mut new = old.clone();
+        new[2500] = "changed".to_string();
+
+        // Only the changed region is aligned, however long the shared lines around it
+        let diff = diff_lines(&old.join("\n"), &new.join("\n"));
+        assert_eq!(diff.len(), 5001);
+        let changes: Vec<&DiffLine> = diff.iter().filter(|line| line.is_change()).collect();
+        assert_eq!(
+            changes,
+            [
+                &DiffLine::Removed("line 2500".to_string()),
+                &DiffLine::Added("changed".to_string())
+            ]
+        );
+
+        // A rewrite too large to align is shown as removed and then added
+        let rewritten: Vec<String> = (0..5000).map(|i| format!("row {}", i)).collect();
+        let diff = diff_lines(&old.join("\n"), &rewritten.join("\n"));
+        assert_eq!(diff.len(), 10000);
+        assert_eq!(diff[0], DiffLine::Removed("line 0".to_string()));
+        assert_eq!(diff[5000], DiffLine::Added("row 0".to_string()));
+    }
+
+    #[test]
+    fn test_with_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9";
+        let new = "1\n2\n3\nfour\n5\n6\n7\n8\nnine";
+        let diff = diff_lines(old, new);
+
+        let lines: Vec<Option<String>> = with_context(&diff, 1)
+            .into_iter()
+            .map(|line| line.map(DiffLine::to_prefixed))
+            .collect();
+        let line = |text: &str| Some(text.to_string());
+        assert_eq!(
+            lines,
+            vec![
+                None,
+                line("  3"),
+                line("- 4"),
+                line("+ four"),
+                line("  5"),
+                None,
+                line("  8"),
+                line("- 9"),
+                line("+ nine"),
+            ]
+        );
+
+        // Without changes every line is omitted behind a single gap
+        assert_eq!(with_context(&diff_lines(old, old), 2), vec![None]);
+    }
+}